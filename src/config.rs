@@ -1,31 +1,194 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::error::GenError;
+
+/// 消息结构体默认的 derive 列表
+const DEFAULT_DERIVES: [&str; 6] = [
+    "Debug",
+    "Clone",
+    "PartialEq",
+    "Deserialize",
+    "Serialize",
+    "Validate",
+];
+
 /// 配置结构
-#[derive(Debug, Clone)]
+///
+/// 也可从 TOML 文件加载（键名与字段名相同，缺省键取默认值），见 [`Config::from_file`]。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     pub schema_dir: String,
     pub output_dir: String,
     pub generate_mod_file: bool,
     pub show_statistics: bool,
+    pub validate_fast: bool,
+    pub strip_suffix: Option<String>, // 文件名规范化正则（如去除版本后缀）
+    pub generate_from_str: bool,
+    pub strict: bool, // 严格模式：schema 问题视为错误而非警告
+    pub enum_sets: bool,
+    pub max_line_length: usize, // 生成代码的最大行宽（文档注释与函数签名）
+    pub default_string_max: Option<u32>, // 未声明 maxLength 的字符串默认最大长度，None 表示不限制
+    pub skip_deprecated_exports: bool,
+    pub openapi: Option<String>, // OpenAPI 文档路径，设置后从 components.schemas 生成
+    pub iter_accessors: bool,
+    pub datetime_as_string: Vec<String>, // 保持为 String 的 date-time 字段（field 或 Struct.field）
+    pub include: Vec<String>,            // 只处理相对路径匹配任一 glob 的 schema 文件
+    pub exclude: Vec<String>, // 跳过相对路径匹配任一 glob 的 schema 文件（优先于 include）
+    pub type_overrides: Vec<String>, // 字段类型覆盖（field=Type 或 Struct.field=Type）
+    pub no_value_fallback: bool, // 禁止回退到 serde_json::Value
+    pub validation_messages: bool, // 为验证属性生成自定义错误信息
+    pub message_type_ids: bool, // 生成 OCPP-J 消息类型编号常量
+    pub ocpp_j: bool,         // 生成将消息包装为 OCPP-J Call/CallResult 帧的 to_call/to_call_result
+    pub append_only: bool,    // 只写入新的输出文件，保留已存在的文件
+    pub force: bool,          // 忽略生成清单，重写所有输出文件
+    pub emit_constants_module: bool, // 将所有字段限制汇总输出到 constants.rs
+    pub newtype_scalars: bool, // 为受约束的标量定义生成新类型包装
+    pub iter_fields: Vec<String>, // IntoIterator 代理字段（Struct.field 或 Struct）
+    pub emit_validate_dispatch: bool, // 生成按 action 名称验证负载的 dispatch.rs
+    pub emit_action_registry: bool, // 生成列出所有 action 的 actions.rs
+    pub derive_default: bool, // 为字段均有已知默认值的结构体生成 Default 实现
+    pub builders: bool,       // 为每个结构体生成检查必填字段的 FooBuilder
+    pub format_types: bool,   // uuid 映射为 uuid::Uuid，uri 生成 #[validate(url)]
+    pub display: bool,        // 生成输出紧凑 JSON 的 Display 实现
+    pub rustfmt: bool,        // 写入前通过 rustfmt 格式化生成的消息文件
+    pub header: bool,         // 在生成的文件开头写明生成工具、版本与来源 schema
+    pub doc_examples: bool,   // 将 schema 的 examples 写入文档注释，消息示例生成反序列化文档测试
+    pub checked_setters: bool, // 有约束字段的 setter 先检查约束并返回 Result
+    pub try_new: bool,        // 生成构造后立即验证的 try_new
+    pub examples: bool,       // 生成返回满足约束的示例实例的 example()
+    pub try_from_value: bool, // 为消息生成先反序列化再验证的 TryFrom<serde_json::Value>
+    pub fail_fast: bool,      // 遇到第一个失败的 schema 文件即中止
+    pub allow_duplicates: bool, // 同名消息 schema 以后出现者为准，而不是报错
+    pub share_helpers: bool,  // 多个消息中结构相同的内联辅助类型只生成一次，放入 shared.rs
+    pub local_definitions: bool, // 引用本文件 definitions 的类型在消息文件中生成，而不是从共用的 datatypes 模块导入
+    pub deny_unknown_fields: bool, // 结构体拒绝未声明的字段（允许额外属性的对象与 CustomDataType 除外）
+    pub single_file: bool,         // 将所有消息写入单个 messages.rs，不生成 mod.rs
+    pub dry_run: bool,             // 不写入文件，打印将要变化的文件及差异
+    pub gen_tests: bool,           // 为每个消息文件生成 JSON 往返序列化测试
+    pub gen_proptest: bool,        // 为每个结构体生成满足字段约束的 proptest 策略
+    pub into_setters: bool,        // String/Vec 的 setter 接受 impl Into<T>
+    pub repr_c: bool,              // 为仅含 FFI 安全标量字段的结构体生成 #[repr(C)]
+    pub feature_gates: bool,       // 按 schema 子目录为模块生成 cargo feature 门控
+    pub rename_struct: Option<String>, // 结构体重命名映射文件（JSON 对象：schema 名 => Rust 名）
+    pub type_map: Option<String>,  // 引用类型映射文件（JSON 或 TOML：类型名 => TypeMapping）
+    pub ocpp_version: String,      // 引用类型导入路径中的版本模块（如 v2_1、v2_0_1、v1_6）
+    pub jobs: usize,               // 并行解析与生成的线程数，0 表示使用全部 CPU 核心
+    pub derives: Vec<String>,      // 消息结构体的 derive 列表（Serialize/Deserialize 总会补齐）
+    pub enum_all: bool,            // 生成的枚举带有列出全部变体的 ALL 常量
+    pub enum_default_first: bool,  // schema 未给出 default 的枚举以第一个变体实现 Default
+    pub enum_str_eq: bool,         // 生成的枚举可直接与线上字符串（&str、String）比较
+    pub quiet: bool,               // 只输出错误日志
+    pub verbose: bool,             // 额外输出 debug 日志
 }
 
-impl Config {
+impl Default for Config {
     /// 创建默认配置
-    pub fn default() -> Self {
+    fn default() -> Self {
         Self {
             schema_dir: "../tests/schema_validation/schemas/v2.1".to_string(),
             output_dir: "../v2_1/messages".to_string(),
             generate_mod_file: true, // 默认生成 mod.rs 文件
             show_statistics: true,
+            validate_fast: false,
+            strip_suffix: None,
+            generate_from_str: false,
+            strict: false,
+            enum_sets: false,
+            max_line_length: 100,
+            default_string_max: Some(512),
+            skip_deprecated_exports: false,
+            openapi: None,
+            iter_accessors: false,
+            datetime_as_string: Vec::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            type_overrides: Vec::new(),
+            no_value_fallback: false,
+            validation_messages: false,
+            message_type_ids: false,
+            ocpp_j: false,
+            append_only: false,
+            force: false,
+            emit_constants_module: false,
+            newtype_scalars: false,
+            iter_fields: Vec::new(),
+            emit_validate_dispatch: false,
+            emit_action_registry: false,
+            derive_default: false,
+            builders: false,
+            format_types: false,
+            display: false,
+            rustfmt: true,
+            header: true,
+            doc_examples: false,
+            checked_setters: false,
+            try_new: false,
+            examples: false,
+            try_from_value: false,
+            fail_fast: false,
+            allow_duplicates: false,
+            share_helpers: false,
+            local_definitions: false,
+            deny_unknown_fields: false,
+            single_file: false,
+            dry_run: false,
+            gen_tests: false,
+            gen_proptest: false,
+            into_setters: false,
+            repr_c: false,
+            feature_gates: false,
+            rename_struct: None,
+            type_map: None,
+            ocpp_version: "v2_1".to_string(),
+            jobs: 0,
+            derives: DEFAULT_DERIVES.iter().map(|d| d.to_string()).collect(),
+            enum_all: false,
+            enum_default_first: false,
+            enum_str_eq: false,
+            quiet: false,
+            verbose: false,
         }
     }
+}
 
+impl Config {
     /// 从命令行参数创建配置
+    ///
+    /// 优先级：命令行参数 > 环境变量（见 [`Config::apply_env`]）> `--config` 文件 > 默认值。
     pub fn from_args() -> Self {
         let args: Vec<String> = std::env::args().collect();
-        let mut config = Self::default();
+
+        // 先加载配置文件，其余参数再覆盖文件中的值
+        let mut config = match args.iter().position(|arg| arg == "--config") {
+            Some(index) => match args.get(index + 1) {
+                Some(path) => Self::from_file(path).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }),
+                None => {
+                    eprintln!("Error: --config requires a value");
+                    std::process::exit(1);
+                }
+            },
+            None => Self::default(),
+        };
+
+        if let Err(e) = config.apply_env(|name| std::env::var(name).ok()) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
 
         let mut i = 1;
         while i < args.len() {
             match args[i].as_str() {
+                "--config" => {
+                    // 已在解析开始前加载
+                    i += 2;
+                }
                 "--schema-dir" => {
                     if i + 1 < args.len() {
                         config.schema_dir = args[i + 1].clone();
@@ -56,6 +219,336 @@ impl Config {
                     config.show_statistics = false;
                     i += 1;
                 }
+                "--validate-fast" => {
+                    config.validate_fast = true;
+                    i += 1;
+                }
+                "--strip-suffix" => {
+                    if i + 1 < args.len() {
+                        config.strip_suffix = Some(args[i + 1].clone());
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --strip-suffix requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--include" => {
+                    if i + 1 < args.len() {
+                        config.include.push(args[i + 1].clone());
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --include requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--exclude" => {
+                    if i + 1 < args.len() {
+                        config.exclude.push(args[i + 1].clone());
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --exclude requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--from-str" => {
+                    config.generate_from_str = true;
+                    i += 1;
+                }
+                "--strict" => {
+                    config.strict = true;
+                    i += 1;
+                }
+                "--enum-sets" => {
+                    config.enum_sets = true;
+                    i += 1;
+                }
+                "--max-line-length" => match args.get(i + 1).and_then(|v| v.parse().ok()) {
+                    Some(value) => {
+                        config.max_line_length = value;
+                        i += 2;
+                    }
+                    None => {
+                        eprintln!("Error: --max-line-length requires a numeric value");
+                        std::process::exit(1);
+                    }
+                },
+                "--default-string-max" => match args.get(i + 1).and_then(|v| v.parse().ok()) {
+                    Some(value) => {
+                        config.default_string_max = Some(value);
+                        i += 2;
+                    }
+                    None => {
+                        eprintln!("Error: --default-string-max requires a numeric value");
+                        std::process::exit(1);
+                    }
+                },
+                "--no-default-string-max" => {
+                    config.default_string_max = None;
+                    i += 1;
+                }
+                "--skip-deprecated-exports" => {
+                    config.skip_deprecated_exports = true;
+                    i += 1;
+                }
+                "--openapi" => {
+                    if i + 1 < args.len() {
+                        config.openapi = Some(args[i + 1].clone());
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --openapi requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--iter-accessors" => {
+                    config.iter_accessors = true;
+                    i += 1;
+                }
+                "--datetime-as-string" => {
+                    if i + 1 < args.len() {
+                        config.datetime_as_string = args[i + 1]
+                            .split(',')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect();
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --datetime-as-string requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--type-override" => {
+                    if i + 1 < args.len() {
+                        config.type_overrides = args[i + 1]
+                            .split(',')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect();
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --type-override requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--no-value-fallback" => {
+                    config.no_value_fallback = true;
+                    i += 1;
+                }
+                "--validation-messages" => {
+                    config.validation_messages = true;
+                    i += 1;
+                }
+                "--message-type-ids" => {
+                    config.message_type_ids = true;
+                    i += 1;
+                }
+                "--ocpp-j" => {
+                    config.ocpp_j = true;
+                    i += 1;
+                }
+                "--append-only" => {
+                    config.append_only = true;
+                    i += 1;
+                }
+                "--emit-constants-module" => {
+                    config.emit_constants_module = true;
+                    i += 1;
+                }
+                "--newtype-scalars" => {
+                    config.newtype_scalars = true;
+                    i += 1;
+                }
+                "--iter-field" => {
+                    if i + 1 < args.len() {
+                        config.iter_fields = args[i + 1]
+                            .split(',')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect();
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --iter-field requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--emit-validate-dispatch" => {
+                    config.emit_validate_dispatch = true;
+                    i += 1;
+                }
+                "--derive-default" => {
+                    config.derive_default = true;
+                    i += 1;
+                }
+                "--builders" => {
+                    config.builders = true;
+                    i += 1;
+                }
+                "--format-types" => {
+                    config.format_types = true;
+                    i += 1;
+                }
+                "--display" => {
+                    config.display = true;
+                    i += 1;
+                }
+                "--no-fmt" => {
+                    config.rustfmt = false;
+                    i += 1;
+                }
+                "--no-header" => {
+                    config.header = false;
+                    i += 1;
+                }
+                "--doc-examples" => {
+                    config.doc_examples = true;
+                    i += 1;
+                }
+                "--checked-setters" => {
+                    config.checked_setters = true;
+                    i += 1;
+                }
+                "--try-new" => {
+                    config.try_new = true;
+                    i += 1;
+                }
+                "--examples" => {
+                    config.examples = true;
+                    i += 1;
+                }
+                "--try-from-value" => {
+                    config.try_from_value = true;
+                    i += 1;
+                }
+                "--fail-fast" => {
+                    config.fail_fast = true;
+                    i += 1;
+                }
+                "--emit-action-registry" => {
+                    config.emit_action_registry = true;
+                    i += 1;
+                }
+                "--force" => {
+                    config.force = true;
+                    i += 1;
+                }
+                "--allow-duplicates" => {
+                    config.allow_duplicates = true;
+                    i += 1;
+                }
+                "--share-helpers" => {
+                    config.share_helpers = true;
+                    i += 1;
+                }
+                "--deny-unknown-fields" => {
+                    config.deny_unknown_fields = true;
+                    i += 1;
+                }
+                "--local-definitions" => {
+                    config.local_definitions = true;
+                    i += 1;
+                }
+                "--single-file" => {
+                    config.single_file = true;
+                    i += 1;
+                }
+                "--dry-run" => {
+                    config.dry_run = true;
+                    i += 1;
+                }
+                "--gen-tests" => {
+                    config.gen_tests = true;
+                    i += 1;
+                }
+                "--gen-proptest" => {
+                    config.gen_proptest = true;
+                    i += 1;
+                }
+                "--into-setters" => {
+                    config.into_setters = true;
+                    i += 1;
+                }
+                "--repr-c" => {
+                    config.repr_c = true;
+                    i += 1;
+                }
+                "--feature-gates" => {
+                    config.feature_gates = true;
+                    i += 1;
+                }
+                "-q" | "--quiet" => {
+                    config.quiet = true;
+                    i += 1;
+                }
+                "-v" | "--verbose" => {
+                    config.verbose = true;
+                    i += 1;
+                }
+                "--type-map" => {
+                    if i + 1 < args.len() {
+                        config.type_map = Some(args[i + 1].clone());
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --type-map requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--rename-struct" => {
+                    if i + 1 < args.len() {
+                        config.rename_struct = Some(args[i + 1].clone());
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --rename-struct requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--ocpp-version" => match args.get(i + 1).and_then(|v| version_module(v)) {
+                    Some(module) => {
+                        config.ocpp_version = module;
+                        i += 2;
+                    }
+                    None => {
+                        eprintln!("Error: --ocpp-version requires an OCPP version such as 1.6, 2.0.1 or 2.1");
+                        std::process::exit(1);
+                    }
+                },
+                "--jobs" => match args.get(i + 1).and_then(|v| v.parse().ok()) {
+                    Some(value) => {
+                        config.jobs = value;
+                        i += 2;
+                    }
+                    None => {
+                        eprintln!("Error: --jobs requires a numeric value");
+                        std::process::exit(1);
+                    }
+                },
+                "--derives" => {
+                    if i + 1 < args.len() {
+                        config.derives = args[i + 1]
+                            .split(',')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect();
+                        i += 2;
+                    } else {
+                        eprintln!("Error: --derives requires a value");
+                        std::process::exit(1);
+                    }
+                }
+                "--enum-all" => {
+                    config.enum_all = true;
+                    i += 1;
+                }
+                "--enum-default-first" => {
+                    config.enum_default_first = true;
+                    i += 1;
+                }
+                "--enum-str-eq" => {
+                    config.enum_str_eq = true;
+                    i += 1;
+                }
+                "--version" | "-V" => {
+                    println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+                    std::process::exit(0);
+                }
                 "--help" | "-h" => {
                     Self::print_help();
                     std::process::exit(0);
@@ -71,6 +564,64 @@ impl Config {
         config
     }
 
+    /// 从 TOML 配置文件（如 gen.toml）加载配置
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config file '{}': {}", path, e))?;
+        let mut config: Self = toml::from_str(&content)
+            .map_err(|e| format!("Invalid config file '{}': {}", path, e))?;
+        // ocpp_version 与 --ocpp-version 一样接受版本号（如 2.0.1），无效值留给 validate 报告
+        if let Some(module) = version_module(&config.ocpp_version) {
+            config.ocpp_version = module;
+        }
+        Ok(config)
+    }
+
+    /// 应用 GEN_* 环境变量，`lookup` 按变量名返回其值
+    ///
+    /// 支持的变量：GEN_SCHEMA_DIR、GEN_OUTPUT_DIR、GEN_MOD_FILE、GEN_SHOW_STATS、
+    /// GEN_STRIP_SUFFIX、GEN_OPENAPI、GEN_MAX_LINE_LENGTH、GEN_STRICT。
+    /// 布尔变量接受 1/0、true/false、yes/no。
+    pub fn apply_env(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        let flag = |name: &str| -> Result<Option<bool>, String> {
+            match lookup(name).as_deref() {
+                None => Ok(None),
+                Some("1" | "true" | "yes") => Ok(Some(true)),
+                Some("0" | "false" | "no") => Ok(Some(false)),
+                Some(other) => Err(format!("{} must be a boolean, got '{}'", name, other)),
+            }
+        };
+
+        if let Some(value) = lookup("GEN_SCHEMA_DIR") {
+            self.schema_dir = value;
+        }
+        if let Some(value) = lookup("GEN_OUTPUT_DIR") {
+            self.output_dir = value;
+        }
+        if let Some(value) = flag("GEN_MOD_FILE")? {
+            self.generate_mod_file = value;
+        }
+        if let Some(value) = flag("GEN_SHOW_STATS")? {
+            self.show_statistics = value;
+        }
+        if let Some(value) = lookup("GEN_STRIP_SUFFIX") {
+            self.strip_suffix = Some(value);
+        }
+        if let Some(value) = lookup("GEN_OPENAPI") {
+            self.openapi = Some(value);
+        }
+        if let Some(value) = lookup("GEN_MAX_LINE_LENGTH") {
+            self.max_line_length = value
+                .parse()
+                .map_err(|_| format!("GEN_MAX_LINE_LENGTH must be a number, got '{}'", value))?;
+        }
+        if let Some(value) = flag("GEN_STRICT")? {
+            self.strict = value;
+        }
+
+        Ok(())
+    }
+
     /// 打印帮助信息
     pub fn print_help() {
         println!("OCPP v2.1 Message Generator");
@@ -79,6 +630,9 @@ impl Config {
         println!("    gen_messages [OPTIONS]");
         println!();
         println!("OPTIONS:");
+        println!(
+            "    --config <FILE>       Load options from a TOML file; other flags override it"
+        );
         println!("    --schema-dir <DIR>    Schema files directory (default: ../tests/schema_validation/schemas/v2.1)");
         println!(
             "    --output-dir <DIR>    Output directory (default: ../generated/v2_1/messages)"
@@ -86,18 +640,537 @@ impl Config {
         println!("    --mod-file            Generate mod.rs file (default)");
         println!("    --no-mod-file         Don't generate mod.rs file");
         println!("    --no-stats            Don't show statistics");
+        println!("    --validate-fast       Generate validate_fast() that stops at the first invalid field");
+        println!("    --strip-suffix <REGEX>  Strip matches of REGEX from schema filenames (e.g. '_v\\d+p\\d+$')");
+        println!("    --include <GLOB>      Only process schemas whose path under the schema dir matches (repeatable)");
+        println!("    --exclude <GLOB>      Skip schemas whose path under the schema dir matches; wins over --include");
+        println!("    --from-str            Generate FromStr impls that parse and validate JSON");
+        println!(
+            "    --strict              Treat schema inconsistencies as errors instead of warnings"
+        );
+        println!("    --enum-sets           Generate HashSet fields for arrays of enums with uniqueItems");
+        println!("    --max-line-length <N> Wrap generated doc comments and signatures at N columns (default: 100)");
+        println!("    --default-string-max <N>  Max length checked on strings without maxLength (default: 512)");
+        println!("    --no-default-string-max   Leave strings without maxLength unchecked");
+        println!(
+            "    --skip-deprecated-exports  Leave deprecated messages out of mod.rs re-exports"
+        );
+        println!(
+            "    --openapi <FILE>      Generate from components.schemas of an OpenAPI 3 document"
+        );
+        println!("    --iter-accessors      Generate iter_<field>() accessors for array fields");
+        println!("    --datetime-as-string <LIST>  Keep these date-time fields as String (comma-separated field or Struct.field)");
+        println!("    --type-override <LIST>  Force field types (comma-separated field=Type or Struct.field=Type)");
+        println!("                          Overridden fields skip validation, except number fields forced to f32/f64");
+        println!("    --no-value-fallback   Fail instead of generating serde_json::Value for untyped fields");
+        println!("    --validation-messages  Add descriptive message = \"...\" to generated validate attributes");
+        println!("    --message-type-ids    Generate MESSAGE_TYPE_ID constants (2 for requests, 3 for responses)");
+        println!("    --ocpp-j              Generate to_call()/to_call_result(), wrapping messages in OCPP-J frames");
+        println!("    --append-only         Only write new message files; leave existing output files untouched");
+        println!("    --emit-constants-module  Collect all schema limits into constants.rs");
+        println!("    --newtype-scalars     Generate validated newtype wrappers for constrained scalar definitions");
+        println!("    --iter-field <LIST>   Implement IntoIterator/iter() via a Vec field (Struct.field, or Struct for its only Vec)");
+        println!("    --emit-validate-dispatch  Generate dispatch.rs with validate_message(json, action)");
+        println!("    --derive-default      Generate Default impls for structs whose fields all have defaults");
+        println!("    --builders            Generate a FooBuilder per struct whose build() checks required fields");
+        println!("    --format-types        Map uuid formats to uuid::Uuid and validate uri formats as URLs");
+        println!(
+            "    --display             Implement Display by writing the message as compact JSON"
+        );
+        println!("    --no-fmt              Write generated files as-is instead of piping them through rustfmt");
+        println!("    --no-header           Omit the \"generated by\" comment naming the tool version and source schemas");
+        println!("    --doc-examples        Show schema examples in doc comments, with a no_run doc test per message example");
+        println!("    --checked-setters     Make setters of constrained fields check the value and return Result");
+        println!("    --try-new             Generate try_new(), which validates the message it constructs");
+        println!("    --examples            Generate example(), returning an instance whose sample values satisfy the schema");
+        println!("                          (referenced datatypes must provide example() too, e.g. generated with --examples)");
+        println!("    --try-from-value      Implement TryFrom<serde_json::Value> for messages, deserializing then validating");
+        println!("    --fail-fast           Abort on the first schema file that fails instead of skipping it");
+        println!("    --emit-action-registry  Write actions.rs with an Action enum of every generated message");
+        println!("    --force               Rewrite every output file, even if unchanged since the last run");
+        println!("    --allow-duplicates    Let a later schema replace an earlier one with the same message name");
+        println!("    --share-helpers       Generate inline helper types repeated across messages once, in shared.rs");
+        println!("    --deny-unknown-fields  Reject unknown JSON fields, except in objects allowing additionalProperties and customData");
+        println!("    --local-definitions   Generate $refs to the schema's own definitions in the message file instead of importing them");
+        println!("                          from the shared datatypes module (the default, which keeps one type per OCPP datatype)");
+        println!("    --single-file         Write every message into one messages.rs instead of a file per message");
+        println!("    --dry-run             Print a diff of files that would change without writing; exit 1 if any would");
+        println!("    --gen-tests           Add a #[cfg(test)] JSON round-trip test to each message file");
+        println!("    --gen-proptest        Add #[cfg(test)] proptest strategies generating valid values of each struct (needs proptest)");
+        println!("    --into-setters        Make String/Vec setters and with_* methods take impl Into<T>");
+        println!("    --repr-c              Emit #[repr(C)] on structs whose fields are all FFI-safe scalars");
+        println!("    --feature-gates       Gate mod.rs entries behind a cargo feature named after the schema subfolder");
+        println!(
+            "    --rename-struct <FILE>  JSON object mapping schema names to Rust struct names"
+        );
+        println!("    --type-map <FILE>     JSON or TOML file mapping referenced type names to {{ import, rust_type, needs_validation }}");
+        println!("    --ocpp-version <VERSION>  OCPP version whose module hosts referenced types, e.g. 1.6, 2.0.1 (default: 2.1)");
+        println!(
+            "    --jobs <N>            Parse and generate on N threads (default: 0 = all cores)"
+        );
+        println!("    --derives <LIST>      Derives for message structs (default: Debug,Clone,PartialEq,Deserialize,Serialize,Validate)");
+        println!("    --enum-all            Give generated enums a `pub const ALL: &[Self]` listing every variant");
+        println!("    --enum-default-first  Default enums without a schema default to their first variant");
+        println!("    --enum-str-eq         Let generated enums compare equal to their wire value as &str or String");
+        println!("    -q, --quiet           Only log errors");
+        println!(
+            "    -v, --verbose         Also log debug messages, such as each schema file parsed"
+        );
+        println!("    -V, --version         Print the tool version");
         println!("    -h, --help            Print help information");
+        println!();
+        println!("ENVIRONMENT (used when the corresponding flag is not given):");
+        println!("    GEN_SCHEMA_DIR, GEN_OUTPUT_DIR, GEN_STRIP_SUFFIX, GEN_OPENAPI, GEN_MAX_LINE_LENGTH");
+        println!("    GEN_MOD_FILE, GEN_SHOW_STATS, GEN_STRICT  (1/0, true/false, yes/no)");
+        println!("    RUST_LOG              Log filter applied after --quiet/--verbose, e.g. ocpp_messages_gen_tool::parser=debug");
     }
 
     /// 验证配置
     pub fn validate(&self) -> Result<(), String> {
-        if !std::path::Path::new(&self.schema_dir).exists() {
+        if let Some(openapi) = &self.openapi {
+            if !std::path::Path::new(openapi).exists() {
+                return Err(format!("OpenAPI document does not exist: {}", openapi));
+            }
+        } else if !std::path::Path::new(&self.schema_dir).exists() {
             return Err(format!(
                 "Schema directory does not exist: {}",
                 self.schema_dir
             ));
         }
 
+        self.strip_suffix_regex()
+            .map_err(|e| format!("Invalid --strip-suffix pattern: {}", e))?;
+
+        self.schema_filters()
+            .map_err(|e| format!("Invalid --include/--exclude pattern: {}", e))?;
+
+        if let Some(entry) = self.type_overrides.iter().find(|e| !e.contains('=')) {
+            return Err(format!(
+                "Invalid --type-override entry '{}': expected field=Type",
+                entry
+            ));
+        }
+
+        self.struct_renames()
+            .map_err(|e| format!("Invalid --rename-struct mapping: {}", e))?;
+
+        self.type_mappings()
+            .map_err(|e| format!("Invalid --type-map file: {}", e))?;
+
+        if version_module(&self.ocpp_version).as_deref() != Some(self.ocpp_version.as_str()) {
+            return Err(format!(
+                "Invalid ocpp_version '{}': expected an OCPP version such as 1.6, 2.0.1 or 2.1",
+                self.ocpp_version
+            ));
+        }
+
+        self.validate_derives()?;
+
+        if self.quiet && self.verbose {
+            return Err("--quiet cannot be combined with --verbose".to_string());
+        }
+
+        // 这些选项生成按模块路径引用消息的文件，或为每个消息生成同名的测试模块
+        if self.single_file {
+            let flags = [
+                ("--emit-validate-dispatch", self.emit_validate_dispatch),
+                ("--emit-action-registry", self.emit_action_registry),
+                ("--feature-gates", self.feature_gates),
+                ("--gen-tests", self.gen_tests),
+                ("--gen-proptest", self.gen_proptest),
+            ];
+            if let Some((flag, _)) = flags.iter().find(|(_, enabled)| *enabled) {
+                return Err(format!("--single-file cannot be combined with {}", flag));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 检查 derive 列表与其他选项的组合是否可以编译
+    fn validate_derives(&self) -> Result<(), String> {
+        let requires = [
+            ("Eq", "PartialEq"),
+            ("PartialOrd", "PartialEq"),
+            ("Ord", "Eq"),
+            ("Ord", "PartialOrd"),
+        ];
+        for (derive, required) in requires {
+            if self.has_derive(derive) && !self.has_derive(required) {
+                return Err(format!(
+                    "Invalid --derives: {} requires {} to be derived as well",
+                    derive, required
+                ));
+            }
+        }
+
+        if self.has_derive("Default") && self.derive_default {
+            return Err(
+                "Invalid --derives: Default conflicts with the impl generated by --derive-default"
+                    .to_string(),
+            );
+        }
+
+        if self.gen_tests && !self.has_derive("PartialEq") {
+            return Err(
+                "Invalid --derives: --gen-tests compares decoded messages and requires PartialEq to be derived"
+                    .to_string(),
+            );
+        }
+
+        if self.gen_proptest && !self.has_derive("Debug") {
+            return Err(
+                "Invalid --derives: --gen-proptest requires Debug, which proptest needs for generated values"
+                    .to_string(),
+            );
+        }
+
+        // 这些生成的代码会调用 validate() 或 #[validate] 使用的辅助函数
+        if !self.has_derive("Validate") {
+            let flags = [
+                ("--from-str", self.generate_from_str),
+                ("--builders", self.builders),
+                ("--emit-validate-dispatch", self.emit_validate_dispatch),
+                ("--checked-setters", self.checked_setters),
+                ("--try-new", self.try_new),
+                ("--try-from-value", self.try_from_value),
+            ];
+            if let Some((flag, _)) = flags.iter().find(|(_, enabled)| *enabled) {
+                return Err(format!(
+                    "Invalid --derives: {} relies on validation code and requires Validate to be derived",
+                    flag
+                ));
+            }
+        }
+
         Ok(())
     }
+
+    /// 判断消息结构体是否派生指定的 trait
+    pub fn has_derive(&self, derive: &str) -> bool {
+        self.derives.iter().any(|d| d == derive)
+    }
+
+    /// 日志级别：--quiet 只保留错误，--verbose 包含 debug，默认为 info
+    pub fn log_level(&self) -> log::LevelFilter {
+        if self.quiet {
+            log::LevelFilter::Error
+        } else if self.verbose {
+            log::LevelFilter::Debug
+        } else {
+            log::LevelFilter::Info
+        }
+    }
+
+    /// 编译文件名规范化正则
+    pub fn strip_suffix_regex(&self) -> Result<Option<Regex>, regex::Error> {
+        self.strip_suffix.as_deref().map(Regex::new).transpose()
+    }
+
+    /// 编译 schema 文件的 include/exclude glob，未设置 include 时为 None（匹配全部）
+    pub fn schema_filters(&self) -> Result<(Option<GlobSet>, GlobSet), globset::Error> {
+        let build = |patterns: &[String]| {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in patterns {
+                builder.add(Glob::new(pattern)?);
+            }
+            builder.build()
+        };
+        let include = if self.include.is_empty() {
+            None
+        } else {
+            Some(build(&self.include)?)
+        };
+        Ok((include, build(&self.exclude)?))
+    }
+
+    /// 读取结构体重命名映射（未设置时为空）
+    pub fn struct_renames(&self) -> Result<HashMap<String, String>, GenError> {
+        let Some(path) = &self.rename_struct else {
+            return Ok(HashMap::new());
+        };
+        let content = std::fs::read_to_string(path).map_err(GenError::io(path))?;
+        serde_json::from_str(&content).map_err(|source| GenError::JsonParse {
+            file: path.into(),
+            source,
+        })
+    }
+
+    /// 读取引用类型映射（未设置时为空），`.toml` 文件按 TOML 解析，其余按 JSON 解析
+    pub fn type_mappings(&self) -> Result<HashMap<String, TypeMapping>, GenError> {
+        let Some(path) = &self.type_map else {
+            return Ok(HashMap::new());
+        };
+        let content = std::fs::read_to_string(path).map_err(GenError::io(path))?;
+        if path.ends_with(".toml") {
+            toml::from_str(&content)
+                .map_err(|e| GenError::InvalidConfig(format!("invalid TOML in {}: {}", path, e)))
+        } else {
+            serde_json::from_str(&content).map_err(|source| GenError::JsonParse {
+                file: path.into(),
+                source,
+            })
+        }
+    }
+
+    /// 判断 date-time 字段是否按配置保持为 String
+    pub fn keeps_datetime_as_string(&self, struct_name: &str, field_name: &str) -> bool {
+        self.datetime_as_string
+            .iter()
+            .any(|entry| field_key_matches(entry, struct_name, field_name))
+    }
+
+    /// 查找字段的类型覆盖，Struct.field 形式优先于仅字段名
+    pub fn type_override(&self, struct_name: &str, field_name: &str) -> Option<&str> {
+        let overrides: Vec<(&str, &str)> = self
+            .type_overrides
+            .iter()
+            .filter_map(|entry| entry.split_once('='))
+            .map(|(key, rust_type)| (key.trim(), rust_type.trim()))
+            .filter(|(key, _)| field_key_matches(key, struct_name, field_name))
+            .collect();
+
+        overrides
+            .iter()
+            .find(|(key, _)| key.contains('.'))
+            .or_else(|| overrides.first())
+            .map(|(_, rust_type)| *rust_type)
+    }
+}
+
+/// --type-map 中单个引用类型的映射，覆盖内置的 $ref 解析规则
+///
+/// 省略的键沿用内置规则的结果；`import` 为空字符串表示不需要导入。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TypeMapping {
+    pub import: Option<String>, // use 语句或类型路径（如 crate::v2_1::datatypes::IdTokenType）
+    pub rust_type: Option<String>,
+    pub needs_validation: Option<bool>, // 字段是否生成 #[validate(nested)]
+}
+
+/// 将 OCPP 版本号（如 `2.0.1`、`v2_0_1`）映射为模块名（`v2_0_1`）
+fn version_module(version: &str) -> Option<String> {
+    let digits = version
+        .strip_prefix('v')
+        .unwrap_or(version)
+        .replace(['.', '_'], "_");
+    let valid = !digits.is_empty()
+        && digits
+            .split('_')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    valid.then(|| format!("v{}", digits))
+}
+
+/// 匹配 `field` 或 `Struct.field` 形式的字段键
+fn field_key_matches(key: &str, struct_name: &str, field_name: &str) -> bool {
+    match key.split_once('.') {
+        Some((struct_part, field_part)) => struct_part == struct_name && field_part == field_name,
+        None => key == field_name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_env_sets_output_dir_without_flag() {
+        let mut config = Config::default();
+        config
+            .apply_env(env(&[
+                ("GEN_OUTPUT_DIR", "/tmp/out"),
+                ("GEN_MOD_FILE", "false"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.output_dir, "/tmp/out");
+        assert!(!config.generate_mod_file);
+        assert_eq!(config.schema_dir, Config::default().schema_dir);
+    }
+
+    #[test]
+    fn test_env_rejects_invalid_boolean() {
+        let mut config = Config::default();
+        let error = config
+            .apply_env(env(&[("GEN_STRICT", "maybe")]))
+            .unwrap_err();
+        assert!(error.contains("GEN_STRICT"));
+    }
+
+    #[test]
+    fn test_from_file_reads_toml_and_defaults_missing_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gen.toml");
+        std::fs::write(
+            &path,
+            r#"
+schema_dir = "schemas"
+output_dir = "out"
+generate_mod_file = false
+builders = true
+type_overrides = ["amount=f64"]
+"#,
+        )
+        .unwrap();
+
+        let config = Config::from_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(config.schema_dir, "schemas");
+        assert_eq!(config.output_dir, "out");
+        assert!(!config.generate_mod_file);
+        assert!(config.builders);
+        assert_eq!(config.type_overrides, vec!["amount=f64"]);
+        assert!(config.show_statistics);
+        assert_eq!(config.max_line_length, 100);
+    }
+
+    #[test]
+    fn test_from_file_reads_ocpp_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gen.toml");
+        let schema_dir = format!("schema_dir = {:?}\n", dir.path().to_str().unwrap());
+        std::fs::write(&path, format!("{}ocpp_version = \"2.0.1\"\n", schema_dir)).unwrap();
+
+        let config = Config::from_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(config.ocpp_version, "v2_0_1");
+        assert!(config.validate().is_ok());
+
+        // 旧的字段名不是配置键
+        std::fs::write(&path, "version_module = \"v2_0_1\"\n").unwrap();
+        assert!(Config::from_file(path.to_str().unwrap()).is_err());
+
+        std::fs::write(&path, format!("{}ocpp_version = \"2.x\"\n", schema_dir)).unwrap();
+        let config = Config::from_file(path.to_str().unwrap()).unwrap();
+        assert!(config.validate().unwrap_err().contains("ocpp_version"));
+    }
+
+    #[test]
+    fn test_from_file_rejects_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gen.toml");
+        std::fs::write(&path, "schema_directory = \"schemas\"\n").unwrap();
+
+        let error = Config::from_file(path.to_str().unwrap()).unwrap_err();
+        assert!(error.contains("schema_directory"));
+    }
+
+    #[test]
+    fn test_version_module_from_version_number() {
+        assert_eq!(version_module("1.6").as_deref(), Some("v1_6"));
+        assert_eq!(version_module("2.0.1").as_deref(), Some("v2_0_1"));
+        assert_eq!(version_module("v2_1").as_deref(), Some("v2_1"));
+        assert_eq!(version_module("2.x"), None);
+        assert_eq!(version_module(""), None);
+    }
+
+    #[test]
+    fn test_validate_derives_rejects_incompatible_combinations() {
+        let with_derives = |derives: &[&str]| Config {
+            derives: derives.iter().map(|d| d.to_string()).collect(),
+            ..Config::default()
+        };
+
+        assert!(Config::default().validate_derives().is_ok());
+        assert!(
+            with_derives(&["Debug", "PartialEq", "Eq", "Hash", "Validate"])
+                .validate_derives()
+                .is_ok()
+        );
+
+        let error = with_derives(&["Debug", "Eq"])
+            .validate_derives()
+            .unwrap_err();
+        assert!(error.contains("Eq requires PartialEq"));
+
+        let error = with_derives(&["PartialEq", "PartialOrd", "Ord"])
+            .validate_derives()
+            .unwrap_err();
+        assert!(error.contains("Ord requires Eq"));
+
+        let config = Config {
+            derive_default: true,
+            ..with_derives(&["Debug", "Default", "Validate"])
+        };
+        assert!(config
+            .validate_derives()
+            .unwrap_err()
+            .contains("--derive-default"));
+
+        // 不派生 Validate 时，会调用 validate() 的生成代码无法编译
+        let config = Config {
+            builders: true,
+            ..with_derives(&["Debug", "Clone"])
+        };
+        assert!(config
+            .validate_derives()
+            .unwrap_err()
+            .contains("--builders"));
+        assert!(with_derives(&["Debug", "Clone"]).validate_derives().is_ok());
+
+        let config = Config {
+            gen_proptest: true,
+            ..with_derives(&["Clone", "PartialEq"])
+        };
+        assert!(config
+            .validate_derives()
+            .unwrap_err()
+            .contains("--gen-proptest"));
+    }
+
+    #[test]
+    fn test_type_mappings_read_toml_and_reject_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("types.toml");
+        std::fs::write(
+            &path,
+            r#"
+[IdTokenType]
+import = "crate::tokens::IdTokenType"
+
+[EventDataType]
+rust_type = "EventData"
+needs_validation = true
+"#,
+        )
+        .unwrap();
+        let config = Config {
+            type_map: Some(path.to_string_lossy().into_owned()),
+            ..Config::default()
+        };
+
+        let mappings = config.type_mappings().unwrap();
+        assert_eq!(
+            mappings["IdTokenType"].import.as_deref(),
+            Some("crate::tokens::IdTokenType")
+        );
+        assert_eq!(mappings["IdTokenType"].rust_type, None);
+        assert_eq!(mappings["EventDataType"].needs_validation, Some(true));
+
+        std::fs::write(&path, "[IdTokenType]\nimprot = \"x\"\n").unwrap();
+        let error = config.type_mappings().unwrap_err().to_string();
+        assert!(error.contains("unknown field `improt`"), "{}", error);
+    }
+
+    #[test]
+    fn test_log_level_follows_quiet_and_verbose() {
+        assert_eq!(Config::default().log_level(), log::LevelFilter::Info);
+        let quiet = Config {
+            quiet: true,
+            ..Config::default()
+        };
+        assert_eq!(quiet.log_level(), log::LevelFilter::Error);
+        let verbose = Config {
+            verbose: true,
+            ..Config::default()
+        };
+        assert_eq!(verbose.log_level(), log::LevelFilter::Debug);
+    }
 }
@@ -0,0 +1,101 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+/// 代码生成过程中的错误
+#[derive(Debug, Error)]
+pub enum GenError {
+    /// 读写文件失败
+    #[error("{}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// schema 文件不是合法的 JSON
+    #[error("invalid JSON in {}: {source}", file.display())]
+    JsonParse {
+        file: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// schema 文件不是合法的 YAML
+    #[error("invalid YAML in {}: {source}", file.display())]
+    YamlParse {
+        file: PathBuf,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    /// schema 内容无法生成代码，`file` 在解析文件时由调用方补充
+    #[error("{}{detail}", file.as_ref().map(|f| format!("{}: ", f.display())).unwrap_or_default())]
+    UnsupportedSchema {
+        file: Option<PathBuf>,
+        detail: String,
+    },
+
+    /// 严格模式下消息缺少请求或响应
+    #[error("incomplete message pair for {base_name}: request or response schema is missing")]
+    MissingPair { base_name: String },
+
+    /// 不同目录中的两个 schema 映射到同一个请求或响应
+    #[error("{name} is defined by both {} and {}", first.display(), second.display())]
+    DuplicateMessage {
+        name: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+
+    /// 配置无效（参数冲突、derive 无法满足等）
+    #[error("{0}")]
+    InvalidConfig(String),
+
+    /// --dry-run 发现生成结果与磁盘上的文件不一致
+    #[error("{} generated file(s) are out of date", files.len())]
+    OutOfDate { files: Vec<PathBuf> },
+
+    /// 未启用 --fail-fast 时，处理结束后汇总返回的失败文件及原因
+    #[error("{} file(s) failed to generate", failures.len())]
+    Failures { failures: Vec<(PathBuf, GenError)> },
+}
+
+impl GenError {
+    /// 构造不含文件路径的 schema 错误
+    pub fn unsupported(detail: impl Into<String>) -> Self {
+        GenError::UnsupportedSchema {
+            file: None,
+            detail: detail.into(),
+        }
+    }
+
+    /// 返回将 io::Error 附加路径的转换函数，用于 `map_err`
+    pub fn io(path: impl AsRef<Path>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        move |source| GenError::Io { path, source }
+    }
+
+    /// 错误信息中已包含的文件路径
+    pub fn file(&self) -> Option<&Path> {
+        match self {
+            GenError::Io { path, .. } => Some(path),
+            GenError::JsonParse { file, .. } | GenError::YamlParse { file, .. } => Some(file),
+            GenError::UnsupportedSchema { file, .. } => file.as_deref(),
+            GenError::DuplicateMessage { second, .. } => Some(second),
+            _ => None,
+        }
+    }
+
+    /// 为尚未记录文件路径的 schema 错误补充出错文件
+    pub fn in_file(self, path: &Path) -> Self {
+        match self {
+            GenError::UnsupportedSchema { file: None, detail } => GenError::UnsupportedSchema {
+                file: Some(path.to_path_buf()),
+                detail,
+            },
+            other => other,
+        }
+    }
+}
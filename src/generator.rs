@@ -1,32 +1,152 @@
 use convert_case::{Case, Casing};
-use std::collections::{BTreeMap, HashSet};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Once;
 
-use crate::types::{MessagePair, StructInfo};
+use crate::config::Config;
+use crate::error::GenError;
+use crate::manifest::Manifest;
+use crate::shared::SHARED_MODULE;
+use crate::types::{
+    FieldInfo, InlineEnumInfo, MessagePair, NewtypeInfo, SharedTypes, StructInfo, UnionInfo,
+};
 
 /// 生成配对的 Rust 文件
+///
+/// 返回是否写入了文件；追加模式下已存在的输出文件、以及内容与清单一致的文件会被跳过。
+#[tracing::instrument(
+    name = "generate_file",
+    skip_all,
+    fields(
+        base_name = %pair.base_name,
+        field_count = pair.structs().map(|s| s.fields.len()).sum::<usize>(),
+    )
+)]
 pub fn generate_paired_file(
     pair: &MessagePair,
-    output_dir: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
+    config: &Config,
+    manifest: &Manifest,
+) -> Result<bool, GenError> {
     let filename = format!("{}.rs", pair.base_name.to_case(Case::Snake));
-    let output_path = PathBuf::from(output_dir).join(filename);
+    let output_path = PathBuf::from(&config.output_dir).join(filename);
 
-    let mut code = String::new();
+    if config.append_only && output_path.exists() {
+        return Ok(false);
+    }
+
+    let mut code = file_header(&pair.sources, GENERATED_FILE_NOTE, config);
+
+    // Deprecated messages should only warn at use sites, not inside their own module
+    if pair.structs().any(has_deprecations) {
+        code.push_str("#![allow(deprecated)]\n\n");
+    }
+
+    let items = generate_pair_items(pair, config, &mut BTreeMap::new())?;
 
-    // Add optimized imports
-    let optimized_imports = optimize_imports(&pair.combined_imports);
-    for import in optimized_imports {
+    // Add optimized imports; helpers moved to shared.rs may leave some of them unused
+    let mut imports = if config.share_helpers {
+        used_imports(&pair.combined_imports, &items)
+    } else {
+        pair.combined_imports.clone()
+    };
+    if config.try_from_value && (pair.request.is_some() || pair.response.is_some()) {
+        imports.insert(format!("use super::{}::FromValueError;", ERRORS_MODULE));
+    }
+    for import in optimize_imports(&imports) {
         code.push_str(&import);
         code.push('\n');
     }
     code.push('\n');
 
+    code.push_str(&items);
+
+    // JSON round-trip tests guarding against serde rename mistakes
+    if config.gen_tests {
+        code.push('\n');
+        code.push_str(&generate_round_trip_tests(pair));
+    }
+
+    // proptest strategies producing values that pass validation
+    if config.gen_proptest {
+        code.push('\n');
+        code.push_str(&generate_proptest_strategies(pair, config));
+    }
+
+    let code = if config.rustfmt {
+        format_with_rustfmt(code, &output_path)
+    } else {
+        code
+    };
+    manifest.write(&output_path, &code)
+}
+
+/// 文件头中对整个文件生效的编辑提示：与新输出不一致的文件每次运行都会重写（见 `Manifest::write`）
+const GENERATED_FILE_NOTE: &str =
+    "Manual changes are overwritten on every run, even if the schemas are unchanged.";
+
+/// 生成文件开头的注释：生成工具与版本、来源 schema 及编辑提示（--no-header 时为空）
+///
+/// 没有单独来源的汇总文件（如 mod.rs、shared.rs）以 schema 目录或 OpenAPI 文档为来源。
+fn file_header(sources: &[String], note: &str, config: &Config) -> String {
+    if !config.header {
+        return String::new();
+    }
+    let source = match sources {
+        [] => config
+            .openapi
+            .clone()
+            .unwrap_or_else(|| config.schema_dir.clone()),
+        sources => sources.join(", "),
+    };
+    format!(
+        "// Code generated by {} {}. DO NOT EDIT.\n// Source: {}\n// {}\n\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        source,
+        note
+    )
+}
+
+/// 生成消息对的类型定义（新类型包装、请求、响应与 definitions 数据类型），不含导入
+///
+/// `emitted` 记录已生成的顶层类型名及其代码，同名类型只生成一次；
+/// 单文件模式下跨消息共享，同名但内容不同时打印警告并保留首次出现的定义。
+fn generate_pair_items(
+    pair: &MessagePair,
+    config: &Config,
+    emitted: &mut BTreeMap<String, String>,
+) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    // Generate newtype wrappers for constrained scalar definitions
+    // 请求与响应引用同一定义时只生成一次（保留首次出现的定义）
+    let mut newtypes: BTreeMap<&str, &NewtypeInfo> = BTreeMap::new();
+    for newtype in pair
+        .structs()
+        .flat_map(|info| info.fields.iter())
+        .filter_map(|field| field.newtype.as_ref())
+    {
+        newtypes.entry(newtype.name.as_str()).or_insert(newtype);
+    }
+    for newtype in newtypes.values() {
+        let newtype_code = generate_newtype_code(newtype, config);
+        if first_definition(emitted, &newtype.name, &newtype_code) {
+            code.push_str(&newtype_code);
+            code.push('\n');
+        }
+    }
+
     // Generate Request struct if available
     if let Some(request) = &pair.request {
-        code.push_str(&generate_struct_code(request, "request")?);
-        code.push('\n');
+        let request_code = generate_struct_code(request, "request", &pair.base_name, config)?;
+        if first_definition(emitted, &request.name, &request_code) {
+            code.push_str(&request_code);
+            code.push('\n');
+        }
     }
 
     // Generate Response struct if available
@@ -43,21 +163,613 @@ pub fn generate_paired_file(
         } else {
             "response"
         };
-        code.push_str(&generate_struct_code(response, message_type)?);
+        let response_code = generate_struct_code(response, message_type, &pair.base_name, config)?;
+        if first_definition(emitted, &response.name, &response_code) {
+            code.push_str(&response_code);
+        }
     }
 
-    fs::write(output_path, code)?;
-    Ok(())
+    // Generate data types from a definitions-only schema
+    let mut separate = false;
+    for enum_info in &pair.definition_enums {
+        let enum_code = generate_inline_enum_code(enum_info, config);
+        if first_definition(emitted, &enum_info.name, &enum_code) {
+            if separate {
+                code.push('\n');
+            }
+            code.push_str(&enum_code);
+            separate = true;
+        }
+    }
+    for definition in &pair.definitions {
+        let definition_code =
+            generate_struct_code(definition, "definition", &pair.base_name, config)?;
+        if first_definition(emitted, &definition.name, &definition_code) {
+            if separate {
+                code.push('\n');
+            }
+            code.push_str(&definition_code);
+            separate = true;
+        }
+    }
+
+    Ok(code)
+}
+
+/// 记录顶层类型的定义，返回是否为首次出现（需要输出）
+fn first_definition(emitted: &mut BTreeMap<String, String>, name: &str, code: &str) -> bool {
+    match emitted.get(name) {
+        None => {
+            emitted.insert(name.to_string(), code.to_string());
+            true
+        }
+        Some(previous) => {
+            if previous != code {
+                log::warn!(
+                    "{} is generated more than once with different contents, keeping the first",
+                    name
+                );
+                tracing::warn!(name, "conflicting definitions, keeping the first");
+            }
+            false
+        }
+    }
+}
+
+/// 通过 rustfmt 格式化生成的代码
+///
+/// rustfmt 未安装或格式化失败时打印警告并原样返回，不影响生成。
+fn format_with_rustfmt(code: String, output_path: &Path) -> String {
+    let child = Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout", "--quiet"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            // 每个文件都会调用，只提示一次
+            static MISSING: Once = Once::new();
+            MISSING.call_once(|| {
+                log::warn!(
+                    "rustfmt is not available ({}), writing unformatted files",
+                    e
+                );
+                tracing::warn!(error = %e, "rustfmt not available");
+            });
+            return code;
+        }
+    };
+
+    // 在独立线程中写入 stdin，避免输出填满管道时相互阻塞
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = code.clone();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output();
+    let written = matches!(writer.join(), Ok(Ok(())));
+
+    let error = match output {
+        Ok(output) if written && output.status.success() => {
+            return String::from_utf8(output.stdout).unwrap_or(code);
+        }
+        Ok(output) => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        Err(e) => e.to_string(),
+    };
+    log::warn!(
+        "rustfmt failed on {}, writing it unformatted: {}",
+        output_path.display(),
+        error
+    );
+    tracing::warn!(path = %output_path.display(), "rustfmt failed");
+    code
+}
+
+/// 判断结构体（含内联嵌套结构体）是否有弃用的消息或字段
+fn has_deprecations(struct_info: &StructInfo) -> bool {
+    struct_info.deprecated.is_some()
+        || struct_info.fields.iter().any(|field| {
+            field.deprecated.is_some()
+                || field.inline_struct.as_deref().is_some_and(has_deprecations)
+        })
+}
+
+/// 为弃用字段及其访问方法添加 #[deprecated] 属性
+fn push_deprecated_attr(code: &mut String, field: &FieldInfo) {
+    if let Some(note) = &field.deprecated {
+        code.push_str(&format!("    #[deprecated(note = {:?})]\n", note));
+    }
+}
+
+/// 生成内联字符串 enum 对应的 Rust 枚举，每个变体通过 rename 保留原始线上字符串
+fn generate_inline_enum_code(inline_enum: &InlineEnumInfo, config: &Config) -> String {
+    let mut code = String::new();
+
+    match &inline_enum.description {
+        Some(description) => code.push_str(&format!("/// {}\n", description)),
+        None => code.push_str(&format!("/// Allowed values of `{}`.\n", inline_enum.name)),
+    }
+    let mut derives = vec!["Debug", "Clone", "PartialEq", "Eq", "Hash"];
+    derives.extend(comparison_derives(config).filter(|d| matches!(*d, "PartialOrd" | "Ord")));
+    derives.extend(["Deserialize", "Serialize"]);
+    code.push_str(&format!("#[derive({})]\n", derives.join(", ")));
+    code.push_str(&format!("pub enum {} {{\n", inline_enum.name));
+
+    let variants = enum_variants(inline_enum);
+    for (variant, value) in &variants {
+        code.push_str(&format!("    #[serde(rename = {:?})]\n", value));
+        code.push_str(&format!("    {},\n", variant));
+    }
+    code.push_str("}\n");
+    code.push('\n');
+
+    // 线上字符串与变体之间的转换，与 serde 的 rename 保持一致
+    code.push_str(&format!("impl {} {{\n", inline_enum.name));
+    if config.enum_all {
+        let all: Vec<String> = variants
+            .iter()
+            .map(|(variant, _)| format!("Self::{}", variant))
+            .collect();
+        code.push_str("    /// Every variant, in schema order.\n");
+        code.push_str(&format!(
+            "    pub const ALL: &[Self] = &[{}];\n",
+            all.join(", ")
+        ));
+        code.push('\n');
+    }
+    code.push_str("    /// Returns the wire value of the variant, as serialized by serde.\n");
+    code.push_str("    pub fn as_str(&self) -> &'static str {\n");
+    code.push_str("        match self {\n");
+    for (variant, value) in &variants {
+        code.push_str(&format!("            Self::{} => {:?},\n", variant, value));
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+
+    let expected: Vec<String> = inline_enum
+        .values
+        .iter()
+        .map(|value| format!("{:?}", value))
+        .collect();
+    code.push_str(&format!(
+        "impl std::str::FromStr for {} {{\n",
+        inline_enum.name
+    ));
+    code.push_str("    type Err = serde::de::value::Error;\n");
+    code.push('\n');
+    code.push_str("    /// Parses a wire value, rejecting strings that are not a variant.\n");
+    code.push_str("    fn from_str(s: &str) -> Result<Self, Self::Err> {\n");
+    code.push_str("        match s {\n");
+    for (variant, value) in &variants {
+        code.push_str(&format!(
+            "            {:?} => Ok(Self::{}),\n",
+            value, variant
+        ));
+    }
+    code.push_str(&format!(
+        "            other => Err(serde::de::Error::unknown_variant(other, &[{}])),\n",
+        expected.join(", ")
+    ));
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    // schema default 优先；--enum-default-first 时退回第一个变体，否则不实现 Default
+    let default = match &inline_enum.default {
+        Some(default) => variants.iter().find(|(_, value)| value == default),
+        None if config.enum_default_first => variants.first(),
+        None => None,
+    };
+    if let Some((variant, value)) = default {
+        code.push('\n');
+        code.push_str(&format!("impl Default for {} {{\n", inline_enum.name));
+        code.push_str(&format!("    /// Returns `{}`.\n", value));
+        code.push_str("    fn default() -> Self {\n");
+        code.push_str(&format!("        Self::{}\n", variant));
+        code.push_str("    }\n");
+        code.push_str("}\n");
+    }
+
+    // 与字符串比较时使用线上值（as_str），而不是变体名
+    if config.enum_str_eq {
+        for (other, deref) in [("&str", "*other"), ("String", "other.as_str()")] {
+            code.push('\n');
+            code.push_str(&format!(
+                "impl PartialEq<{}> for {} {{\n",
+                other, inline_enum.name
+            ));
+            code.push_str("    /// Compares the wire value of the variant with the string.\n");
+            code.push_str(&format!("    fn eq(&self, other: &{}) -> bool {{\n", other));
+            code.push_str(&format!("        self.as_str() == {}\n", deref));
+            code.push_str("    }\n");
+            code.push_str("}\n");
+        }
+    }
+    code
+}
+
+/// 内联枚举的变体名及其线上字符串，按 schema 顺序
+fn enum_variants(inline_enum: &InlineEnumInfo) -> Vec<(String, &str)> {
+    let mut used = HashSet::new();
+    inline_enum
+        .values
+        .iter()
+        .map(|value| {
+            let variant = enum_variant_name(value, &used);
+            used.insert(variant.clone());
+            (variant, value.as_str())
+        })
+        .collect()
+}
+
+/// 生成 oneOf/anyOf 对应的 untagged 枚举，Validate 委托给各变体
+fn generate_union_code(union: &UnionInfo, config: &Config) -> String {
+    let mut code = String::new();
+
+    match &union.description {
+        Some(description) => code.push_str(&format!("/// {}\n", description)),
+        None => code.push_str(&format!(
+            "/// Alternative shapes of `{}`, tried in order.\n",
+            union.name
+        )),
+    }
+    let mut derives = vec!["Debug", "Clone", "PartialEq"];
+    derives.extend(comparison_derives(config));
+    derives.extend(["Deserialize", "Serialize"]);
+    code.push_str(&format!("#[derive({})]\n", derives.join(", ")));
+    match &union.tag {
+        Some(tag) => {
+            // serde 会消费判别属性，分支类型自身的该字段由辅助函数补回与去除
+            let prefix = union.name.to_case(Case::Snake);
+            code.push_str(&format!("#[serde(tag = {:?})]\n", tag));
+            code.push_str(&format!("pub enum {} {{\n", union.name));
+            for ((variant, rust_type), value) in union.variants.iter().zip(&union.tag_values) {
+                code.push_str(&format!("    #[serde(rename = {:?})]\n", value));
+                code.push_str(&format!(
+                    "    {}(\n        #[serde(\n            deserialize_with = \"{}_{}\",\n            serialize_with = \"{}_without_tag\"\n        )]\n        {},\n    ),\n",
+                    variant,
+                    prefix,
+                    variant.to_case(Case::Snake),
+                    prefix,
+                    rust_type
+                ));
+            }
+            code.push_str("}\n");
+            code.push_str(&generate_union_tag_helpers(union, tag, &prefix));
+        }
+        None => {
+            code.push_str("#[serde(untagged)]\n");
+            code.push_str(&format!("pub enum {} {{\n", union.name));
+            for (variant, rust_type) in &union.variants {
+                code.push_str(&format!("    {}({}),\n", variant, rust_type));
+            }
+            code.push_str("}\n");
+        }
+    }
+
+    // validator 的派生宏不支持枚举，因此直接实现 Validate
+    if config.has_derive("Validate") {
+        code.push('\n');
+        code.push_str(&format!("impl Validate for {} {{\n", union.name));
+        code.push_str("    fn validate(&self) -> Result<(), validator::ValidationErrors> {\n");
+        code.push_str("        match self {\n");
+        for ((variant, rust_type), constraints) in union.variants.iter().zip(&union.constraints) {
+            // 标量变体与同样约束的字段（或新类型包装）做相同的检查
+            let checks = match constraints {
+                Some(constraints) if constraints.inner_type == "Decimal" => {
+                    decimal_range_checks(&constraints.inner_field())
+                }
+                Some(constraints) => {
+                    constraint_violations(&constraints.inner_field(), "value", config)
+                }
+                None => Vec::new(),
+            };
+            if !checks.is_empty() {
+                let rule = if rust_type == "String" {
+                    "length"
+                } else {
+                    "range"
+                };
+                code.push_str(&format!("            Self::{}(value) => {{\n", variant));
+                code.push_str(&format!("                if {} {{\n", checks.join(" || ")));
+                code.push_str(
+                    "                    let mut errors = validator::ValidationErrors::new();\n",
+                );
+                code.push_str(&format!(
+                    "                    errors.add(\"{}\", validator::ValidationError::new(\"{}\"));\n",
+                    variant, rule
+                ));
+                code.push_str("                    return Err(errors);\n");
+                code.push_str("                }\n");
+                code.push_str("                Ok(())\n");
+                code.push_str("            }\n");
+            } else if is_nested_struct_type(rust_type, "") {
+                code.push_str(&format!(
+                    "            Self::{}(value) => value.validate(),\n",
+                    variant
+                ));
+            } else {
+                code.push_str(&format!("            Self::{}(_) => Ok(()),\n", variant));
+            }
+        }
+        code.push_str("        }\n");
+        code.push_str("    }\n");
+        code.push_str("}\n");
+    }
+
+    code
+}
+
+/// 生成 tagged 联合类型的 serde 辅助函数
+///
+/// 分支类型自身带有判别字段：反序列化时把 serde 消费掉的标签补回，序列化时去掉该字段以免重复。
+fn generate_union_tag_helpers(union: &UnionInfo, tag: &str, prefix: &str) -> String {
+    let mut code = String::new();
+
+    code.push('\n');
+    code.push_str(&format!(
+        "/// Deserializes a `{}` branch, restoring the `{}` tag consumed by serde.\n",
+        union.name, tag
+    ));
+    code.push_str(&format!(
+        "fn {}_with_tag<'de, D, T>(deserializer: D, value: &str) -> Result<T, D::Error>\n",
+        prefix
+    ));
+    code.push_str("where\n");
+    code.push_str("    D: serde::Deserializer<'de>,\n");
+    code.push_str("    T: serde::de::DeserializeOwned,\n");
+    code.push_str("{\n");
+    code.push_str("    let mut fields = serde_json::Map::deserialize(deserializer)?;\n");
+    code.push_str(&format!(
+        "    fields.insert({:?}.to_string(), value.into());\n",
+        tag
+    ));
+    code.push_str("    serde_json::from_value(fields.into()).map_err(serde::de::Error::custom)\n");
+    code.push_str("}\n");
+
+    for ((variant, rust_type), value) in union.variants.iter().zip(&union.tag_values) {
+        code.push('\n');
+        code.push_str(&format!(
+            "/// Deserializes the `{}` branch of `{}`.\n",
+            value, union.name
+        ));
+        code.push_str(&format!(
+            "fn {}_{}<'de, D>(deserializer: D) -> Result<{}, D::Error>\n",
+            prefix,
+            variant.to_case(Case::Snake),
+            rust_type
+        ));
+        code.push_str("where\n");
+        code.push_str("    D: serde::Deserializer<'de>,\n");
+        code.push_str("{\n");
+        code.push_str(&format!(
+            "    {}_with_tag(deserializer, {:?})\n",
+            prefix, value
+        ));
+        code.push_str("}\n");
+    }
+
+    code.push('\n');
+    code.push_str(&format!(
+        "/// Serializes a `{}` branch without its own `{}` field, which serde writes as the tag.\n",
+        union.name, tag
+    ));
+    code.push_str(&format!(
+        "fn {}_without_tag<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>\n",
+        prefix
+    ));
+    code.push_str("where\n");
+    code.push_str("    T: Serialize,\n");
+    code.push_str("    S: serde::Serializer,\n");
+    code.push_str("{\n");
+    code.push_str(
+        "    let mut value = serde_json::to_value(value).map_err(serde::ser::Error::custom)?;\n",
+    );
+    code.push_str("    if let Some(fields) = value.as_object_mut() {\n");
+    code.push_str(&format!("        fields.remove({:?});\n", tag));
+    code.push_str("    }\n");
+    code.push_str("    value.serialize(serializer)\n");
+    code.push_str("}\n");
+    code
+}
+
+/// 将 enum 字符串值转换为 PascalCase 变体名，处理空值、数字开头和转换后的重名
+fn enum_variant_name(value: &str, used: &HashSet<String>) -> String {
+    let mut variant: String = value
+        .to_case(Case::Pascal)
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+
+    if variant.is_empty() {
+        variant = "Empty".to_string();
+    } else if variant.starts_with(|c: char| c.is_ascii_digit()) {
+        variant = format!("Value{}", variant);
+    }
+
+    let base = variant.clone();
+    let mut suffix = 2;
+    while used.contains(&variant) {
+        variant = format!("{}{}", base, suffix);
+        suffix += 1;
+    }
+    variant
+}
+
+/// 生成受约束标量的新类型包装（含手写的 Validate 实现与 Deref/From 转换）
+fn generate_newtype_code(newtype: &NewtypeInfo, config: &Config) -> String {
+    let mut code = String::new();
+    let name = &newtype.name;
+    let inner = &newtype.inner_type;
+
+    match &newtype.description {
+        Some(description) => code.push_str(&format!("/// {}\n", description)),
+        None => code.push_str(&format!("/// Constrained `{}` wrapper.\n", inner)),
+    }
+    // String、整数与 Decimal 均支持比较类 trait，随消息结构体一起派生
+    let mut derives = vec!["Debug", "Clone", "PartialEq"];
+    derives.extend(comparison_derives(config));
+    derives.extend(["Deserialize", "Serialize"]);
+    code.push_str(&format!("#[derive({})]\n", derives.join(", ")));
+    code.push_str("#[serde(transparent)]\n");
+    code.push_str(&format!("pub struct {}(pub {});\n", name, inner));
+    code.push('\n');
+
+    // validator 的派生宏不支持元组结构体，因此直接实现 Validate；
+    // 约束与同样约束的字段一致（含排他边界与 Decimal 的上下限）
+    let field = newtype.inner_field();
+    let checks = if inner == "Decimal" {
+        decimal_range_checks(&field)
+    } else {
+        constraint_violations(&field, "value", config)
+    };
+    let rule = if inner == "String" { "length" } else { "range" };
+
+    code.push_str(&format!("impl Validate for {} {{\n", name));
+    code.push_str("    fn validate(&self) -> Result<(), validator::ValidationErrors> {\n");
+    if checks.is_empty() {
+        code.push_str("        Ok(())\n");
+    } else {
+        code.push_str("        let value = &self.0;\n");
+        code.push_str(&format!("        if {} {{\n", checks.join(" || ")));
+        code.push_str("            let mut errors = validator::ValidationErrors::new();\n");
+        code.push_str(&format!(
+            "            errors.add(\"0\", validator::ValidationError::new(\"{}\"));\n",
+            rule
+        ));
+        code.push_str("            return Err(errors);\n");
+        code.push_str("        }\n");
+        code.push_str("        Ok(())\n");
+    }
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+
+    code.push_str(&format!("impl std::ops::Deref for {} {{\n", name));
+    code.push_str(&format!("    type Target = {};\n", inner));
+    code.push('\n');
+    code.push_str(&format!("    fn deref(&self) -> &{} {{\n", inner));
+    code.push_str("        &self.0\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+
+    code.push_str(&format!("impl From<{}> for {} {{\n", inner, name));
+    code.push_str(&format!("    fn from(value: {}) -> Self {{\n", inner));
+    code.push_str("        Self(value)\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    if inner == "String" {
+        code.push('\n');
+        code.push_str(&format!("impl From<&str> for {} {{\n", name));
+        code.push_str("    fn from(value: &str) -> Self {\n");
+        code.push_str("        Self(value.to_string())\n");
+        code.push_str("    }\n");
+        code.push_str("}\n");
+    }
+
+    code
 }
 
 /// 生成单个结构体的代码
 pub fn generate_struct_code(
     struct_info: &StructInfo,
     message_type: &str,
-) -> Result<String, Box<dyn std::error::Error>> {
+    action: &str,
+    config: &Config,
+) -> Result<String, GenError> {
     let mut code = String::new();
 
-    // Add struct comment
+    // Structs lifted from inline objects come first, nested ones before their parents;
+    // helpers shared with other messages live in shared.rs instead
+    for inline in struct_info
+        .fields
+        .iter()
+        .filter_map(|field| field.inline_struct.as_deref())
+        .filter(|inline| !inline.emitted_elsewhere)
+    {
+        code.push_str(&generate_struct_code(inline, "definition", action, config)?);
+        code.push('\n');
+    }
+
+    // Inline string enums are emitted right before the struct that uses them
+    for inline_enum in struct_info
+        .fields
+        .iter()
+        .filter_map(|field| field.inline_enum.as_ref())
+        .filter(|inline_enum| !inline_enum.emitted_elsewhere)
+    {
+        code.push_str(&generate_inline_enum_code(inline_enum, config));
+        code.push('\n');
+    }
+
+    // oneOf/anyOf unions are emitted the same way
+    for union in struct_info
+        .fields
+        .iter()
+        .filter_map(|field| field.union.as_ref())
+    {
+        code.push_str(&generate_union_code(union, config));
+        code.push('\n');
+    }
+
+    // Validator helpers are only referenced by #[validate] attributes
+    let validate = config.has_derive("Validate");
+
+    // Compiled regexes for `pattern` constraints
+    for field in struct_info.fields.iter().filter(|_| validate) {
+        if let Some(pattern) = &field.pattern {
+            code.push_str(&format!(
+                "static {}: Lazy<Regex> = Lazy::new(|| Regex::new({:?}).unwrap());\n",
+                pattern_regex_name(&struct_info.name, field),
+                pattern
+            ));
+            code.push('\n');
+        }
+    }
+
+    // Range checks for Decimal fields, which validator's range cannot handle
+    for field in struct_info.fields.iter().filter(|_| validate) {
+        if field.needs_validation && field.rust_type == "Decimal" {
+            if let Some(range_fn) = generate_decimal_range_fn(&struct_info.name, field) {
+                code.push_str(&range_fn);
+                code.push('\n');
+            }
+        }
+    }
+
+    // multipleOf checks, which validator has no built-in rule for
+    for field in struct_info.fields.iter().filter(|_| validate) {
+        if let Some(multiple_of_fn) = generate_multiple_of_fn(&struct_info.name, field) {
+            code.push_str(&multiple_of_fn);
+            code.push('\n');
+        }
+    }
+
+    // Fixed `const` values, which validator has no built-in rule for
+    for field in struct_info.fields.iter().filter(|_| validate) {
+        if let Some(const_fn) = generate_const_fn(&struct_info.name, field) {
+            code.push_str(&const_fn);
+            code.push('\n');
+        }
+    }
+
+    // Schema defaults filled in for absent optional fields
+    for field in &struct_info.fields {
+        if let Some(default_fn) = generate_default_fn(&struct_info.name, field) {
+            code.push_str(&default_fn);
+            code.push('\n');
+        }
+    }
+
+    // Add struct comment, led by the schema title if present
+    if let Some(title) = struct_info.title.as_deref().filter(|t| !t.is_empty()) {
+        push_wrapped_doc(&mut code, "", title, "", config.max_line_length);
+        code.push_str("///\n");
+    }
     if struct_info.name.ends_with("Request") || struct_info.name.ends_with("Response") {
         code.push_str(&format!(
             "/// {} body for the {} {}.\n",
@@ -74,15 +786,59 @@ pub fn generate_struct_code(
                 .replace("Response", ""),
             message_type
         ));
+    } else if message_type == "definition" {
+        code.push_str(&format!("/// {} data type.\n", struct_info.name));
     } else {
         // For standalone messages
         code.push_str(&format!("/// {} message structure.\n", struct_info.name));
     }
 
+    // Schema examples of the whole message
+    if config.doc_examples && !struct_info.examples.is_empty() {
+        push_struct_examples(&mut code, struct_info, action, config);
+    }
+
+    // Mark deprecated messages
+    if let Some(note) = &struct_info.deprecated {
+        code.push_str(&format!("#[deprecated(note = {:?})]\n", note));
+    }
+
     // Add struct definition
-    code.push_str("#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]\n");
-    code.push_str("#[serde(rename_all = \"camelCase\")]\n");
-    code.push_str(&format!("pub struct {} {{\n", struct_info.name));
+    check_derives(struct_info, config)?;
+    code.push_str(&format!(
+        "#[derive({})]\n",
+        struct_derives(config).join(", ")
+    ));
+    let rename_all = struct_info
+        .rename_all
+        .as_deref()
+        .unwrap_or(DEFAULT_RENAME_ALL);
+    // 没有字段（如空的确认响应）时无需重命名
+    if !struct_info.fields.is_empty() {
+        code.push_str(&format!("#[serde(rename_all = \"{}\")]\n", rename_all));
+    }
+    // 严格模式拒绝未声明的字段；允许额外属性的对象（含 customData 扩展）保持宽松
+    if config.deny_unknown_fields && !struct_info.allows_additional_properties {
+        code.push_str("#[serde(deny_unknown_fields)]\n");
+    }
+
+    // FFI 布局：仅当所有字段都是必填的 FFI 安全标量时才生成
+    if config.repr_c {
+        if is_ffi_safe(struct_info) {
+            code.push_str("#[repr(C)]\n");
+        } else {
+            log::warn!(
+                "{} is not FFI-safe (requires non-optional scalar fields only), skipping #[repr(C)]",
+                struct_info.name
+            );
+            tracing::warn!(struct_name = %struct_info.name, "struct is not FFI-safe, skipping #[repr(C)]");
+        }
+    }
+    // 没有字段时生成 `pub struct Foo {}`
+    code.push_str(&format!("pub struct {} {{", struct_info.name));
+    if !struct_info.fields.is_empty() {
+        code.push('\n');
+    }
 
     // Add fields
     for (index, field) in struct_info.fields.iter().enumerate() {
@@ -91,28 +847,65 @@ pub fn generate_struct_code(
             code.push('\n');
         }
 
-        // Add description as comment if available
-        if let Some(description) = &field.description {
-            if !description.is_empty() {
-                code.push_str(&format!("    /// {}\n", description));
+        // Add title and description as comment if available
+        let docs: Vec<&str> = [&field.title, &field.description]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|doc| !doc.is_empty())
+            .collect();
+        for (index, doc) in docs.iter().enumerate() {
+            if index > 0 {
+                code.push_str("    ///\n");
             }
+            push_wrapped_doc(&mut code, "    ", doc, "", config.max_line_length);
         }
+        if config.doc_examples && !field.examples.is_empty() {
+            push_json_examples(&mut code, "    ", &field.examples, !docs.is_empty());
+        }
+        push_deprecated_attr(&mut code, field);
 
         // Add serde attributes using the existing project's multi-line format
         let mut serde_attrs = Vec::new();
 
-        // Handle field renaming for Rust keywords or when camelCase conversion doesn't match
+        // Handle field renaming for Rust keywords or when the rename_all conversion doesn't match
         if field.name != field.original_name {
-            // 检查是否需要 rename：如果原始名称转换为 snake_case 后与字段名不同，则需要 rename
+            // 检查是否需要 rename：字段名与原始名称的 snake_case 不同（关键字），
+            // 或者 rename_all 规则无法还原出原始名称
             let expected_snake_case = field.original_name.to_case(Case::Snake);
-            if field.name != expected_snake_case {
+            if field.name != expected_snake_case
+                || apply_serde_rename_rule(&field.name, rename_all) != field.original_name
+            {
                 serde_attrs.push(format!("rename = \"{}\"", field.original_name));
             }
         }
 
-        // Handle optional fields
-        if field.is_optional {
-            serde_attrs.push("skip_serializing_if = \"Option::is_none\"".to_string());
+        // 自定义编解码模块（如原始字节字段的 serde_bytes）
+        let schema_default = schema_default_expr(field).is_some();
+        if let Some(with) = &field.serde_with {
+            serde_attrs.push(format!("with = \"{}\"", with));
+            if field.is_optional && !field.required_nullable && !schema_default {
+                // 自定义反序列化不会为缺失字段自动填充 None
+                serde_attrs.push("default".to_string());
+            }
+        }
+
+        // 缺失的字段反序列化为 schema default
+        if schema_default {
+            serde_attrs.push(format!(
+                "default = \"{}\"",
+                default_fn_name(&struct_info.name, field)
+            ));
+        }
+
+        // Handle optional fields; required nullable keys are always written (as null when None)
+        // and must be present, which serde only enforces for Option with a deserialize_with
+        if field.required_nullable {
+            if field.serde_with.is_none() {
+                serde_attrs.push("deserialize_with = \"Option::deserialize\"".to_string());
+            }
+        } else if field.is_optional {
+            serde_attrs.push("skip_serializing_if = \"Option::is_none\"".to_string());
         }
 
         // Add serde attribute if needed
@@ -134,7 +927,9 @@ pub fn generate_struct_code(
         }
 
         // Add validation attributes
-        add_validation_attributes(&mut code, field);
+        if validate {
+            add_validation_attributes(&mut code, &struct_info.name, field, config);
+        }
 
         // Add field definition
         let field_type = if field.is_optional {
@@ -149,13 +944,205 @@ pub fn generate_struct_code(
     code.push_str("}\n\n");
 
     // Add implementation block
-    code.push_str(&generate_impl_block(struct_info)?);
+    code.push_str(&generate_impl_block(
+        struct_info,
+        message_type,
+        action,
+        config,
+    )?);
+
+    // Add Default implementation if enabled and every field has a known default
+    if config.derive_default {
+        if let Some(default_impl) = generate_default_impl(struct_info) {
+            code.push('\n');
+            code.push_str(&default_impl);
+        }
+    }
+
+    // Add FromStr implementation if enabled
+    if config.generate_from_str {
+        code.push('\n');
+        code.push_str(&generate_from_str_impl(struct_info)?);
+    }
+
+    // Add TryFrom<serde_json::Value> for messages, definitions are only converted as part of one
+    if config.try_from_value && message_type != "definition" {
+        code.push('\n');
+        code.push_str(&generate_try_from_value_impl(struct_info));
+    }
+
+    // Add Display implementation writing the compact JSON form
+    if config.display {
+        code.push('\n');
+        code.push_str(&generate_display_impl(struct_info));
+    }
+
+    // Add IntoIterator implementations delegating to the configured Vec field
+    if let Some((field, item_type)) = iter_field(struct_info, config) {
+        code.push('\n');
+        code.push_str(&generate_into_iterator_impls(struct_info, field, item_type));
+    }
+
+    // Add a separate builder that checks required fields before validating
+    if config.builders {
+        code.push('\n');
+        code.push_str(&generate_builder(struct_info, config)?);
+    }
 
     Ok(code)
 }
 
+/// 查找 --iter-field 指定的迭代代理字段及其元素类型
+///
+/// `Struct.field` 直接指定字段；仅给出 `Struct` 时选取其唯一的 Vec 字段。
+/// 只支持必填的 Vec 字段。
+fn iter_field<'a>(
+    struct_info: &'a StructInfo,
+    config: &Config,
+) -> Option<(&'a FieldInfo, &'a str)> {
+    let vec_item = |field: &'a FieldInfo| {
+        field
+            .rust_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+            .filter(|_| !field.is_optional)
+    };
+
+    config
+        .iter_fields
+        .iter()
+        .find_map(|entry| match entry.split_once('.') {
+            Some((struct_name, field_name)) if struct_name == struct_info.name => struct_info
+                .fields
+                .iter()
+                .find(|f| f.original_name == field_name || f.name == field_name)
+                .and_then(|f| vec_item(f).map(|item| (f, item))),
+            None if entry == &struct_info.name => {
+                let mut candidates = struct_info
+                    .fields
+                    .iter()
+                    .filter(|f| f.rust_type.starts_with("Vec<"));
+                match (candidates.next(), candidates.next()) {
+                    (Some(f), None) => vec_item(f).map(|item| (f, item)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+}
+
+/// 生成委托给集合字段的 IntoIterator 实现（按值与按引用）
+fn generate_into_iterator_impls(
+    struct_info: &StructInfo,
+    field: &FieldInfo,
+    item_type: &str,
+) -> String {
+    let mut code = String::new();
+
+    code.push_str(&format!("impl IntoIterator for {} {{\n", struct_info.name));
+    code.push_str(&format!("    type Item = {};\n", item_type));
+    code.push_str(&format!(
+        "    type IntoIter = std::vec::IntoIter<{}>;\n",
+        item_type
+    ));
+    code.push('\n');
+    code.push_str("    fn into_iter(self) -> Self::IntoIter {\n");
+    code.push_str(&format!("        self.{}.into_iter()\n", field.name));
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+
+    code.push_str(&format!(
+        "impl<'a> IntoIterator for &'a {} {{\n",
+        struct_info.name
+    ));
+    code.push_str(&format!("    type Item = &'a {};\n", item_type));
+    code.push_str(&format!(
+        "    type IntoIter = std::slice::Iter<'a, {}>;\n",
+        item_type
+    ));
+    code.push('\n');
+    code.push_str("    fn into_iter(self) -> Self::IntoIter {\n");
+    code.push_str(&format!("        self.{}.iter()\n", field.name));
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    code
+}
+
+/// 生成委托给集合字段的 iter 方法
+fn generate_iter_method(field: &FieldInfo, item_type: &str) -> String {
+    let mut code = String::new();
+
+    code.push_str(&format!(
+        "    /// Returns an iterator over the {} items of this message.\n",
+        field.name
+    ));
+    code.push_str(&format!(
+        "    pub fn iter(&self) -> std::slice::Iter<'_, {}> {{\n",
+        item_type
+    ));
+    code.push_str(&format!("        self.{}.iter()\n", field.name));
+    code.push_str("    }\n");
+
+    code
+}
+
+/// 默认的 serde rename_all 规则
+const DEFAULT_RENAME_ALL: &str = "camelCase";
+
+/// 模拟 serde 对 snake_case 字段名应用 rename_all 规则的结果
+fn apply_serde_rename_rule(field_name: &str, rule: &str) -> String {
+    let pascal_case = || {
+        let mut result = String::new();
+        let mut capitalize = true;
+        for ch in field_name.chars() {
+            if ch == '_' {
+                capitalize = true;
+            } else if capitalize {
+                result.push(ch.to_ascii_uppercase());
+                capitalize = false;
+            } else {
+                result.push(ch);
+            }
+        }
+        result
+    };
+
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field_name.to_ascii_uppercase(),
+        "PascalCase" => pascal_case(),
+        "camelCase" => {
+            let pascal = pascal_case();
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        }
+        "kebab-case" => field_name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field_name.replace('_', "-").to_ascii_uppercase(),
+        // lowercase / snake_case 对 snake_case 字段名不做改变
+        _ => field_name.to_string(),
+    }
+}
+
+/// 字段 pattern 约束对应的正则静态变量名，包含结构体名以避免请求与响应间冲突
+fn pattern_regex_name(struct_name: &str, field: &FieldInfo) -> String {
+    format!(
+        "{}_{}_REGEX",
+        struct_name.to_case(Case::UpperSnake),
+        field.name.to_case(Case::UpperSnake)
+    )
+}
+
 /// 添加验证属性
-fn add_validation_attributes(code: &mut String, field: &crate::types::FieldInfo) {
+fn add_validation_attributes(
+    code: &mut String,
+    struct_name: &str,
+    field: &FieldInfo,
+    config: &Config,
+) {
     if field.needs_validation {
         if field.rust_type == "String" {
             // 处理字符串长度限制
@@ -165,24 +1152,73 @@ fn add_validation_attributes(code: &mut String, field: &crate::types::FieldInfo)
                 length_constraints.push(format!("min = {}", min_length));
             }
 
+            // 未声明 maxLength 时使用配置的默认最大长度（可关闭）
+            let max_length = string_max_length(field, config);
+            if let Some(max_length) = max_length {
+                length_constraints.push(format!("max = {}", max_length));
+            }
+
+            if !length_constraints.is_empty() {
+                push_validation_message(
+                    &mut length_constraints,
+                    field,
+                    field.min_length.map(|v| v.to_string()),
+                    max_length.map(|v| v.to_string()),
+                    " chars",
+                    config,
+                );
+                code.push_str(&format!(
+                    "    #[validate(length({}))]\n",
+                    length_constraints.join(", ")
+                ));
+            }
+
+            // 处理正则表达式约束
+            if field.pattern.is_some() {
+                code.push_str(&format!(
+                    "    #[validate(regex(path = *{}))]\n",
+                    pattern_regex_name(struct_name, field)
+                ));
+            }
+
+            // 处理字符串格式约束
+            match field.string_format.as_deref() {
+                Some("email") => code.push_str("    #[validate(email)]\n"),
+                Some("uri") => code.push_str("    #[validate(url)]\n"),
+                _ => {}
+            }
+        } else if field.rust_type == "Vec<u8>" {
+            // 原始字节：minLength/maxLength 直接约束字节数
+            let mut length_constraints = Vec::new();
+
+            if let Some(min_length) = &field.min_length {
+                length_constraints.push(format!("min = {}", min_length));
+            }
+
             if let Some(max_length) = &field.max_length {
                 length_constraints.push(format!("max = {}", max_length));
             }
 
             if !length_constraints.is_empty() {
+                push_validation_message(
+                    &mut length_constraints,
+                    field,
+                    field.min_length.map(|v| v.to_string()),
+                    field.max_length.map(|v| v.to_string()),
+                    " bytes",
+                    config,
+                );
                 code.push_str(&format!(
                     "    #[validate(length({}))]\n",
                     length_constraints.join(", ")
                 ));
-            } else {
-                // 默认最大长度限制
-                code.push_str("    #[validate(length(max = 255))]\n");
             }
-        } else if field.rust_type.starts_with("Vec<") {
-            // 处理数组类型
+        } else if field.rust_type.starts_with("Vec<") || field.rust_type.starts_with("HashSet<") {
+            // 处理数组类型（包括枚举集合）
             let inner_type = field
                 .rust_type
                 .strip_prefix("Vec<")
+                .or_else(|| field.rust_type.strip_prefix("HashSet<"))
                 .unwrap()
                 .strip_suffix(">")
                 .unwrap();
@@ -199,6 +1235,14 @@ fn add_validation_attributes(code: &mut String, field: &crate::types::FieldInfo)
             }
 
             if !length_constraints.is_empty() {
+                push_validation_message(
+                    &mut length_constraints,
+                    field,
+                    field.min_items.map(|v| v.to_string()),
+                    field.max_items.map(|v| v.to_string()),
+                    " items",
+                    config,
+                );
                 code.push_str(&format!(
                     "    #[validate(length({}))]\n",
                     length_constraints.join(", ")
@@ -206,435 +1250,6629 @@ fn add_validation_attributes(code: &mut String, field: &crate::types::FieldInfo)
             }
 
             // 添加嵌套验证（如果需要）
-            if inner_type.ends_with("Type") && !inner_type.ends_with("EnumType") {
+            if is_nested_struct_type(inner_type, struct_name) {
                 // 只对包含复杂数据类型的 Vec 添加 nested 验证
                 // 注意：这需要内部类型也实现 Validate trait
                 code.push_str("    #[validate(nested)]\n");
             }
-        } else if field.rust_type.ends_with("Type") && !field.rust_type.ends_with("EnumType") {
+        } else if let Some(value_type) = field
+            .rust_type
+            .strip_prefix("HashMap<String, ")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            // validator 对 HashMap 逐个验证取值
+            if is_nested_struct_type(value_type, struct_name) {
+                code.push_str("    #[validate(nested)]\n");
+            }
+        } else if is_nested_struct_type(&field.rust_type, struct_name) {
             // 只对非枚举类型添加 nested 验证
             // 注意：这需要类型也实现 Validate trait
             code.push_str("    #[validate(nested)]\n");
-        } else if field.rust_type == "i32"
-            || field.rust_type == "i64"
-            || field.rust_type == "u32"
-            || field.rust_type == "u64"
-        {
+        } else if is_integer_type(&field.rust_type) {
             // 处理整数类型的数值范围验证
-            add_numeric_range_validation(code, field);
+            add_numeric_range_validation(code, field, config);
         } else if field.rust_type == "f32" || field.rust_type == "f64" {
             // 处理浮点数类型的数值范围验证
-            add_numeric_range_validation(code, field);
+            add_numeric_range_validation(code, field, config);
         } else if field.rust_type == "Decimal" {
-            // Decimal 类型需要特殊处理，因为 validator crate 不直接支持 Decimal
-            // 我们可以添加自定义验证或者跳过验证
-            // 这里我们跳过验证，因为 Decimal 类型本身就有精度保证
+            // validator 的 range 不支持 Decimal，改用生成的自定义验证函数
+            let (min, max) = numeric_bounds(field);
+            if min.is_some() || max.is_some() {
+                let mut constraints = vec![format!(
+                    "function = \"{}\"",
+                    decimal_range_fn_name(struct_name, field)
+                )];
+                push_validation_message(&mut constraints, field, min, max, "", config);
+                code.push_str(&format!(
+                    "    #[validate(custom({}))]\n",
+                    constraints.join(", ")
+                ));
+            }
+        }
+
+        // multipleOf 由生成的自定义函数检查，可与 range 同时存在
+        if has_multiple_of_check(field) {
+            code.push_str(&format!(
+                "    #[validate(custom(function = \"{}\"))]\n",
+                multiple_of_fn_name(struct_name, field)
+            ));
+        }
+
+        // const 固定值同样由生成的自定义函数检查
+        if const_comparand(field).is_some() {
+            code.push_str(&format!(
+                "    #[validate(custom(function = \"{}\"))]\n",
+                const_fn_name(struct_name, field)
+            ));
         }
     }
 }
 
 /// 添加数值范围验证
-fn add_numeric_range_validation(code: &mut String, field: &crate::types::FieldInfo) {
+///
+/// 排他边界：整数已在 `numeric_bounds` 中换算为相邻的包含边界，
+/// 浮点数使用 validator 的 `exclusive_min`/`exclusive_max`。
+fn add_numeric_range_validation(code: &mut String, field: &FieldInfo, config: &Config) {
+    let (min, max) = numeric_bounds(field);
+    let (exclusive_min, exclusive_max) = exclusive_bounds(field);
     let mut range_constraints = Vec::new();
 
-    if let Some(min) = &field.min_value {
-        // 对于整数类型，如果是整数值则不显示小数点
-        if field.rust_type == "i32"
-            || field.rust_type == "i64"
-            || field.rust_type == "u32"
-            || field.rust_type == "u64"
-        {
-            if min.fract() == 0.0 {
-                range_constraints.push(format!("min = {}", *min as i64));
-            } else {
-                range_constraints.push(format!("min = {}", min));
-            }
+    if let Some(min) = &min {
+        let key = if exclusive_min {
+            "exclusive_min"
+        } else {
+            "min"
+        };
+        range_constraints.push(format!("{} = {}", key, min));
+    }
+
+    if let Some(max) = &max {
+        let key = if exclusive_max {
+            "exclusive_max"
         } else {
-            range_constraints.push(format!("min = {}", min));
+            "max"
+        };
+        range_constraints.push(format!("{} = {}", key, max));
+    }
+
+    if !range_constraints.is_empty() {
+        push_validation_message(&mut range_constraints, field, min, max, "", config);
+        code.push_str(&format!(
+            "    #[validate(range({}))]\n",
+            range_constraints.join(", ")
+        ));
+    }
+}
+
+/// 启用 --validation-messages 时追加由字段名和边界生成的 message 参数
+fn push_validation_message(
+    constraints: &mut Vec<String>,
+    field: &FieldInfo,
+    min: Option<String>,
+    max: Option<String>,
+    unit: &str,
+    config: &Config,
+) {
+    if !config.validation_messages {
+        return;
+    }
+
+    let (exclusive_min, exclusive_max) = exclusive_bounds(field);
+    let lower = |min: &str| match exclusive_min {
+        true => format!("greater than {}{}", min, unit),
+        false => format!("at least {}{}", min, unit),
+    };
+    let upper = |max: &str| match exclusive_max {
+        true => format!("less than {}{}", max, unit),
+        false => format!("at most {}{}", max, unit),
+    };
+    let message = match (min, max) {
+        (Some(min), Some(max)) if !exclusive_min && !exclusive_max => {
+            format!("{} must be between {} and {}{}", field.name, min, max, unit)
         }
+        (Some(min), Some(max)) => {
+            format!("{} must be {} and {}", field.name, lower(&min), upper(&max))
+        }
+        (Some(min), None) => format!("{} must be {}", field.name, lower(&min)),
+        (None, Some(max)) => format!("{} must be {}", field.name, upper(&max)),
+        (None, None) => return,
+    };
+    constraints.push(format!("message = {:?}", message));
+}
+
+/// 字符串字段的最大长度：schema 的 maxLength，未声明时取 --default-string-max
+fn string_max_length(field: &FieldInfo, config: &Config) -> Option<u32> {
+    field.max_length.or(config.default_string_max)
+}
+
+/// 判断类型是否为需要 `#[validate(nested)]` 的结构体：数据类型（枚举除外）或结构体自身，
+/// 递归引用的 `Box<...>` 按其中的类型判断
+fn is_nested_struct_type(rust_type: &str, struct_name: &str) -> bool {
+    let rust_type = rust_type
+        .strip_prefix("Box<")
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(rust_type);
+    (rust_type.ends_with("Type") && !rust_type.ends_with("EnumType")) || rust_type == struct_name
+}
+
+/// 判断是否为整数类型
+fn is_integer_type(rust_type: &str) -> bool {
+    matches!(rust_type, "i32" | "i64" | "u32" | "u64")
+}
+
+/// 判断是否为 getter 按值返回的 Copy 基本类型
+fn is_copy_type(rust_type: &str) -> bool {
+    is_integer_type(rust_type) || matches!(rust_type, "f32" | "f64" | "bool")
+}
+
+/// 判断是否为无符号整数类型
+fn is_unsigned_type(rust_type: &str) -> bool {
+    matches!(rust_type, "u32" | "u64")
+}
+
+/// 判断结构体是否可以使用 #[repr(C)] 布局
+fn is_ffi_safe(struct_info: &StructInfo) -> bool {
+    !struct_info.fields.is_empty()
+        && struct_info.fields.iter().all(|field| {
+            !field.is_optional
+                && (is_integer_type(&field.rust_type)
+                    || matches!(field.rust_type.as_str(), "bool" | "f32" | "f64"))
+        })
+}
+
+/// Decimal 字段范围验证函数名
+fn decimal_range_fn_name(struct_name: &str, field: &FieldInfo) -> String {
+    format!(
+        "validate_{}_{}_range",
+        struct_name.to_case(Case::Snake),
+        field.name
+    )
+}
+
+/// 比较 Decimal（`value` 为其引用）与 schema 上下限的条件，任一成立即违反约束
+fn decimal_range_checks(field: &FieldInfo) -> Vec<String> {
+    let (min, max) = numeric_bounds(field);
+    let (exclusive_min, exclusive_max) = exclusive_bounds(field);
+    let mut checks = Vec::new();
+    if let Some(min) = &min {
+        let op = if exclusive_min { "<=" } else { "<" };
+        checks.push(format!("*value {} {}", op, decimal_literal(min)));
+    }
+    if let Some(max) = &max {
+        let op = if exclusive_max { ">=" } else { ">" };
+        checks.push(format!("*value {} {}", op, decimal_literal(max)));
     }
+    checks
+}
 
-    if let Some(max) = &field.max_value {
-        // 对于整数类型，如果是整数值则不显示小数点
-        if field.rust_type == "i32"
-            || field.rust_type == "i64"
-            || field.rust_type == "u32"
-            || field.rust_type == "u64"
-        {
-            if max.fract() == 0.0 {
-                range_constraints.push(format!("max = {}", *max as i64));
-            } else {
-                range_constraints.push(format!("max = {}", max));
+/// 生成比较 Decimal 与 schema 上下限的自定义验证函数，无上下限时返回 None
+fn generate_decimal_range_fn(struct_name: &str, field: &FieldInfo) -> Option<String> {
+    let checks = decimal_range_checks(field);
+    if checks.is_empty() {
+        return None;
+    }
+
+    let mut code = String::new();
+    code.push_str(&format!(
+        "/// Checks `{}` of `{}` against its schema bounds.\n",
+        field.name, struct_name
+    ));
+    code.push_str(&format!(
+        "fn {}(value: &Decimal) -> Result<(), validator::ValidationError> {{\n",
+        decimal_range_fn_name(struct_name, field)
+    ));
+    code.push_str(&format!("    if {} {{\n", checks.join(" || ")));
+    code.push_str("        return Err(validator::ValidationError::new(\"range\"));\n");
+    code.push_str("    }\n");
+    code.push_str("    Ok(())\n");
+    code.push_str("}\n");
+    Some(code)
+}
+
+/// 判断字段是否需要生成 multipleOf 检查（仅限需要验证的数值字段）
+fn has_multiple_of_check(field: &FieldInfo) -> bool {
+    field.needs_validation
+        && field.multiple_of.is_some()
+        && (is_integer_type(&field.rust_type)
+            || matches!(field.rust_type.as_str(), "f32" | "f64" | "Decimal"))
+}
+
+/// 消息结构体的 derive 列表：按配置顺序去重，并补齐 serde 属性所需的 Serialize/Deserialize
+fn struct_derives(config: &Config) -> Vec<&str> {
+    let mut derives: Vec<&str> = Vec::new();
+    for derive in config.derives.iter().map(String::as_str) {
+        if !derives.contains(&derive) {
+            derives.push(derive);
+        }
+    }
+    for required in ["Deserialize", "Serialize"] {
+        if !derives.contains(&required) {
+            derives.push(required);
+        }
+    }
+    derives
+}
+
+/// 配置中需要字段类型同样实现的比较类 derive
+fn comparison_derives(config: &Config) -> impl Iterator<Item = &str> {
+    ["Eq", "Hash", "PartialOrd", "Ord"]
+        .into_iter()
+        .filter(|derive| config.has_derive(derive))
+}
+
+/// 检查结构体的字段类型是否支持所选的 derive
+///
+/// 只检查已知的标准类型；引用的 crate 类型无法在此判断，由编译器报告。
+/// 字段的联合与新类型随结构体派生相同的比较 trait，其包装的类型一并检查。
+fn check_derives(struct_info: &StructInfo, config: &Config) -> Result<(), GenError> {
+    let unsupported: &[(&str, &[&str])] = &[
+        // Ord 隐含 Eq，先报告 Ord 以便给出改用 PartialOrd 的提示
+        ("Ord", &["f32", "f64", "HashSet", "HashMap", "Value"]),
+        ("PartialOrd", &["HashSet", "HashMap", "Value"]),
+        ("Eq", &["f32", "f64"]),
+        ("Hash", &["f32", "f64", "HashSet", "HashMap"]),
+    ];
+
+    for field in &struct_info.fields {
+        let mut names = type_names(&field.rust_type);
+        if let Some(union) = &field.union {
+            names.extend(union.variants.iter().flat_map(|(_, t)| type_names(t)));
+        }
+        if let Some(newtype) = &field.newtype {
+            names.extend(type_names(&newtype.inner_type));
+        }
+        for (derive, types) in unsupported {
+            if !config.has_derive(derive) {
+                continue;
             }
+            if let Some(name) = names.iter().find(|name| types.contains(name)) {
+                // 浮点数仍可部分比较
+                let hint = if *derive == "Ord" && matches!(*name, "f32" | "f64") {
+                    "; derive PartialOrd without Ord instead"
+                } else {
+                    ""
+                };
+                return Err(GenError::InvalidConfig(format!(
+                    "{} cannot derive {}: field `{}` has type {} which does not implement it{}",
+                    struct_info.name, derive, field.name, name, hint
+                )));
+            }
+        }
+        if config.has_derive("Default") && default_value(&struct_info.name, field).is_none() {
+            return Err(GenError::InvalidConfig(format!(
+                "{} cannot derive Default: field `{}` of type {} has no known default",
+                struct_info.name, field.name, field.rust_type
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// 生成 multipleOf 自定义验证函数的名称
+fn multiple_of_fn_name(struct_name: &str, field: &FieldInfo) -> String {
+    format!(
+        "validate_{}_{}_multiple_of",
+        struct_name.to_case(Case::Snake),
+        field.name
+    )
+}
+
+/// 生成检查数值是否为 multipleOf 整数倍的自定义验证函数，无该约束时返回 None
+///
+/// 整数在除数为整数时直接取余；Decimal 精确取余；浮点数（及除数为小数的整数）
+/// 按相对误差 `EPSILON` 判断商是否接近整数，以吸收二进制舍入误差。
+fn generate_multiple_of_fn(struct_name: &str, field: &FieldInfo) -> Option<String> {
+    if !has_multiple_of_check(field) {
+        return None;
+    }
+    let multiple_of = field.multiple_of?;
+
+    let param = match field.rust_type.as_str() {
+        "Decimal" => "value: &Decimal".to_string(),
+        rust_type => format!("value: {}", rust_type),
+    };
+    // 浮点比较需要先计算商
+    let mut quotient = None;
+    let check = if field.rust_type == "Decimal" {
+        let divisor = decimal_literal(&multiple_of.to_string());
+        format!("*value % {} != Decimal::ZERO", divisor)
+    } else if is_integer_type(&field.rust_type) && multiple_of.fract() == 0.0 {
+        format!("value % {} != 0", multiple_of as u64)
+    } else {
+        let value = match field.rust_type.as_str() {
+            "f64" => "value",
+            _ => "value as f64",
+        };
+        quotient = Some(format!("{} / {:?}", value, multiple_of));
+        "(quotient - quotient.round()).abs() > EPSILON * quotient.abs().max(1.0)".to_string()
+    };
+
+    let mut code = String::new();
+    code.push_str(&format!(
+        "/// Checks that `{}` of `{}` is a multiple of {}.\n",
+        field.name, struct_name, multiple_of
+    ));
+    if quotient.is_some() {
+        code.push_str("///\n");
+        code.push_str("/// The value passes when the quotient is within a relative tolerance of\n");
+        code.push_str(
+            "/// `EPSILON` of a whole number, absorbing floating-point rounding error.\n",
+        );
+    }
+    code.push_str(&format!(
+        "fn {}({}) -> Result<(), validator::ValidationError> {{\n",
+        multiple_of_fn_name(struct_name, field),
+        param
+    ));
+    if let Some(quotient) = &quotient {
+        code.push_str("    const EPSILON: f64 = 1e-9;\n");
+        code.push_str(&format!("    let quotient = {};\n", quotient));
+    }
+    code.push_str(&format!("    if {} {{\n", check));
+    code.push_str("        return Err(validator::ValidationError::new(\"multiple_of\"));\n");
+    code.push_str("    }\n");
+    code.push_str("    Ok(())\n");
+    code.push_str("}\n");
+    Some(code)
+}
+
+/// 生成 const 自定义验证函数的名称
+fn const_fn_name(struct_name: &str, field: &FieldInfo) -> String {
+    format!(
+        "validate_{}_{}_const",
+        struct_name.to_case(Case::Snake),
+        field.name
+    )
+}
+
+/// 与字段值比较的 schema const 字面量，字符串不分配内存；类型不受支持时返回 None
+fn const_comparand(field: &FieldInfo) -> Option<String> {
+    match field.const_value.as_ref()? {
+        Value::String(s) if field.rust_type == "String" => Some(format!("{:?}", s)),
+        value => default_literal(value, &field.rust_type),
+    }
+}
+
+/// `value` 不等于 schema const 时成立的条件，`value` 为指向字段值的引用表达式
+fn const_violation(field: &FieldInfo, value: &str) -> Option<String> {
+    let comparand = const_comparand(field)?;
+    if field.rust_type == "String" {
+        Some(format!("{} != {}", value, comparand))
+    } else {
+        Some(format!("*{} != {}", value, comparand))
+    }
+}
+
+/// 生成拒绝 schema const 以外取值的自定义验证函数，无 const 时返回 None
+///
+/// const 与字段类型不匹配或类型不受支持时打印警告并返回 None。
+fn generate_const_fn(struct_name: &str, field: &FieldInfo) -> Option<String> {
+    let value = field.const_value.as_ref()?;
+    let Some(comparand) = const_comparand(field) else {
+        log::warn!(
+            "{}.{} const {} is not supported for type {}, ignoring it",
+            struct_name,
+            field.original_name,
+            value,
+            field.rust_type
+        );
+        tracing::warn!(struct_name, field = %field.original_name, "unsupported const ignored");
+        return None;
+    };
+
+    // validator 按值传入数值，其余类型传入引用
+    let (param, check) = match field.rust_type.as_str() {
+        "String" => ("value: &str".to_string(), format!("value != {}", comparand)),
+        rust_type if is_copy_type(rust_type) && rust_type != "bool" => (
+            format!("value: {}", rust_type),
+            format!("value != {}", comparand),
+        ),
+        rust_type => match rust_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            Some(item_type) => (
+                format!("value: &[{}]", item_type),
+                format!("*value != {}", comparand),
+            ),
+            None => (
+                format!("value: &{}", rust_type),
+                format!("*value != {}", comparand),
+            ),
+        },
+    };
+
+    let mut code = String::new();
+    code.push_str(&format!(
+        "/// Checks that `{}` of `{}` equals its schema constant {}.\n",
+        field.name, struct_name, value
+    ));
+    code.push_str(&format!(
+        "fn {}({}) -> Result<(), validator::ValidationError> {{\n",
+        const_fn_name(struct_name, field),
+        param
+    ));
+    code.push_str(&format!("    if {} {{\n", check));
+    code.push_str("        return Err(validator::ValidationError::new(\"const\"));\n");
+    code.push_str("    }\n");
+    code.push_str("    Ok(())\n");
+    code.push_str("}\n");
+    Some(code)
+}
+
+/// 必填字段 schema const 的值表达式：new 与 builder 直接填入该值，不再作为参数
+fn fixed_value_expr(field: &FieldInfo) -> Option<String> {
+    if field.is_optional {
+        return None;
+    }
+    default_literal(field.const_value.as_ref()?, &field.rust_type)
+}
+
+/// 将十进制数字面量（如 "-12.5"）转换为精确的 Decimal 构造表达式
+fn decimal_literal(value: &str) -> String {
+    let (int_part, frac_part) = value.split_once('.').unwrap_or((value, ""));
+    let mantissa = format!("{}{}", int_part, frac_part);
+    match mantissa.parse::<i64>() {
+        Ok(mantissa) => format!("Decimal::new({}, {})", mantissa, frac_part.len()),
+        Err(_) => format!(
+            "Decimal::from_i128_with_scale({}, {})",
+            mantissa,
+            frac_part.len()
+        ),
+    }
+}
+
+/// 计算数值字段的上下限（已格式化为 Rust 字面量）
+///
+/// 整数的排他边界换算为最近的包含边界（如 `exclusiveMinimum: 0` 变为 `min = 1`），
+/// 其余类型沿用 schema 中的原文（如 `99.9`），排他性由 `exclusive_bounds` 给出。
+fn numeric_bounds(field: &FieldInfo) -> (Option<String>, Option<String>) {
+    let is_integer = is_integer_type(&field.rust_type);
+
+    // 对于整数类型，如果是整数值则不显示小数点
+    let format_bound = |value: f64| {
+        if is_integer && value.fract() == 0.0 {
+            format!("{}", value as i64)
+        } else {
+            format!("{}", value)
+        }
+    };
+    // Decimal 与浮点优先使用原文，避免经 f64 往返丢失精度
+    let literal_or = |literal: &Option<String>, value: String| match literal {
+        Some(literal) if !is_integer => literal.clone(),
+        _ => value,
+    };
+    // 浮点字段的边界需是浮点字面量（`0.0` 而非 `0`），否则与 f64 比较无法通过类型检查
+    let is_float = matches!(field.rust_type.as_str(), "f32" | "f64");
+    let float_literal = |value: String| {
+        if is_float && !value.contains(['.', 'e', 'E']) {
+            format!("{}.0", value)
         } else {
-            range_constraints.push(format!("max = {}", max));
+            value
         }
+    };
+
+    // 整数的小数边界取范围内最近的整数，排他边界同样换算为包含边界
+    let min = field
+        .min_value
+        .map(|v| match (is_integer, field.exclusive_min) {
+            (true, true) => v.floor() + 1.0,
+            (true, false) => v.ceil(),
+            (false, _) => v,
+        })
+        // 无符号类型本身保证非负，0 下限无需重复检查
+        .filter(|v| !(is_unsigned_type(&field.rust_type) && *v <= 0.0))
+        .map(format_bound)
+        .map(|v| literal_or(&field.min_literal, v))
+        .map(float_literal);
+    let max = field
+        .max_value
+        .map(|v| match (is_integer, field.exclusive_max) {
+            (true, true) => v.ceil() - 1.0,
+            (true, false) => v.floor(),
+            (false, _) => v,
+        })
+        .map(format_bound)
+        .map(|v| literal_or(&field.max_literal, v))
+        .map(float_literal);
+
+    if min.is_none() && max.is_none() && field.name.contains("id") && field.rust_type == "i32" {
+        // 为 ID 字段添加默认的非负验证
+        return (Some("0".to_string()), None);
+    }
+
+    (min, max)
+}
+
+/// `numeric_bounds` 返回的上下限是否为排他边界（整数已换算为包含边界，始终为 false）
+fn exclusive_bounds(field: &FieldInfo) -> (bool, bool) {
+    if is_integer_type(&field.rust_type) {
+        (false, false)
+    } else {
+        (field.exclusive_min, field.exclusive_max)
+    }
+}
+
+/// 单文件模式（--single-file）的输出文件名
+const SINGLE_FILE: &str = "messages.rs";
+
+/// 单文件模式：将所有消息写入同一个 messages.rs，共用一份合并后的导入
+///
+/// 同名的顶层类型只生成一次；本文件中已定义的类型不再从其他模块导入。
+pub fn generate_single_file(
+    message_pairs: &[&MessagePair],
+    shared: &SharedTypes,
+    config: &Config,
+    manifest: &Manifest,
+) -> Result<bool, GenError> {
+    let output_path = PathBuf::from(&config.output_dir).join(SINGLE_FILE);
+
+    if config.append_only && output_path.exists() {
+        return Ok(false);
+    }
+
+    let mut emitted = BTreeMap::new();
+    let mut items = Vec::new();
+    if config.try_from_value {
+        items.push(generate_from_value_error());
+    }
+    if !shared.is_empty() {
+        items.push(generate_shared_items(shared, config, &mut emitted)?);
+    }
+    for pair in message_pairs {
+        let pair_code = generate_pair_items(pair, config, &mut emitted)?;
+        if !pair_code.is_empty() {
+            items.push(pair_code);
+        }
+    }
+
+    // 导入路径形如 `use crate::v2_1::datatypes::Foo;` 或 `pub use super::shared::Foo;`，
+    // Foo 已在本文件生成时去掉
+    let imports: HashSet<String> = message_pairs
+        .iter()
+        .flat_map(|pair| pair.combined_imports.iter())
+        .chain(shared.structs.iter().flat_map(|info| info.imports.iter()))
+        .filter(|import| {
+            let imported = import
+                .trim_start_matches("pub ")
+                .strip_prefix("use ")
+                .and_then(|path| path.strip_suffix(';'))
+                .and_then(|path| path.rsplit_once("::"));
+            !imported.is_some_and(|(_, name)| emitted.contains_key(name))
+        })
+        .cloned()
+        .collect();
+
+    let mut code = file_header(&[], GENERATED_FILE_NOTE, config);
+    if message_pairs
+        .iter()
+        .flat_map(|pair| pair.structs())
+        .chain(&shared.structs)
+        .any(has_deprecations)
+    {
+        code.push_str("#![allow(deprecated)]\n\n");
+    }
+    for import in optimize_imports(&imports) {
+        code.push_str(&import);
+        code.push('\n');
+    }
+    code.push('\n');
+    code.push_str(&items.join("\n"));
+
+    check_duplicate_definitions(&code)?;
+
+    let code = if config.rustfmt {
+        format_with_rustfmt(code, &output_path)
+    } else {
+        code
+    };
+    manifest.write(&output_path, &code)
+}
+
+/// 生成共享辅助类型文件 shared.rs（--share-helpers）
+pub fn generate_shared_file(
+    shared: &SharedTypes,
+    config: &Config,
+    manifest: &Manifest,
+) -> Result<bool, GenError> {
+    let output_path = PathBuf::from(&config.output_dir).join(format!("{}.rs", SHARED_MODULE));
+
+    if config.append_only && output_path.exists() {
+        return Ok(false);
+    }
+
+    let items = generate_shared_items(shared, config, &mut BTreeMap::new())?;
+
+    let mut code = file_header(&[], GENERATED_FILE_NOTE, config);
+    if shared.structs.iter().any(has_deprecations) {
+        code.push_str("#![allow(deprecated)]\n\n");
+    }
+    let mut imports: HashSet<String> = shared
+        .structs
+        .iter()
+        .flat_map(|info| info.imports.iter().cloned())
+        .collect();
+    if !shared.enums.is_empty() {
+        imports.insert("use serde::{Deserialize, Serialize};".to_string());
+    }
+    for import in optimize_imports(&used_imports(&imports, &items)) {
+        code.push_str(&import);
+        code.push('\n');
+    }
+    code.push('\n');
+    code.push_str(&items);
+
+    let code = if config.rustfmt {
+        format_with_rustfmt(code, &output_path)
+    } else {
+        code
+    };
+    manifest.write(&output_path, &code)
+}
+
+/// 生成共享的辅助枚举与结构体（嵌套的结构体在引用它的结构体之前）
+fn generate_shared_items(
+    shared: &SharedTypes,
+    config: &Config,
+    emitted: &mut BTreeMap<String, String>,
+) -> Result<String, GenError> {
+    let mut items = Vec::new();
+    for inline_enum in &shared.enums {
+        let enum_code = generate_inline_enum_code(inline_enum, config);
+        if first_definition(emitted, &inline_enum.name, &enum_code) {
+            items.push(enum_code);
+        }
+    }
+    for info in &shared.structs {
+        let struct_code = generate_struct_code(info, "definition", SHARED_MODULE, config)?;
+        if first_definition(emitted, &info.name, &struct_code) {
+            items.push(struct_code);
+        }
+    }
+    Ok(items.join("\n"))
+}
+
+/// 去掉生成代码中没有用到的导入，花括号中的名称逐个判断
+fn used_imports(imports: &HashSet<String>, code: &str) -> HashSet<String> {
+    let is_used = |name: &str| {
+        code.match_indices(name).any(|(start, _)| {
+            let before = code[..start].chars().next_back();
+            let after = code[start + name.len()..].chars().next();
+            let is_ident = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            !is_ident(before) && !is_ident(after)
+        })
+    };
+
+    imports
+        .iter()
+        .filter_map(|import| {
+            let Some(path) = import
+                .strip_prefix("use ")
+                .and_then(|path| path.strip_suffix(';'))
+            else {
+                return Some(import.clone());
+            };
+            match path.split_once('{') {
+                Some((prefix, names)) => {
+                    let names: Vec<&str> = names
+                        .trim_end_matches('}')
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty() && is_used(name))
+                        .collect();
+                    match names.as_slice() {
+                        [] => None,
+                        [name] => Some(format!("use {}{};", prefix, name)),
+                        names => Some(format!("use {}{{{}}};", prefix, names.join(", "))),
+                    }
+                }
+                None => {
+                    let name = path.rsplit("::").next().unwrap_or(path);
+                    is_used(name).then(|| import.clone())
+                }
+            }
+        })
+        .collect()
+}
+
+/// 检查合并后的代码中没有重复的顶层定义（类型、函数、静态变量与常量）
+fn check_duplicate_definitions(code: &str) -> Result<(), GenError> {
+    let mut seen = HashSet::new();
+    for line in code.lines() {
+        let item = line.strip_prefix("pub ").unwrap_or(line);
+        let Some(rest) = [
+            "struct ", "enum ", "fn ", "static ", "const ", "type ", "mod ",
+        ]
+        .iter()
+        .find_map(|keyword| item.strip_prefix(keyword)) else {
+            continue;
+        };
+        let name: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if !seen.insert(name.clone()) {
+            return Err(GenError::unsupported(format!(
+                "{} is defined more than once in {}",
+                name, SINGLE_FILE
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 生成模块文件
+pub fn generate_mod_file(
+    message_pairs: &[&MessagePair],
+    config: &Config,
+    manifest: &Manifest,
+) -> Result<bool, GenError> {
+    let mod_path = PathBuf::from(&config.output_dir).join("mod.rs");
+    let mut code = file_header(
+        &[],
+        "Edits outside the GEN-BEGIN/GEN-END markers are kept across runs.",
+        config,
+    );
+
+    // 收集所有模块名并排序
+    let mut all_modules: Vec<(String, &MessagePair)> = message_pairs
+        .iter()
+        .map(|pair| (pair.base_name.to_case(Case::Snake), *pair))
+        .collect();
+
+    // 按模块名排序
+    all_modules.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // 共享的字段限制常量模块
+    if config.emit_action_registry {
+        code.push_str("pub mod actions;\n");
+    }
+    if config.emit_constants_module {
+        code.push_str("pub mod constants;\n");
+    }
+    if config.emit_validate_dispatch {
+        code.push_str("pub mod dispatch;\n");
+    }
+    if config.try_from_value {
+        code.push_str(&format!("pub mod {};\n", ERRORS_MODULE));
+    }
+    if config.share_helpers {
+        code.push_str(&format!("pub mod {};\n", SHARED_MODULE));
+    }
+
+    // 添加模块声明
+    for (module_name, pair) in &all_modules {
+        push_feature_gate(&mut code, pair, config);
+        code.push_str(&format!("pub mod {};\n", module_name));
+    }
+
+    code.push('\n');
+
+    if config.emit_action_registry {
+        code.push_str("pub use actions::Action;\n");
+    }
+    if config.try_from_value {
+        code.push_str(&format!("pub use {}::FromValueError;\n", ERRORS_MODULE));
+    }
+
+    // 添加重新导出，配对消息在前，独立消息在后
+    let (paired_exports, standalone_exports): (Vec<_>, Vec<_>) =
+        all_modules.iter().partition(|(_, pair)| pair.is_complete());
+
+    for (module_name, pair) in paired_exports.iter().chain(standalone_exports.iter()) {
+        let exported_types: Vec<&str> = pair
+            .definition_enums
+            .iter()
+            .map(|info| info.name.as_str())
+            .chain(
+                pair.structs()
+                    .filter(|info| !(config.skip_deprecated_exports && info.deprecated.is_some()))
+                    .map(|info| info.name.as_str()),
+            )
+            .collect();
+
+        if !exported_types.is_empty() {
+            // 重新导出与模块声明使用相同的门控，否则关闭 feature 后无法编译
+            push_feature_gate(&mut code, pair, config);
+        }
+
+        match exported_types.as_slice() {
+            [] => {}
+            [single] => code.push_str(&format!("pub use {}::{};\n", module_name, single)),
+            types => code.push_str(&format!(
+                "pub use {}::{{{}}};\n",
+                module_name,
+                types.join(", ")
+            )),
+        }
+    }
+
+    // 保留标记区域之外的手写内容，只重写生成区域
+    let existing = fs::read_to_string(&mod_path).ok();
+    let merged = merge_generated_region(existing.as_deref(), &code);
+
+    // 追加模式下没有新模块时保持 mod.rs 不变
+    if config.append_only && existing.as_deref() == Some(merged.as_str()) {
+        return Ok(false);
+    }
+
+    manifest.write(&mod_path, &merged)
+}
+
+/// 生成 constants.rs，按结构体分模块汇总 schema 中的字段限制
+pub fn generate_constants_module(
+    message_pairs: &[&MessagePair],
+    config: &Config,
+    manifest: &Manifest,
+) -> Result<bool, GenError> {
+    let constants_path = PathBuf::from(&config.output_dir).join("constants.rs");
+    let mut code = file_header(&[], GENERATED_FILE_NOTE, config);
+
+    code.push_str("//! Field limits collected from the OCPP schemas.\n");
+
+    let mut structs: Vec<&StructInfo> = message_pairs.iter().flat_map(|p| p.structs()).collect();
+    structs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    for struct_info in structs {
+        let constants: Vec<String> = struct_info
+            .fields
+            .iter()
+            .flat_map(field_limit_constants)
+            .collect();
+        if constants.is_empty() {
+            continue;
+        }
+
+        code.push('\n');
+        code.push_str(&format!("/// Limits of `{}` fields.\n", struct_info.name));
+        code.push_str(&format!(
+            "pub mod {} {{\n",
+            struct_info.name.to_case(Case::Snake)
+        ));
+        for constant in constants {
+            code.push_str(&format!("    {}\n", constant));
+        }
+        code.push_str("}\n");
+    }
+
+    manifest.write(&constants_path, &code)
+}
+
+/// 生成 dispatch.rs：按 action 名称反序列化并验证请求负载
+pub fn generate_validate_dispatch(
+    message_pairs: &[&MessagePair],
+    config: &Config,
+    manifest: &Manifest,
+) -> Result<bool, GenError> {
+    let dispatch_path = PathBuf::from(&config.output_dir).join("dispatch.rs");
+    let mut code = file_header(&[], GENERATED_FILE_NOTE, config);
+
+    let mut requests: Vec<(&MessagePair, &StructInfo)> = message_pairs
+        .iter()
+        .filter_map(|pair| pair.request.as_ref().map(|request| (*pair, request)))
+        .collect();
+    requests.sort_by(|a, b| a.0.base_name.cmp(&b.0.base_name));
+
+    code.push_str("//! Validation dispatch for request payloads by OCPP action name.\n");
+    code.push('\n');
+    if !requests.is_empty() {
+        code.push_str("use validator::Validate;\n");
+        code.push('\n');
+    }
+    code.push_str("/// Error returned by [`validate_message`].\n");
+    code.push_str("#[derive(Debug)]\n");
+    code.push_str("pub enum ValidateMessageError {\n");
+    code.push_str("    /// The action name does not match any generated request.\n");
+    code.push_str("    UnknownAction(String),\n");
+    code.push_str("    /// The payload is not valid JSON for the request type.\n");
+    code.push_str("    Json(serde_json::Error),\n");
+    code.push_str("    /// The payload violates the request's constraints.\n");
+    code.push_str("    Validation(validator::ValidationErrors),\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("impl std::fmt::Display for ValidateMessageError {\n");
+    code.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n");
+    code.push_str("        match self {\n");
+    code.push_str(
+        "            Self::UnknownAction(action) => write!(f, \"unknown action: {}\", action),\n",
+    );
+    code.push_str("            Self::Json(e) => write!(f, \"invalid JSON payload: {}\", e),\n");
+    code.push_str("            Self::Validation(e) => write!(f, \"invalid payload: {}\", e),\n");
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("impl std::error::Error for ValidateMessageError {}\n");
+    code.push('\n');
+    code.push_str("/// Deserializes `json` into the request type for `action` and validates it.\n");
+    if requests
+        .iter()
+        .any(|(_, request)| request.deprecated.is_some())
+    {
+        code.push_str("#[allow(deprecated)]\n");
+    }
+    code.push_str(
+        "pub fn validate_message(json: &str, action: &str) -> Result<(), ValidateMessageError> {\n",
+    );
+    if requests.is_empty() {
+        code.push_str("    let _ = json;\n");
+    }
+    code.push_str("    match action {\n");
+    for (pair, request) in &requests {
+        let module_name = pair.base_name.to_case(Case::Snake);
+        if config.feature_gates {
+            if let Some(feature) = &pair.feature {
+                code.push_str(&format!("        #[cfg(feature = \"{}\")]\n", feature));
+            }
+        }
+        code.push_str(&format!("        {:?} => {{\n", pair.base_name));
+        code.push_str(&format!(
+            "            let message: super::{}::{} =\n",
+            module_name, request.name
+        ));
+        code.push_str(
+            "                serde_json::from_str(json).map_err(ValidateMessageError::Json)?;\n",
+        );
+        code.push_str("            message\n");
+        code.push_str("                .validate()\n");
+        code.push_str("                .map_err(ValidateMessageError::Validation)\n");
+        code.push_str("        }\n");
+    }
+    code.push_str("        _ => Err(ValidateMessageError::UnknownAction(action.to_string())),\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    manifest.write(&dispatch_path, &code)
+}
+
+/// TryFrom<serde_json::Value> 错误类型所在的模块名
+const ERRORS_MODULE: &str = "errors";
+
+/// 生成 errors.rs：消息的 TryFrom<serde_json::Value> 实现共用的错误类型（--try-from-value）
+pub fn generate_errors_module(config: &Config, manifest: &Manifest) -> Result<bool, GenError> {
+    let errors_path = PathBuf::from(&config.output_dir).join(format!("{}.rs", ERRORS_MODULE));
+    let mut code = file_header(&[], GENERATED_FILE_NOTE, config);
+
+    code.push_str("//! Errors returned when converting JSON values into messages.\n");
+    code.push('\n');
+    code.push_str(&generate_from_value_error());
+
+    manifest.write(&errors_path, &code)
+}
+
+/// 生成 FromValueError：区分反序列化失败与约束验证失败
+fn generate_from_value_error() -> String {
+    let mut code = String::new();
+
+    code.push_str("/// Error returned when converting a `serde_json::Value` into a message.\n");
+    code.push_str("#[derive(Debug)]\n");
+    code.push_str("pub enum FromValueError {\n");
+    code.push_str("    /// The value does not have the shape of the message.\n");
+    code.push_str("    Json(serde_json::Error),\n");
+    code.push_str("    /// The message violates its constraints.\n");
+    code.push_str("    Validation(validator::ValidationErrors),\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("impl std::fmt::Display for FromValueError {\n");
+    code.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n");
+    code.push_str("        match self {\n");
+    code.push_str("            Self::Json(e) => write!(f, \"invalid message JSON: {}\", e),\n");
+    code.push_str("            Self::Validation(e) => write!(f, \"invalid message: {}\", e),\n");
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("impl std::error::Error for FromValueError {}\n");
+
+    code
+}
+
+/// 生成 actions.rs：列出所有消息 action 的 `Action` 枚举
+///
+/// 仅含 definitions 的模块不是 action；没有 Request/Response 后缀的独立消息视为请求。
+pub fn generate_action_registry(
+    message_pairs: &[&MessagePair],
+    config: &Config,
+    manifest: &Manifest,
+) -> Result<bool, GenError> {
+    let actions_path = PathBuf::from(&config.output_dir).join("actions.rs");
+    let mut code = file_header(&[], GENERATED_FILE_NOTE, config);
+
+    let mut actions: Vec<(&str, Option<&str>, Option<&str>)> = message_pairs
+        .iter()
+        .filter(|pair| pair.request.is_some() || pair.response.is_some())
+        .map(|pair| {
+            let request = pair.request.as_ref().map(|r| r.name.as_str());
+            let response = pair.response.as_ref().map(|r| r.name.as_str());
+            match (request, response) {
+                (None, Some(message)) if !message.ends_with("Response") => {
+                    (pair.base_name.as_str(), Some(message), None)
+                }
+                _ => (pair.base_name.as_str(), request, response),
+            }
+        })
+        .collect();
+    actions.sort_by(|a, b| a.0.cmp(b.0));
+
+    code.push_str("//! Registry of the OCPP actions that have generated messages.\n");
+    code.push('\n');
+    code.push_str("use std::fmt;\n");
+    code.push_str("use std::str::FromStr;\n");
+    code.push('\n');
+    code.push_str("/// An OCPP action, named as in the action field of a CALL message.\n");
+    code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n");
+    code.push_str("pub enum Action {\n");
+    for (name, _, _) in &actions {
+        code.push_str(&format!("    /// The `{}` action.\n", name));
+        code.push_str(&format!("    {},\n", name));
+    }
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("impl Action {\n");
+    code.push_str("    /// Every action, in name order.\n");
+    code.push_str("    pub const ALL: &'static [Action] = &[\n");
+    for (name, _, _) in &actions {
+        code.push_str(&format!("        Action::{},\n", name));
+    }
+    code.push_str("    ];\n");
+    code.push('\n');
+    code.push_str("    /// Returns the OCPP action name.\n");
+    code.push_str("    pub fn as_str(&self) -> &'static str {\n");
+    code.push_str("        match *self {\n");
+    for (name, _, _) in &actions {
+        code.push_str(&format!("            Self::{} => {:?},\n", name, name));
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push('\n');
+    code.push_str("    /// Returns the name of the request struct, if the action has one.\n");
+    code.push_str("    pub fn request_type(&self) -> Option<&'static str> {\n");
+    code.push_str("        match *self {\n");
+    for (name, request, _) in &actions {
+        push_type_name_arm(&mut code, name, *request);
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push('\n');
+    code.push_str("    /// Returns the name of the response struct, if the action has one.\n");
+    code.push_str("    pub fn response_type(&self) -> Option<&'static str> {\n");
+    code.push_str("        match *self {\n");
+    for (name, _, response) in &actions {
+        push_type_name_arm(&mut code, name, *response);
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("impl fmt::Display for Action {\n");
+    code.push_str("    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {\n");
+    code.push_str("        f.write_str(self.as_str())\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("/// Error returned when parsing an unknown action name.\n");
+    code.push_str("#[derive(Debug, Clone, PartialEq, Eq)]\n");
+    code.push_str("pub struct UnknownAction(pub String);\n");
+    code.push('\n');
+    code.push_str("impl fmt::Display for UnknownAction {\n");
+    code.push_str("    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {\n");
+    code.push_str("        write!(f, \"unknown action: {}\", self.0)\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+    code.push_str("impl std::error::Error for UnknownAction {}\n");
+    code.push('\n');
+    code.push_str("impl FromStr for Action {\n");
+    code.push_str("    type Err = UnknownAction;\n");
+    code.push('\n');
+    code.push_str("    /// Parses an OCPP action name; names are case-sensitive.\n");
+    code.push_str("    fn from_str(s: &str) -> Result<Self, Self::Err> {\n");
+    code.push_str("        Self::ALL\n");
+    code.push_str("            .iter()\n");
+    code.push_str("            .find(|action| action.as_str() == s)\n");
+    code.push_str("            .copied()\n");
+    code.push_str("            .ok_or_else(|| UnknownAction(s.to_string()))\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    manifest.write(&actions_path, &code)
+}
+
+/// 输出 `Action` 的请求/响应类型名方法中的一个 match 分支
+fn push_type_name_arm(code: &mut String, action: &str, type_name: Option<&str>) {
+    match type_name {
+        Some(type_name) => code.push_str(&format!(
+            "            Self::{} => Some({:?}),\n",
+            action, type_name
+        )),
+        None => code.push_str(&format!("            Self::{} => None,\n", action)),
+    }
+}
+
+/// 生成单个字段的限制常量声明
+fn field_limit_constants(field: &FieldInfo) -> Vec<String> {
+    let prefix = field.name.trim_end_matches('_').to_uppercase();
+    let mut constants = Vec::new();
+
+    let mut push_usize = |suffix: &str, value: Option<u32>| {
+        if let Some(value) = value {
+            constants.push(format!(
+                "pub const {}_{}: usize = {};",
+                prefix, suffix, value
+            ));
+        }
+    };
+    push_usize("MIN_LEN", field.min_length);
+    push_usize("MAX_LEN", field.max_length);
+    push_usize("MIN_ITEMS", field.min_items);
+    push_usize("MAX_ITEMS", field.max_items);
+
+    // 整数字段沿用字段类型，其余数值（Decimal、浮点）统一为 f64
+    let numeric_type = if is_integer_type(&field.rust_type) {
+        field.rust_type.as_str()
+    } else {
+        "f64"
+    };
+    for (suffix, value) in [("MIN", field.min_value), ("MAX", field.max_value)] {
+        if let Some(value) = value {
+            let literal = if numeric_type == "f64" {
+                format!("{:?}", value)
+            } else {
+                format!("{}", value as i64)
+            };
+            constants.push(format!(
+                "pub const {}_{}: {} = {};",
+                prefix, suffix, numeric_type, literal
+            ));
+        }
+    }
+
+    constants
+}
+
+/// 启用 --feature-gates 时为功能块内的模块输出 cfg 属性
+fn push_feature_gate(code: &mut String, pair: &MessagePair, config: &Config) {
+    if config.feature_gates {
+        if let Some(feature) = &pair.feature {
+            code.push_str(&format!("#[cfg(feature = \"{}\")]\n", feature));
+        }
+    }
+}
+
+/// mod.rs 中生成区域的起始标记
+const GEN_BEGIN_MARKER: &str = "// GEN-BEGIN";
+/// mod.rs 中生成区域的结束标记
+const GEN_END_MARKER: &str = "// GEN-END";
+
+/// 将生成的代码合并到已有文件的标记区域中
+///
+/// 若已有文件包含完整的标记对，则仅替换标记之间的内容；已有文件没有标记时（手写的
+/// mod.rs 首次交给生成器），保留其内容并在末尾追加由标记包裹的生成代码，其中与生成代码
+/// 相同的行（如旧版本生成的模块声明）去掉，避免重复定义；没有已有文件时只输出生成区域。
+fn merge_generated_region(existing: Option<&str>, generated: &str) -> String {
+    let region = format!("{}\n{}{}", GEN_BEGIN_MARKER, generated, GEN_END_MARKER);
+
+    let Some(existing) = existing else {
+        return format!("{}\n", region);
+    };
+    if let (Some(begin), Some(end)) = (
+        existing.find(GEN_BEGIN_MARKER),
+        existing.find(GEN_END_MARKER),
+    ) {
+        if begin < end {
+            return format!(
+                "{}{}{}",
+                &existing[..begin],
+                region,
+                &existing[end + GEN_END_MARKER.len()..]
+            );
+        }
+    }
+
+    let generated_lines: HashSet<&str> = generated
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let kept: Vec<&str> = existing
+        .lines()
+        .filter(|line| !generated_lines.contains(line.trim()))
+        .collect();
+    let kept = kept.join("\n");
+    let kept = kept.trim();
+    if kept.is_empty() {
+        format!("{}\n", region)
+    } else {
+        format!("{}\n\n{}\n", kept, region)
+    }
+}
+
+/// 优化导入语句，将同一路径的导入合并（不限于 crate 内部路径），使用现有项目的多行格式
+fn optimize_imports(imports: &HashSet<String>) -> Vec<String> {
+    // 键为（可见性前缀, 路径），如 ("use", "crate::v2_1::datatypes")、("pub use", "super::shared")
+    let mut grouped_imports: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    let mut other_imports = Vec::new();
+
+    // 先按字典序遍历，使输出不依赖 HashSet 的迭代顺序
+    let sorted_imports: BTreeSet<&String> = imports.iter().collect();
+
+    for import in sorted_imports {
+        match parse_import(import) {
+            ImportType::Grouped {
+                prefix,
+                path,
+                items,
+            } => {
+                grouped_imports
+                    .entry((prefix, path))
+                    .or_default()
+                    .extend(items);
+            }
+            ImportType::Other(import_str) => {
+                other_imports.push(import_str);
+            }
+        }
+    }
+
+    let mut crate_imports = Vec::new();
+    for ((prefix, path), mut items) in grouped_imports {
+        items.sort();
+        items.dedup();
+
+        let import = if items.len() == 1 {
+            format!("{} {}::{};", prefix, path, items[0])
+        } else if items.len() <= 3 {
+            // 短列表使用单行格式
+            format!("{} {}::{{{}}};", prefix, path, items.join(", "))
+        } else {
+            // 长列表使用多行格式，匹配现有项目风格
+            let mut multi_line_import = format!("{} {}::{{\n", prefix, path);
+            for (i, item) in items.iter().enumerate() {
+                if i == items.len() - 1 {
+                    multi_line_import.push_str(&format!("    {},\n", item));
+                } else {
+                    multi_line_import.push_str(&format!("    {}, \n", item));
+                }
+            }
+            multi_line_import.push_str("};");
+            multi_line_import
+        };
+
+        if prefix == "use" && (path == "crate" || path.starts_with("crate::")) {
+            crate_imports.push(import);
+        } else {
+            other_imports.push(import);
+        }
+    }
+
+    // crate 导入在前，其余导入按字典序排在其后
+    other_imports.sort();
+    other_imports.dedup();
+    crate_imports.extend(other_imports);
+
+    crate_imports
+}
+
+#[derive(Debug)]
+enum ImportType {
+    Grouped {
+        prefix: String,
+        path: String,
+        items: Vec<String>,
+    },
+    Other(String),
+}
+
+/// 解析导入语句：`use path::Item;` 与 `use path::{A, B};` 按路径分组，嵌套分组等其他形式保持原样
+fn parse_import(import: &str) -> ImportType {
+    let other = || ImportType::Other(import.to_string());
+    let Some((prefix, rest)) = ["use ", "pub use "]
+        .iter()
+        .find_map(|prefix| Some((prefix.trim_end(), import.strip_prefix(prefix)?)))
+    else {
+        return other();
+    };
+    let Some(rest) = rest.strip_suffix(';') else {
+        return other();
+    };
+
+    let (path, items) = match rest.split_once("::{") {
+        Some((path, list)) => {
+            let Some(list) = list.strip_suffix('}') else {
+                return other();
+            };
+            let items: Vec<String> = list
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect();
+            (path, items)
+        }
+        // 找到最后一个 :: 来分离路径和导入项
+        None => match rest.rsplit_once("::") {
+            Some((path, item)) => (path, vec![item.to_string()]),
+            None => return other(),
+        },
+    };
+
+    if items.is_empty()
+        || items.iter().any(|item| item.contains(['{', '}', ':']))
+        || path.contains('{')
+    {
+        return other();
+    }
+    ImportType::Grouped {
+        prefix: prefix.to_string(),
+        path: path.to_string(),
+        items,
+    }
+}
+
+/// 生成结构体的实现块
+fn generate_impl_block(
+    struct_info: &StructInfo,
+    message_type: &str,
+    action: &str,
+    config: &Config,
+) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    code.push_str(&format!("impl {} {{\n", struct_info.name));
+
+    // OCPP-J 帧中的消息类型编号（2 = Call，3 = CallResult）
+    if config.message_type_ids {
+        let type_id = match message_type {
+            "request" => Some((2, "Call")),
+            "response" => Some((3, "CallResult")),
+            _ => None,
+        };
+        if let Some((id, frame)) = type_id {
+            code.push_str(&format!(
+                "    /// OCPP-J message type ID of the {} frame carrying this message.\n",
+                frame
+            ));
+            code.push_str(&format!("    pub const MESSAGE_TYPE_ID: u8 = {};\n", id));
+            code.push('\n');
+        }
+    }
+
+    // Generate new method
+    code.push_str(&generate_new_method(struct_info, config)?);
+    code.push('\n');
+
+    // Generate validating constructor
+    if config.try_new {
+        code.push_str(&generate_try_new_method(struct_info, config)?);
+        code.push('\n');
+    }
+
+    // Generate example constructor
+    if config.examples {
+        code.push_str(&generate_example_method(struct_info, config));
+        code.push('\n');
+    }
+
+    // Generate action accessor (data types from definitions are not messages)
+    if message_type != "definition" {
+        code.push_str(&generate_action_method(action));
+        code.push('\n');
+    }
+
+    // Generate OCPP-J framing helpers
+    if config.ocpp_j {
+        if let Some(method) = generate_frame_method(message_type, action) {
+            code.push_str(&method);
+            code.push('\n');
+        }
+    }
+
+    // Generate setter methods
+    for field in &struct_info.fields {
+        code.push_str(&generate_setter_method(&struct_info.name, field, config)?);
+        code.push('\n');
+    }
+
+    // Generate getter methods
+    for field in &struct_info.fields {
+        code.push_str(&generate_getter_method(field, config)?);
+        code.push('\n');
+    }
+
+    // Generate with methods for optional fields
+    for field in &struct_info.fields {
+        if field.is_optional {
+            code.push_str(&generate_with_method(field, config)?);
+            code.push('\n');
+        }
+    }
+
+    // Generate unset/is_set helpers for optional fields
+    for field in &struct_info.fields {
+        if field.is_optional {
+            code.push_str(&generate_unset_method(field, config));
+            code.push('\n');
+            code.push_str(&generate_is_set_method(field, config));
+            code.push('\n');
+        }
+    }
+
+    // Generate len/is_empty/push helpers for list fields
+    for field in &struct_info.fields {
+        if field.rust_type.starts_with("Vec<") {
+            code.push_str(&generate_vec_methods(field, config));
+            code.push('\n');
+        }
+    }
+
+    // Generate contains/insert helpers for enum set fields
+    for field in &struct_info.fields {
+        if field.rust_type.starts_with("HashSet<") {
+            code.push_str(&generate_set_methods(field)?);
+            code.push('\n');
+        }
+    }
+
+    // Generate iter() delegating to the configured collection field
+    if let Some((field, item_type)) = iter_field(struct_info, config) {
+        code.push_str(&generate_iter_method(field, item_type));
+        code.push('\n');
+    }
+
+    // Generate fast-fail validation method if enabled
+    if config.validate_fast {
+        code.push_str(&generate_validate_fast_method(struct_info, config)?);
+        code.push('\n');
+    }
+
+    // 去掉最后一个方法后的空行
+    if code.ends_with("\n\n") {
+        code.pop();
+    }
+    code.push_str("}\n");
+    Ok(code)
+}
+
+/// 生成返回 OCPP action 名称的方法
+fn generate_action_method(action: &str) -> String {
+    let mut code = String::new();
+
+    code.push_str("    /// Returns the OCPP action name of this message.\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str(&format!("    /// The `{}` action name.\n", action));
+    code.push_str("    pub fn action(&self) -> &'static str {\n");
+    code.push_str(&format!("        {:?}\n", action));
+    code.push_str("    }\n");
+
+    code
+}
+
+/// 生成将消息包装为 OCPP-J 帧的方法：请求为 `[2, id, action, payload]` 的 Call，
+/// 响应为 `[3, id, payload]` 的 CallResult（CallResult 不含 action）
+fn generate_frame_method(message_type: &str, action: &str) -> Option<String> {
+    let mut code = String::new();
+
+    match message_type {
+        "request" => {
+            code.push_str("    /// Wraps the message in an OCPP-J Call frame.\n");
+            code.push_str("    ///\n");
+            code.push_str("    /// # Returns\n");
+            code.push_str("    ///\n");
+            code.push_str(&format!(
+                "    /// The `[2, unique_id, \"{}\", payload]` array.\n",
+                action
+            ));
+            code.push_str("    pub fn to_call(&self, unique_id: &str) -> serde_json::Value {\n");
+            code.push_str(&format!(
+                "        serde_json::json!([2, unique_id, {:?}, self])\n",
+                action
+            ));
+        }
+        "response" => {
+            code.push_str("    /// Wraps the message in an OCPP-J CallResult frame.\n");
+            code.push_str("    ///\n");
+            code.push_str("    /// # Returns\n");
+            code.push_str("    ///\n");
+            code.push_str("    /// The `[3, unique_id, payload]` array.\n");
+            code.push_str(
+                "    pub fn to_call_result(&self, unique_id: &str) -> serde_json::Value {\n",
+            );
+            code.push_str("        serde_json::json!([3, unique_id, self])\n");
+        }
+        _ => return None,
+    }
+    code.push_str("    }\n");
+
+    Some(code)
+}
+
+/// 生成 new 方法
+fn generate_new_method(struct_info: &StructInfo, config: &Config) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    // Collect required fields
+    let required_fields: Vec<&FieldInfo> = struct_info
+        .fields
+        .iter()
+        .filter(|field| !field.is_optional && fixed_value_expr(field).is_none())
+        .collect();
+
+    // Generate method signature
+    code.push_str("    /// Creates a new instance of the struct.\n");
+    code.push_str("    ///\n");
+
+    // Add parameter documentation
+    if !required_fields.is_empty() {
+        for field in &required_fields {
+            let param_doc = if let Some(description) = &field.description {
+                description.clone()
+            } else {
+                format!("The {} field", field.name)
+            };
+            push_param_doc(&mut code, &field.name, &param_doc, config);
+        }
+        code.push_str("    ///\n");
+    }
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    if struct_info
+        .fields
+        .iter()
+        .any(|field| schema_default_expr(field).is_some())
+    {
+        code.push_str("    /// A new instance with required fields set and optional fields at their default or None.\n");
+    } else {
+        code.push_str("    /// A new instance of the struct with required fields set and optional fields as None.\n");
+    }
+
+    // Add parameters
+    let params: Vec<String> = required_fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, field.rust_type))
+        .collect();
+    push_fn_signature(&mut code, "new", &params, "Self", config);
+    if struct_info.fields.is_empty() {
+        code.push_str("        Self {}\n");
+    } else {
+        code.push_str("        Self {\n");
+
+        // Initialize fields
+        for field in &struct_info.fields {
+            if schema_default_expr(field).is_some() {
+                code.push_str(&format!(
+                    "            {}: {}(),\n",
+                    field.name,
+                    default_fn_name(&struct_info.name, field)
+                ));
+            } else if field.is_optional {
+                code.push_str(&format!("            {}: None,\n", field.name));
+            } else if let Some(expr) = fixed_value_expr(field) {
+                code.push_str(&format!("            {}: {},\n", field.name, expr));
+            } else {
+                code.push_str(&format!("            {},\n", field.name));
+            }
+        }
+
+        code.push_str("        }\n");
+    }
+    code.push_str("    }\n");
+
+    Ok(code)
+}
+
+/// 生成 try_new 方法，通过 new 构造后立即验证
+fn generate_try_new_method(struct_info: &StructInfo, config: &Config) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    let required_fields: Vec<&FieldInfo> = struct_info
+        .fields
+        .iter()
+        .filter(|field| !field.is_optional && fixed_value_expr(field).is_none())
+        .collect();
+
+    code.push_str("    /// Creates a new instance of the struct and validates it.\n");
+    code.push_str("    ///\n");
+    if !required_fields.is_empty() {
+        for field in &required_fields {
+            let param_doc = if let Some(description) = &field.description {
+                description.clone()
+            } else {
+                format!("The {} field", field.name)
+            };
+            push_param_doc(&mut code, &field.name, &param_doc, config);
+        }
+        code.push_str("    ///\n");
+    }
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// The new instance, or the validation errors of its required fields.\n");
+
+    let params: Vec<String> = required_fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, field.rust_type))
+        .collect();
+    push_fn_signature(
+        &mut code,
+        "try_new",
+        &params,
+        "Result<Self, validator::ValidationErrors>",
+        config,
+    );
+    let args: Vec<&str> = required_fields
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect();
+    code.push_str(&format!(
+        "        let message = Self::new({});\n",
+        args.join(", ")
+    ));
+    code.push_str("        message.validate()?;\n");
+    code.push_str("        Ok(message)\n");
+    code.push_str("    }\n");
+
+    Ok(code)
+}
+
+/// 生成 example 方法，返回各字段取满足约束的示例值的实例（--examples）
+///
+/// 必填字段无法构造示例值时（如带 pattern 的字符串或本文件之外的类型）不生成该方法。
+fn generate_example_method(struct_info: &StructInfo, config: &Config) -> String {
+    let mut types = SampleTypes::default();
+    types.add_struct(struct_info);
+    let examples = ExampleTypes {
+        types: &types,
+        config,
+    };
+    let Some(values) = examples.struct_example(struct_info) else {
+        // 与 --gen-tests 一样以注释说明缺少的示例值，并提示是哪个字段
+        let field = struct_info
+            .fields
+            .iter()
+            .find(|field| examples.field_example(&struct_info.name, field).is_none())
+            .expect("a struct without an example has a field without one");
+        log::warn!(
+            "{} gets no example(): no sample value for `{}` of type {}",
+            struct_info.name,
+            field.name,
+            field.rust_type
+        );
+        tracing::warn!(
+            name = %struct_info.name,
+            field = %field.name,
+            "no example() generated"
+        );
+        return format!(
+            "    // No example() for {}: no sample value for `{}` of type {}\n",
+            struct_info.name, field.name, field.rust_type
+        );
+    };
+
+    let mut code = String::new();
+    code.push_str(
+        "    /// Returns an example instance whose fields hold sample values satisfying the schema.\n",
+    );
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// An instance that passes validation, for documentation and tests.\n");
+    code.push_str("    pub fn example() -> Self {\n");
+    if values.is_empty() {
+        code.push_str("        Self {}\n");
+    } else {
+        code.push_str("        Self {\n");
+        for (name, value) in values {
+            code.push_str(&format!("            {}: {},\n", name, value));
+        }
+        code.push_str("        }\n");
+    }
+    code.push_str("    }\n");
+
+    code
+}
+
+/// 生成 setter 方法
+fn generate_setter_method(
+    struct_name: &str,
+    field: &FieldInfo,
+    config: &Config,
+) -> Result<String, GenError> {
+    if config.checked_setters {
+        let checks = field_checks(struct_name, field, config);
+        if !checks.is_empty() {
+            return Ok(generate_checked_setter_method(field, &checks, config));
+        }
+    }
+
+    let mut code = String::new();
+
+    let param_doc = if let Some(description) = &field.description {
+        description.clone()
+    } else {
+        format!("The {} field", field.name)
+    };
+
+    // 可选字段保持具体类型，避免传入 None 时无法推断泛型
+    let into_type = if field.is_optional {
+        None
+    } else {
+        into_param_type(field, config)
+    };
+    let field_type = if field.is_optional {
+        format!("Option<{}>", field.rust_type)
+    } else {
+        into_type.clone().unwrap_or_else(|| field.rust_type.clone())
+    };
+    let value = if into_type.is_some() {
+        format!("{}.into()", field.name)
+    } else {
+        field.name.clone()
+    };
+
+    code.push_str(&format!("    /// Sets the {} field.\n", field.name));
+    code.push_str("    ///\n");
+    push_param_doc(&mut code, &field.name, &param_doc, config);
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// A mutable reference to self for method chaining.\n");
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("set_{}", field.name),
+        &[
+            "&mut self".to_string(),
+            format!("{}: {}", field.name, field_type),
+        ],
+        "&mut Self",
+        config,
+    );
+    code.push_str(&format!("        self.{} = {};\n", field.name, value));
+    code.push_str("        self\n");
+    code.push_str("    }\n");
+
+    Ok(code)
+}
+
+/// --into-setters 模式下 String/Vec 字段的参数类型（impl Into<T>）
+fn into_param_type(field: &FieldInfo, config: &Config) -> Option<String> {
+    if config.into_setters && (field.rust_type == "String" || field.rust_type.starts_with("Vec<")) {
+        Some(format!("impl Into<{}>", field.rust_type))
+    } else {
+        None
+    }
+}
+
+/// 生成先检查约束再赋值的 setter，约束不满足时返回对应的 ValidationError
+fn generate_checked_setter_method(field: &FieldInfo, checks: &[String], config: &Config) -> String {
+    let mut code = String::new();
+
+    let param_doc = if let Some(description) = &field.description {
+        description.clone()
+    } else {
+        format!("The {} field", field.name)
+    };
+
+    let into_type = if field.is_optional {
+        None
+    } else {
+        into_param_type(field, config)
+    };
+    let field_type = if field.is_optional {
+        format!("Option<{}>", field.rust_type)
+    } else {
+        into_type.clone().unwrap_or_else(|| field.rust_type.clone())
+    };
+
+    code.push_str(&format!(
+        "    /// Sets the {} field after checking its schema constraints.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    push_param_doc(&mut code, &field.name, &param_doc, config);
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str(
+        "    /// A mutable reference to self for method chaining, or the violated constraint.\n",
+    );
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("set_{}", field.name),
+        &[
+            "&mut self".to_string(),
+            format!("{}: {}", field.name, field_type),
+        ],
+        "Result<&mut Self, validator::ValidationError>",
+        config,
+    );
+
+    if into_type.is_some() {
+        code.push_str(&format!(
+            "        let {}: {} = {}.into();\n",
+            field.name, field.rust_type, field.name
+        ));
+    }
+    let indent = if field.is_optional {
+        code.push_str(&format!(
+            "        if let Some(value) = &{} {{\n",
+            field.name
+        ));
+        "            "
+    } else {
+        code.push_str(&format!("        let value = &{};\n", field.name));
+        "        "
+    };
+    for check in checks {
+        for line in check.lines() {
+            code.push_str(&format!("{}{}\n", indent, line));
+        }
+    }
+    if field.is_optional {
+        code.push_str("        }\n");
+    }
+    code.push_str(&format!("        self.{} = {};\n", field.name, field.name));
+    code.push_str("        Ok(self)\n");
+    code.push_str("    }\n");
+
+    code
+}
+
+/// 生成单个字段的约束检查语句，检查与 `#[validate]` 属性使用相同的边界
+///
+/// 语句中 `value` 为字段值（非 Option）的引用，约束不满足时返回 `ValidationError`。
+/// 嵌套类型的验证不在此列。
+fn field_checks(struct_name: &str, field: &FieldInfo, config: &Config) -> Vec<String> {
+    let mut checks = Vec::new();
+    if !field.needs_validation {
+        return checks;
+    }
+
+    let fail = |condition: &str, code: &str| {
+        format!(
+            "if {} {{\n    return Err(validator::ValidationError::new(\"{}\"));\n}}",
+            condition, code
+        )
+    };
+
+    let violations = constraint_violations(field, "value", config);
+    if !violations.is_empty() {
+        let code = if is_copy_type(&field.rust_type) {
+            "range"
+        } else {
+            "length"
+        };
+        checks.push(fail(&violations.join(" || "), code));
+    }
+    if field.pattern.is_some() {
+        checks.push(fail(
+            &format!(
+                "!{}.is_match(value)",
+                pattern_regex_name(struct_name, field)
+            ),
+            "regex",
+        ));
+    }
+    match field.string_format.as_deref() {
+        Some("email") => checks.push(fail(
+            "!validator::ValidateEmail::validate_email(value)",
+            "email",
+        )),
+        Some("uri") => checks.push(fail("!validator::ValidateUrl::validate_url(value)", "url")),
+        _ => {}
+    }
+    if field.rust_type == "Decimal" && generate_decimal_range_fn(struct_name, field).is_some() {
+        checks.push(format!(
+            "{}(value)?;",
+            decimal_range_fn_name(struct_name, field)
+        ));
+    }
+    if has_multiple_of_check(field) {
+        let arg = if field.rust_type == "Decimal" {
+            "value"
+        } else {
+            "*value"
+        };
+        checks.push(format!(
+            "{}({})?;",
+            multiple_of_fn_name(struct_name, field),
+            arg
+        ));
+    }
+    if let Some(violation) = const_violation(field, "value") {
+        checks.push(fail(&violation, "const"));
+    }
+
+    checks
+}
+
+/// 生成 getter 方法
+fn generate_getter_method(field: &FieldInfo, config: &Config) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    let param_doc = if let Some(description) = &field.description {
+        description.clone()
+    } else {
+        format!("The {} field", field.name)
+    };
+
+    // Copy 基本类型按值返回，其余类型返回引用
+    let by_value = is_copy_type(&field.rust_type);
+    let return_type = match (field.is_optional, by_value) {
+        (true, true) => format!("Option<{}>", field.rust_type),
+        (true, false) => format!("Option<&{}>", field.rust_type),
+        (false, true) => field.rust_type.clone(),
+        (false, false) => format!("&{}", field.rust_type),
+    };
+
+    if by_value {
+        code.push_str(&format!(
+            "    /// Gets the value of the {} field.\n",
+            field.name
+        ));
+    } else {
+        code.push_str(&format!(
+            "    /// Gets a reference to the {} field.\n",
+            field.name
+        ));
+    }
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    push_wrapped_doc(&mut code, "    ", &param_doc, "", config.max_line_length);
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("get_{}", field.name),
+        &["&self".to_string()],
+        &return_type,
+        config,
+    );
+
+    if by_value {
+        code.push_str(&format!("        self.{}\n", field.name));
+    } else if field.is_optional {
+        code.push_str(&format!("        self.{}.as_ref()\n", field.name));
+    } else {
+        code.push_str(&format!("        &self.{}\n", field.name));
+    }
+
+    code.push_str("    }\n");
+
+    // 数组字段额外生成迭代器访问器
+    if config.iter_accessors {
+        if let Some(item_type) = field
+            .rust_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            code.push('\n');
+            code.push_str(&format!(
+                "    /// Returns an iterator over the {} items.\n",
+                field.name
+            ));
+            if field.is_optional {
+                code.push_str("    ///\n");
+                code.push_str("    /// Yields nothing when the field is not set.\n");
+            }
+            push_deprecated_attr(&mut code, field);
+            push_fn_signature(
+                &mut code,
+                &format!("iter_{}", field.name),
+                &["&self".to_string()],
+                &format!("impl Iterator<Item = &{}>", item_type),
+                config,
+            );
+            if field.is_optional {
+                code.push_str(&format!("        self.{}.iter().flatten()\n", field.name));
+            } else {
+                code.push_str(&format!("        self.{}.iter()\n", field.name));
+            }
+            code.push_str("    }\n");
+        }
+    }
+
+    Ok(code)
+}
+
+/// 生成 with 方法（仅用于可选字段）
+fn generate_with_method(field: &FieldInfo, config: &Config) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    let param_doc = if let Some(description) = &field.description {
+        description.clone()
+    } else {
+        format!("The {} field", field.name)
+    };
+
+    code.push_str(&format!(
+        "    /// Sets the {} field and returns self for builder pattern.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    push_param_doc(&mut code, &field.name, &param_doc, config);
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// Self with the field set.\n");
+    let into_type = into_param_type(field, config);
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("with_{}", field.name),
+        &[
+            "mut self".to_string(),
+            format!(
+                "{}: {}",
+                field.name,
+                into_type.as_deref().unwrap_or(&field.rust_type)
+            ),
+        ],
+        "Self",
+        config,
+    );
+    if into_type.is_some() {
+        code.push_str(&format!(
+            "        self.{} = Some({}.into());\n",
+            field.name, field.name
+        ));
+    } else {
+        code.push_str(&format!(
+            "        self.{} = Some({});\n",
+            field.name, field.name
+        ));
+    }
+    code.push_str("        self\n");
+    code.push_str("    }\n");
+
+    Ok(code)
+}
+
+/// 生成将可选字段清空为 None 的 unset 方法（字段名的关键字后缀 `_` 会被去掉）
+fn generate_unset_method(field: &FieldInfo, config: &Config) -> String {
+    let mut code = String::new();
+
+    code.push_str(&format!("    /// Clears the {} field.\n", field.name));
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// A mutable reference to self for method chaining.\n");
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("unset_{}", field.name.trim_end_matches('_')),
+        &["&mut self".to_string()],
+        "&mut Self",
+        config,
+    );
+    code.push_str(&format!("        self.{} = None;\n", field.name));
+    code.push_str("        self\n");
+    code.push_str("    }\n");
+
+    code
+}
+
+/// 生成判断可选字段是否有值的 is_set 方法
+fn generate_is_set_method(field: &FieldInfo, config: &Config) -> String {
+    let mut code = String::new();
+
+    code.push_str(&format!(
+        "    /// Checks whether the {} field is set.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// `true` if the field has a value.\n");
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("is_{}_set", field.name.trim_end_matches('_')),
+        &["&self".to_string()],
+        "bool",
+        config,
+    );
+    code.push_str(&format!("        self.{}.is_some()\n", field.name));
+    code.push_str("    }\n");
+
+    code
+}
+
+/// 输出方法签名，超出行宽时将参数拆分为每行一个
+fn push_fn_signature(
+    code: &mut String,
+    name: &str,
+    params: &[String],
+    return_type: &str,
+    config: &Config,
+) {
+    let signature = format!(
+        "    pub fn {}({}) -> {} {{",
+        name,
+        params.join(", "),
+        return_type
+    );
+
+    if signature.chars().count() > config.max_line_length && !params.is_empty() {
+        code.push_str(&format!("    pub fn {}(\n", name));
+        for param in params {
+            code.push_str(&format!("        {},\n", param));
+        }
+        code.push_str(&format!("    ) -> {} {{\n", return_type));
+    } else {
+        code.push_str(&signature);
+        code.push('\n');
+    }
+}
+
+/// 输出参数文档行（`* `name` - doc`），超出行宽时换行并缩进续行
+fn push_param_doc(code: &mut String, name: &str, doc: &str, config: &Config) {
+    let text = format!("* `{}` - {}", name, doc);
+    push_wrapped_doc(code, "    ", &text, "  ", config.max_line_length);
+}
+
+/// 按最大行宽输出文档注释，续行额外添加 `continuation` 前缀
+fn push_wrapped_doc(
+    code: &mut String,
+    indent: &str,
+    text: &str,
+    continuation: &str,
+    max_line_length: usize,
+) {
+    let mut line = format!("{}/// ", indent);
+    let mut has_words = false;
+
+    for word in text.split_whitespace() {
+        let line_length = line.chars().count() + usize::from(has_words) + word.chars().count();
+        if has_words && line_length > max_line_length {
+            code.push_str(line.trim_end());
+            code.push('\n');
+            line = format!("{}/// {}", indent, continuation);
+            has_words = false;
+        }
+        if has_words {
+            line.push(' ');
+        }
+        line.push_str(word);
+        has_words = true;
+    }
+
+    code.push_str(line.trim_end());
+    code.push('\n');
+}
+
+/// 文档注释中的 Examples 段落，每个 schema 示例以一行紧凑 JSON 列在 json 代码块中
+///
+/// `separate` 为真时先输出空的文档行，与前面的说明分隔。
+fn push_json_examples(code: &mut String, indent: &str, examples: &[Value], separate: bool) {
+    if separate {
+        code.push_str(&format!("{}///\n", indent));
+    }
+    code.push_str(&format!("{}/// # Examples\n", indent));
+    code.push_str(&format!("{}///\n", indent));
+    code.push_str(&format!("{}/// ```json\n", indent));
+    for example in examples {
+        code.push_str(&format!("{}/// {}\n", indent, example));
+    }
+    code.push_str(&format!("{}/// ```\n", indent));
+}
+
+/// 结构体文档中的 Examples 段落：每个消息示例生成一个用 serde_json 反序列化的 no_run 文档测试
+///
+/// 文档测试须以 crate 外部的路径引用类型；无法确定该路径时改为列出 JSON。
+fn push_struct_examples(
+    code: &mut String,
+    struct_info: &StructInfo,
+    base_name: &str,
+    config: &Config,
+) {
+    let module = if config.single_file {
+        SINGLE_FILE.trim_end_matches(".rs").to_string()
+    } else {
+        base_name.to_case(Case::Snake)
+    };
+    let Some(module_path) = doc_test_module_path(&config.output_dir) else {
+        push_json_examples(code, "", &struct_info.examples, true);
+        return;
+    };
+
+    code.push_str("///\n");
+    code.push_str("/// # Examples\n");
+    for example in &struct_info.examples {
+        code.push_str("///\n");
+        code.push_str("/// ```no_run\n");
+        code.push_str(&format!(
+            "/// # use {}::{}::{};\n",
+            module_path, module, struct_info.name
+        ));
+        code.push_str(&format!(
+            "/// let json = {};\n",
+            raw_string_literal(&example.to_string())
+        ));
+        code.push_str(&format!(
+            "/// let message: {} = serde_json::from_str(json).unwrap();\n",
+            struct_info.name
+        ));
+        code.push_str("/// ```\n");
+    }
+}
+
+/// 输出目录对应的模块在 crate 外部的路径（如 `ocpp::v2_1::messages`），供文档测试引用
+///
+/// 输出目录须位于某个 crate 的 `src` 之下：crate 名取自 Cargo.toml 的 `[lib] name` 或包名，
+/// `src` 之后的各级目录依次为模块。无法确定时返回 None。
+fn doc_test_module_path(output_dir: &str) -> Option<String> {
+    let output_dir = fs::canonicalize(output_dir).ok()?;
+    let components: Vec<&str> = output_dir
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    let src = components.iter().rposition(|c| *c == "src")?;
+    let crate_dir: PathBuf = components[..src].iter().collect();
+    let manifest: toml::Value =
+        toml::from_str(&fs::read_to_string(crate_dir.join("Cargo.toml")).ok()?).ok()?;
+    let name = manifest
+        .get("lib")
+        .and_then(|lib| lib.get("name"))
+        .or_else(|| manifest.get("package")?.get("name"))?
+        .as_str()?
+        .replace('-', "_");
+
+    let mut path = vec![name];
+    path.extend(components[src + 1..].iter().map(|c| c.to_string()));
+    Some(path.join("::"))
+}
+
+/// 将文本写成原始字符串字面量，`#` 的数量足以容纳文本中的 `"#` 序列
+fn raw_string_literal(text: &str) -> String {
+    let mut hashes = "#".to_string();
+    while text.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+/// 生成枚举集合字段的 contains/insert 方法
+fn generate_set_methods(field: &FieldInfo) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    let item_type = field
+        .rust_type
+        .strip_prefix("HashSet<")
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(&field.rust_type);
+
+    code.push_str(&format!(
+        "    /// Checks whether the {} set contains the given value.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// `true` if the value is present in the set.\n");
+    code.push_str(&format!(
+        "    pub fn contains_{}(&self, value: &{}) -> bool {{\n",
+        field.name, item_type
+    ));
+    if field.is_optional {
+        code.push_str(&format!(
+            "        self.{}.as_ref().is_some_and(|set| set.contains(value))\n",
+            field.name
+        ));
+    } else {
+        code.push_str(&format!("        self.{}.contains(value)\n", field.name));
+    }
+    code.push_str("    }\n");
+    code.push('\n');
+
+    code.push_str(&format!(
+        "    /// Inserts a value into the {} set.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// `true` if the value was not already present.\n");
+    code.push_str(&format!(
+        "    pub fn insert_{}(&mut self, value: {}) -> bool {{\n",
+        field.name, item_type
+    ));
+    if field.is_optional {
+        code.push_str(&format!(
+            "        self.{}.get_or_insert_with(HashSet::new).insert(value)\n",
+            field.name
+        ));
+    } else {
+        code.push_str(&format!("        self.{}.insert(value)\n", field.name));
+    }
+    code.push_str("    }\n");
+
+    Ok(code)
+}
+
+/// 生成 Vec 字段的 len、is_empty 与 push 辅助方法，可选字段在 push 时创建列表
+///
+/// 与 unset 相同，方法名去掉字段名的关键字后缀 `_`。
+fn generate_vec_methods(field: &FieldInfo, config: &Config) -> String {
+    let mut code = String::new();
+
+    let item_type = field
+        .rust_type
+        .strip_prefix("Vec<")
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(&field.rust_type);
+    let name = field.name.trim_end_matches('_');
+
+    code.push_str(&format!(
+        "    /// Returns the number of elements in the {} list.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// The list length, or 0 if the list is not set.\n");
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("{}_len", name),
+        &["&self".to_string()],
+        "usize",
+        config,
+    );
+    if field.is_optional {
+        code.push_str(&format!(
+            "        self.{}.as_ref().map_or(0, Vec::len)\n",
+            field.name
+        ));
+    } else {
+        code.push_str(&format!("        self.{}.len()\n", field.name));
+    }
+    code.push_str("    }\n");
+    code.push('\n');
+
+    code.push_str(&format!(
+        "    /// Checks whether the {} list is empty.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// `true` if the list has no elements or is not set.\n");
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("{}_is_empty", name),
+        &["&self".to_string()],
+        "bool",
+        config,
+    );
+    if field.is_optional {
+        code.push_str(&format!(
+            "        self.{}.as_ref().is_none_or(Vec::is_empty)\n",
+            field.name
+        ));
+    } else {
+        code.push_str(&format!("        self.{}.is_empty()\n", field.name));
+    }
+    code.push_str("    }\n");
+    code.push('\n');
+
+    code.push_str(&format!(
+        "    /// Appends an element to the {} list.\n",
+        field.name
+    ));
+    code.push_str("    ///\n");
+    push_param_doc(&mut code, "item", "The element to append", config);
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// A mutable reference to self for method chaining.\n");
+    push_deprecated_attr(&mut code, field);
+    push_fn_signature(
+        &mut code,
+        &format!("push_{}", name),
+        &["&mut self".to_string(), format!("item: {}", item_type)],
+        "&mut Self",
+        config,
+    );
+    if field.is_optional {
+        code.push_str(&format!(
+            "        self.{}.get_or_insert_with(Vec::new).push(item);\n",
+            field.name
+        ));
+    } else {
+        code.push_str(&format!("        self.{}.push(item);\n", field.name));
+    }
+    code.push_str("        self\n");
+    code.push_str("    }\n");
+
+    code
+}
+
+/// 生成 FooBuilder：字段均为 Option，build() 检查必填字段并运行 validate()
+fn generate_builder(struct_info: &StructInfo, config: &Config) -> Result<String, GenError> {
+    let mut code = String::new();
+    let name = &struct_info.name;
+    let builder_name = format!("{}Builder", name);
+
+    code.push_str(&format!("impl {} {{\n", name));
+    code.push_str(&format!(
+        "    /// Returns a builder for incrementally constructing a `{}`.\n",
+        name
+    ));
+    code.push_str(&format!("    pub fn builder() -> {} {{\n", builder_name));
+    code.push_str(&format!("        {}::default()\n", builder_name));
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code.push('\n');
+
+    code.push_str(&format!(
+        "/// Builder for [`{}`] that reports missing required fields.\n",
+        name
+    ));
+    code.push_str("#[derive(Debug, Clone, Default)]\n");
+    code.push_str(&format!("pub struct {} {{", builder_name));
+    if !struct_info.fields.is_empty() {
+        code.push('\n');
+    }
+    for field in &struct_info.fields {
+        code.push_str(&format!(
+            "    {}: Option<{}>,\n",
+            field.name, field.rust_type
+        ));
+    }
+    code.push_str("}\n");
+    code.push('\n');
+
+    code.push_str(&format!("impl {} {{\n", builder_name));
+    for field in &struct_info.fields {
+        let param_doc = if let Some(description) = &field.description {
+            description.clone()
+        } else {
+            format!("The {} field", field.name)
+        };
+        code.push_str(&format!("    /// Sets the {} field.\n", field.name));
+        code.push_str("    ///\n");
+        push_param_doc(&mut code, &field.name, &param_doc, config);
+        let into_type = into_param_type(field, config);
+        push_deprecated_attr(&mut code, field);
+        push_fn_signature(
+            &mut code,
+            &field.name,
+            &[
+                "mut self".to_string(),
+                format!(
+                    "{}: {}",
+                    field.name,
+                    into_type.as_deref().unwrap_or(&field.rust_type)
+                ),
+            ],
+            "Self",
+            config,
+        );
+        let value = if into_type.is_some() {
+            format!("{}.into()", field.name)
+        } else {
+            field.name.clone()
+        };
+        code.push_str(&format!("        self.{} = Some({});\n", field.name, value));
+        code.push_str("        self\n");
+        code.push_str("    }\n");
+        code.push('\n');
+    }
+
+    code.push_str(&format!(
+        "    /// Builds the `{}`, failing if a required field is missing or validation fails.\n",
+        name
+    ));
+    code.push_str(&format!(
+        "    pub fn build(self) -> Result<{}, String> {{\n",
+        name
+    ));
+    if struct_info.fields.is_empty() {
+        code.push_str(&format!("        let message = {} {{}};\n", name));
+    } else {
+        code.push_str(&format!("        let message = {} {{\n", name));
+        for field in &struct_info.fields {
+            if schema_default_expr(field).is_some() {
+                code.push_str(&format!(
+                    "            {}: self.{}.or_else({}),\n",
+                    field.name,
+                    field.name,
+                    default_fn_name(name, field)
+                ));
+            } else if field.is_optional {
+                code.push_str(&format!(
+                    "            {}: self.{},\n",
+                    field.name, field.name
+                ));
+            } else if let Some(expr) = fixed_value_expr(field) {
+                // 字面量与枚举变体直接传值（clippy::unnecessary_lazy_evaluations），其余惰性构造
+                let unwrap =
+                    if is_copy_type(&field.rust_type) || field.rust_type.ends_with("EnumType") {
+                        format!("unwrap_or({})", expr)
+                    } else {
+                        format!("unwrap_or_else(|| {})", expr)
+                    };
+                code.push_str(&format!(
+                    "            {}: self.{}.{},\n",
+                    field.name, field.name, unwrap
+                ));
+            } else {
+                code.push_str(&format!(
+                    "            {}: self.{}.ok_or(\"missing required field `{}`\")?,\n",
+                    field.name, field.name, field.name
+                ));
+            }
+        }
+        code.push_str("        };\n");
+    }
+    code.push_str("        message.validate().map_err(|e| e.to_string())?;\n");
+    code.push_str("        Ok(message)\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    Ok(code)
+}
+
+/// 字段 schema default 函数名
+fn default_fn_name(struct_name: &str, field: &FieldInfo) -> String {
+    format!(
+        "default_{}_{}",
+        struct_name.to_case(Case::Snake),
+        field.name
+    )
+}
+
+/// 字段 schema default 对应的 Rust 表达式，类型不受支持时返回 None
+fn schema_default_expr(field: &FieldInfo) -> Option<String> {
+    default_literal(field.schema_default.as_ref()?, &field.rust_type)
+}
+
+/// 将 schema default 值转换为给定 Rust 类型的表达式
+///
+/// 支持字符串、数值（整数、浮点、Decimal）、布尔值及其数组。
+fn default_literal(value: &Value, rust_type: &str) -> Option<String> {
+    if let Some(item_type) = rust_type
+        .strip_prefix("Vec<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        let items = value
+            .as_array()?
+            .iter()
+            .map(|item| default_literal(item, item_type))
+            .collect::<Option<Vec<_>>>()?;
+        return Some(format!("vec![{}]", items.join(", ")));
+    }
+
+    match (rust_type, value) {
+        ("String", Value::String(s)) => Some(format!("{:?}.to_string()", s)),
+        ("bool", Value::Bool(b)) => Some(b.to_string()),
+        ("i32", Value::Number(n)) => n
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(|n| n.to_string()),
+        ("i64", Value::Number(n)) => n.as_i64().map(|n| n.to_string()),
+        ("u32", Value::Number(n)) => n
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .map(|n| n.to_string()),
+        ("u64", Value::Number(n)) => n.as_u64().map(|n| n.to_string()),
+        ("f32" | "f64", Value::Number(n)) => n.as_f64().map(|n| format!("{:?}", n)),
+        // 指数形式无法直接拆分为尾数与小数位数
+        ("Decimal", Value::Number(n)) if !n.to_string().contains(['e', 'E']) => {
+            Some(decimal_literal(&n.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// 生成返回字段 schema default 的函数，供 `#[serde(default = "...")]` 与构造方法使用
+///
+/// 值与字段类型不匹配或类型不受支持时打印警告并返回 None。
+fn generate_default_fn(struct_name: &str, field: &FieldInfo) -> Option<String> {
+    let value = field.schema_default.as_ref()?;
+    let Some(expr) = default_literal(value, &field.rust_type) else {
+        log::warn!(
+            "{}.{} default {} is not supported for type {}, ignoring it",
+            struct_name,
+            field.original_name,
+            value,
+            field.rust_type
+        );
+        tracing::warn!(struct_name, field = %field.original_name, "unsupported default ignored");
+        return None;
+    };
+
+    let mut code = String::new();
+    code.push_str(&format!(
+        "/// Returns the schema default of `{}` in `{}`.\n",
+        field.name, struct_name
+    ));
+    code.push_str(&format!(
+        "fn {}() -> Option<{}> {{\n",
+        default_fn_name(struct_name, field),
+        field.rust_type
+    ));
+    code.push_str(&format!("    Some({})\n", expr));
+    code.push_str("}\n");
+    Some(code)
+}
+
+/// 生成 Default 实现；存在无已知默认值的必填字段（如引用的数据类型）时返回 None
+fn generate_default_impl(struct_info: &StructInfo) -> Option<String> {
+    let values = struct_info
+        .fields
+        .iter()
+        .map(|field| default_value(&struct_info.name, field).map(|value| (&field.name, value)))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut code = String::new();
+    code.push_str(&format!("impl Default for {} {{\n", struct_info.name));
+    code.push_str(
+        "    /// Returns an instance with empty or zero required fields and no optional fields.\n",
+    );
+    code.push_str("    fn default() -> Self {\n");
+    if values.is_empty() {
+        code.push_str("        Self {}\n");
+    } else {
+        code.push_str("        Self {\n");
+        for (name, value) in values {
+            code.push_str(&format!("            {}: {},\n", name, value));
+        }
+        code.push_str("        }\n");
+    }
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    Some(code)
+}
+
+/// 字段的默认值表达式：可选字段为 None，必填字段取类型的零值或空值
+fn default_value(struct_name: &str, field: &FieldInfo) -> Option<String> {
+    if schema_default_expr(field).is_some() {
+        return Some(format!("{}()", default_fn_name(struct_name, field)));
+    }
+    if field.is_optional {
+        return Some("None".to_string());
+    }
+    if let Some(expr) = fixed_value_expr(field) {
+        return Some(expr);
+    }
+
+    let rust_type = field.rust_type.as_str();
+    if is_integer_type(rust_type) {
+        return Some("0".to_string());
+    }
+    let value = match rust_type {
+        "String" => Some("String::new()"),
+        "f32" | "f64" => Some("0.0"),
+        "bool" => Some("false"),
+        "Decimal" => Some("Decimal::ZERO"),
+        "Value" => Some("Value::Null"),
+        "DateTime<Utc>" => Some("DateTime::<Utc>::default()"),
+        _ if rust_type.starts_with("Vec<") => Some("Vec::new()"),
+        _ if rust_type.starts_with("HashSet<") => Some("HashSet::new()"),
+        _ if rust_type.starts_with("HashMap<") => Some("HashMap::new()"),
+        _ => None,
+    };
+    value.map(str::to_string)
+}
+
+/// 生成 JSON 往返测试模块：用 new 构造每个消息，序列化后再反序列化并比较
+///
+/// 必填字段引用了本文件之外的类型（如共享的 datatypes）时无法构造示例值，
+/// 该消息的测试改为一行说明注释。
+fn generate_round_trip_tests(pair: &MessagePair) -> String {
+    let mut types = SampleTypes::default();
+    for info in pair.structs() {
+        types.add_struct(info);
+    }
+
+    let mut code = String::new();
+    for info in pair.structs() {
+        code.push('\n');
+        let unsampled = info
+            .fields
+            .iter()
+            .filter(|field| !field.is_optional && fixed_value_expr(field).is_none())
+            .find(|field| types.field_sample(field, &mut Vec::new()).is_none());
+        if let Some(field) = unsampled {
+            code.push_str(&format!(
+                "    // No round-trip test for {}: no sample value for `{}` of type {}\n",
+                info.name, field.name, field.rust_type
+            ));
+            continue;
+        }
+        let Some(message) = types.struct_sample(info, &mut Vec::new()) else {
+            continue;
+        };
+
+        code.push_str("    #[test]\n");
+        code.push_str(&format!(
+            "    fn {}_round_trips() {{\n",
+            info.name.to_case(Case::Snake)
+        ));
+        code.push_str(&format!("        let message = {};\n", message));
+        code.push_str("        let json = serde_json::to_string(&message).unwrap();\n");
+        code.push_str(&format!(
+            "        let decoded: {} = serde_json::from_str(&json).unwrap();\n",
+            info.name
+        ));
+        code.push_str("        assert_eq!(decoded, message);\n");
+        code.push_str("    }\n");
+    }
+
+    // 枚举的 as_str、FromStr 与 serde 使用相同的线上字符串
+    for inline_enum in types.enums.values() {
+        let name = &inline_enum.name;
+        let variants: Vec<String> = enum_variants(inline_enum)
+            .into_iter()
+            .map(|(variant, _)| format!("{}::{}", name, variant))
+            .collect();
+        let mut unknown = "Unknown".to_string();
+        while inline_enum.values.contains(&unknown) {
+            unknown.push('_');
+        }
+
+        code.push('\n');
+        code.push_str("    #[test]\n");
+        code.push_str(&format!(
+            "    fn {}_parses_wire_values() {{\n",
+            name.to_case(Case::Snake)
+        ));
+        code.push_str(&format!(
+            "        for value in [{}] {{\n",
+            variants.join(", ")
+        ));
+        code.push_str(&format!(
+            "            assert_eq!(value.as_str().parse::<{}>().unwrap(), value);\n",
+            name
+        ));
+        code.push_str(
+            "            assert_eq!(serde_json::to_value(&value).unwrap(), value.as_str());\n",
+        );
+        code.push_str("        }\n");
+        code.push_str(&format!(
+            "        assert!({:?}.parse::<{}>().is_err());\n",
+            unknown, name
+        ));
+        code.push_str("    }\n");
+    }
+
+    // 没有任何测试时不导入，避免 unused import 警告
+    let mut module = String::new();
+    module.push_str("#[cfg(test)]\n");
+    module.push_str("mod tests {\n");
+    if code.contains("#[test]") {
+        module.push_str("    use super::*;\n");
+        module.push_str(&code);
+    } else {
+        module.push_str(code.trim_start_matches('\n'));
+    }
+    module.push_str("}\n");
+    module
+}
+
+/// 往返测试可构造示例值的同文件类型（消息、内联结构体、枚举、联合与新类型）
+#[derive(Default)]
+struct SampleTypes<'a> {
+    structs: BTreeMap<&'a str, &'a StructInfo>,
+    enums: BTreeMap<&'a str, &'a InlineEnumInfo>,
+    unions: BTreeMap<&'a str, &'a UnionInfo>,
+    newtypes: BTreeMap<&'a str, &'a NewtypeInfo>,
+}
+
+impl<'a> SampleTypes<'a> {
+    fn add_struct(&mut self, info: &'a StructInfo) {
+        self.structs.insert(&info.name, info);
+        for field in &info.fields {
+            if let Some(inline) = field.inline_struct.as_deref() {
+                self.add_struct(inline);
+            }
+            if let Some(inline_enum) = &field.inline_enum {
+                self.enums.insert(&inline_enum.name, inline_enum);
+            }
+            if let Some(union) = &field.union {
+                self.unions.insert(&union.name, union);
+            }
+            if let Some(newtype) = &field.newtype {
+                self.newtypes.insert(&newtype.name, newtype);
+            }
+        }
+    }
+
+    /// 通过 new 构造结构体；`visiting` 记录正在构造的结构体，必填字段递归引用自身时放弃
+    fn struct_sample(&self, info: &'a StructInfo, visiting: &mut Vec<&'a str>) -> Option<String> {
+        if visiting.contains(&info.name.as_str()) {
+            return None;
+        }
+        visiting.push(&info.name);
+        let args = info
+            .fields
+            .iter()
+            .filter(|field| !field.is_optional && fixed_value_expr(field).is_none())
+            .map(|field| self.field_sample(field, visiting))
+            .collect::<Option<Vec<_>>>();
+        visiting.pop();
+        Some(format!("{}::new({})", info.name, args?.join(", ")))
+    }
+
+    /// 满足字段约束的最短示例值：最小长度的字符串、最少元素的数组、范围内最接近 0 的数
+    fn field_sample(&self, field: &FieldInfo, visiting: &mut Vec<&'a str>) -> Option<String> {
+        let rust_type = field.rust_type.as_str();
+        if let Some(item_type) = rust_type
+            .strip_prefix("Vec<")
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            return match field.min_items.unwrap_or(0) {
+                0 => Some("Vec::new()".to_string()),
+                count => Some(format!(
+                    "vec![{}; {}]",
+                    self.type_sample(item_type, visiting)?,
+                    count
+                )),
+            };
+        }
+
+        match rust_type {
+            "String" => Some(string_sample(
+                field.min_length.unwrap_or(0),
+                field.string_format.as_deref(),
+            )),
+            _ if is_integer_type(rust_type) => {
+                let (min, max) = numeric_bounds(field);
+                let parse = |bound: Option<String>| bound.and_then(|b| b.parse::<i64>().ok());
+                let value = match (parse(min), parse(max)) {
+                    (Some(min), _) => min,
+                    (None, Some(max)) if max < 0 => max,
+                    _ => 0,
+                };
+                Some(value.to_string())
+            }
+            "f32" | "f64" | "Decimal" => {
+                let literal = number_sample(field);
+                if rust_type == "Decimal" {
+                    Some(decimal_literal(&literal))
+                } else if literal.contains('.') {
+                    Some(literal)
+                } else {
+                    Some(format!("{}.0", literal))
+                }
+            }
+            _ => self.type_sample(rust_type, visiting),
+        }
+    }
+
+    /// 不带字段约束的类型示例值
+    fn type_sample(&self, rust_type: &str, visiting: &mut Vec<&'a str>) -> Option<String> {
+        if let Some(inner) = rust_type
+            .strip_prefix("Box<")
+            .and_then(|rest| rest.strip_suffix('>'))
+        {
+            return Some(format!("Box::new({})", self.type_sample(inner, visiting)?));
+        }
+        if rust_type.starts_with("Option<") {
+            return Some("None".to_string());
+        }
+        if is_integer_type(rust_type) {
+            return Some("0".to_string());
+        }
+
+        let value = match rust_type {
+            "String" => Some("String::new()"),
+            "f32" | "f64" => Some("0.0"),
+            "bool" => Some("false"),
+            "Decimal" => Some("Decimal::ZERO"),
+            "Value" => Some("Value::Null"),
+            "DateTime<Utc>" => Some("DateTime::<Utc>::default()"),
+            "Uuid" => Some("Uuid::nil()"),
+            "Ipv4Addr" => Some("Ipv4Addr::LOCALHOST"),
+            "Ipv6Addr" => Some("Ipv6Addr::LOCALHOST"),
+            _ if rust_type.starts_with("Vec<") => Some("Vec::new()"),
+            _ if rust_type.starts_with("HashSet<") => Some("HashSet::new()"),
+            _ if rust_type.starts_with("HashMap<") => Some("HashMap::new()"),
+            _ => None,
+        };
+        if let Some(value) = value {
+            return Some(value.to_string());
+        }
+
+        if let Some(info) = self.structs.get(rust_type) {
+            return self.struct_sample(info, visiting);
+        }
+        if let Some(inline_enum) = self.enums.get(rust_type) {
+            let first = inline_enum.values.first()?;
+            return Some(format!(
+                "{}::{}",
+                rust_type,
+                enum_variant_name(first, &HashSet::new())
+            ));
+        }
+        if let Some(union) = self.unions.get(rust_type) {
+            return union.variants.iter().zip(&union.constraints).find_map(
+                |((variant, inner), constraints)| {
+                    let value = match constraints {
+                        Some(constraints) => self.scalar_sample(constraints, visiting)?,
+                        None => self.type_sample(inner, visiting)?,
+                    };
+                    Some(format!("{}::{}({})", rust_type, variant, value))
+                },
+            );
+        }
+        if let Some(newtype) = self.newtypes.get(rust_type) {
+            let inner = self.scalar_sample(newtype, visiting)?;
+            return Some(format!("{}({})", rust_type, inner));
+        }
+        None
+    }
+
+    /// 满足标量约束（最小长度、整数下限）的样例值表达式
+    fn scalar_sample(
+        &self,
+        constraints: &NewtypeInfo,
+        visiting: &mut Vec<&'a str>,
+    ) -> Option<String> {
+        if constraints.inner_type == "String" {
+            Some(string_sample(constraints.min_length.unwrap_or(0), None))
+        } else if is_integer_type(&constraints.inner_type) {
+            let min = match constraints.min_value {
+                Some(min) if constraints.exclusive_min => min.floor() as i64 + 1,
+                Some(min) => min.ceil() as i64,
+                None => 0,
+            };
+            Some(min.max(0).to_string())
+        } else {
+            self.type_sample(&constraints.inner_type, visiting)
+        }
+    }
+
+    /// 结构体 `from` 的字段（含嵌套的联合变体）是否直接或间接引用了结构体 `target`
+    fn reaches<'b>(&'b self, from: &'b str, target: &str, visited: &mut Vec<&'b str>) -> bool {
+        if from == target {
+            return true;
+        }
+        if visited.contains(&from) {
+            return false;
+        }
+        visited.push(from);
+        let Some(info) = self.structs.get(from) else {
+            return false;
+        };
+        info.fields.iter().any(|field| {
+            let mut names: Vec<&str> = type_names(&field.rust_type);
+            if let Some(union) = self.unions.get(field.rust_type.as_str()) {
+                names.extend(union.variants.iter().flat_map(|(_, t)| type_names(t)));
+            }
+            names
+                .into_iter()
+                .any(|name| self.structs.contains_key(name) && self.reaches(name, target, visited))
+        })
+    }
+}
+
+/// 满足最小长度与 format 的最短字符串示例
+fn string_sample(min_length: u32, format: Option<&str>) -> String {
+    match format {
+        Some("email") => "\"user@example.com\".to_string()".to_string(),
+        Some("uri") => "\"https://example.com\".to_string()".to_string(),
+        _ if min_length == 0 => "String::new()".to_string(),
+        _ => format!("{:?}.to_string()", "a".repeat(min_length as usize)),
+    }
+}
+
+/// 非整数字段在上下限内最接近 0 的示例值（十进制文本）
+fn number_sample(field: &FieldInfo) -> String {
+    let format_value = |value: f64| format!("{}", value);
+    let min = field.min_value.map(|v| {
+        (
+            v,
+            field.min_literal.clone().unwrap_or_else(|| format_value(v)),
+        )
+    });
+    let max = field.max_value.map(|v| {
+        (
+            v,
+            field.max_literal.clone().unwrap_or_else(|| format_value(v)),
+        )
+    });
+
+    match (min, max) {
+        (Some((min, literal)), _) if min >= 0.0 && !field.exclusive_min => literal,
+        (Some((min, _)), Some((max, _))) if min >= 0.0 => format_value((min + max) / 2.0),
+        (Some((min, _)), None) if min >= 0.0 => format_value(min + 1.0),
+        (_, Some((max, literal))) if max < 0.0 && !field.exclusive_max => literal,
+        (Some((min, _)), Some((max, _))) if max <= 0.0 => format_value((min + max) / 2.0),
+        (None, Some((max, _))) if max <= 0.0 => format_value(max - 1.0),
+        _ => "0".to_string(),
+    }
+}
+
+/// 为同文件类型生成满足约束的示例值表达式（--examples）
+///
+/// 字符串取最小长度（至少一个字符）、数值取范围中点、数组取满足 minItems 的最少元素（至少一个）；
+/// 结构体之间的引用委托给被引用类型的 example()，引用构成环时环上的引用不委托。
+struct ExampleTypes<'a> {
+    types: &'a SampleTypes<'a>,
+    config: &'a Config,
+}
+
+impl ExampleTypes<'_> {
+    /// 结构体各字段的示例值；有必填字段无法构造示例值时返回 None
+    fn struct_example<'i>(&self, info: &'i StructInfo) -> Option<Vec<(&'i str, String)>> {
+        info.fields
+            .iter()
+            .map(|field| Some((field.name.as_str(), self.field_example(&info.name, field)?)))
+            .collect()
+    }
+
+    /// 字段的示例值：schema 默认值与 const 优先，无法构造示例值的可选字段取 None
+    fn field_example(&self, owner: &str, field: &FieldInfo) -> Option<String> {
+        if schema_default_expr(field).is_some() {
+            return Some(format!("{}()", default_fn_name(owner, field)));
+        }
+        let value = match field.const_value.as_ref() {
+            Some(value) => default_literal(value, &field.rust_type),
+            None => self.constrained_example(owner, field),
+        };
+        match (field.is_optional, value) {
+            (true, Some(value)) => Some(format!("Some({})", value)),
+            (true, None) => Some("None".to_string()),
+            (false, value) => value,
+        }
+    }
+
+    /// 满足字段约束（长度、范围、元素数、format 与 multipleOf）的示例值
+    fn constrained_example(&self, owner: &str, field: &FieldInfo) -> Option<String> {
+        let rust_type = field.rust_type.as_str();
+        if rust_type == "String" {
+            return string_example(field, self.config);
+        }
+        if rust_type == "Vec<u8>" {
+            return match example_count(field.min_length, field.max_length)? {
+                0 => Some("Vec::new()".to_string()),
+                count => Some(format!("vec![0u8; {}]", count)),
+            };
+        }
+        if let Some(item_type) = rust_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            let count = example_count(field.min_items, field.max_items)?;
+            return match self.type_example(owner, item_type) {
+                Some(item) if count > 0 => {
+                    Some(format!("vec![{}]", vec![item; count as usize].join(", ")))
+                }
+                _ if field.min_items.unwrap_or(0) == 0 => Some("Vec::new()".to_string()),
+                _ => None,
+            };
+        }
+        if let Some(item_type) = rust_type
+            .strip_prefix("HashSet<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            let count = example_count(field.min_items, field.max_items)? as usize;
+            // 集合元素必须互不相同：枚举取前几个变体，其他类型最多取一个元素
+            let items: Vec<String> = match self.types.enums.get(item_type) {
+                Some(inline_enum) => enum_variants(inline_enum)
+                    .into_iter()
+                    .take(count)
+                    .map(|(variant, _)| format!("{}::{}", item_type, variant))
+                    .collect(),
+                None if count == 1 => self.type_example(owner, item_type).into_iter().collect(),
+                None => Vec::new(),
+            };
+            return if items.len() == count {
+                Some(format!("HashSet::from([{}])", items.join(", ")))
+            } else if field.min_items.unwrap_or(0) == 0 {
+                Some("HashSet::new()".to_string())
+            } else {
+                None
+            };
+        }
+        if is_integer_type(rust_type) {
+            return integer_example(field);
+        }
+        if matches!(rust_type, "f32" | "f64") {
+            return float_example(field);
+        }
+        if rust_type == "Decimal" {
+            return decimal_example(field);
+        }
+        self.type_example(owner, rust_type)
+    }
+
+    /// 不带字段约束的类型示例值；`owner` 为正在构造示例值的结构体，用于切断引用环
+    fn type_example(&self, owner: &str, rust_type: &str) -> Option<String> {
+        if let Some(inner) = rust_type
+            .strip_prefix("Box<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            return Some(format!("Box::new({})", self.type_example(owner, inner)?));
+        }
+        if let Some(value_type) = rust_type
+            .strip_prefix("HashMap<String, ")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            return Some(match self.type_example(owner, value_type) {
+                Some(value) => format!("HashMap::from([(\"key\".to_string(), {})])", value),
+                None => "HashMap::new()".to_string(),
+            });
+        }
+        if rust_type == "String"
+            || is_integer_type(rust_type)
+            || matches!(rust_type, "f32" | "f64" | "Decimal")
+            || rust_type.starts_with("Vec<")
+            || rust_type.starts_with("HashSet<")
+        {
+            let field = FieldInfo {
+                rust_type: rust_type.to_string(),
+                ..FieldInfo::default()
+            };
+            return self.constrained_example(owner, &field);
+        }
+
+        let value = match rust_type {
+            "bool" => Some("true"),
+            "Value" => Some("Value::Null"),
+            "DateTime<Utc>" => Some("DateTime::<Utc>::default()"),
+            "Uuid" => Some("Uuid::nil()"),
+            "Ipv4Addr" => Some("Ipv4Addr::LOCALHOST"),
+            "Ipv6Addr" => Some("Ipv6Addr::LOCALHOST"),
+            _ => None,
+        };
+        if let Some(value) = value {
+            return Some(value.to_string());
+        }
+
+        if let Some(info) = self.types.structs.get(rust_type) {
+            if self.types.reaches(rust_type, owner, &mut Vec::new()) {
+                return None;
+            }
+            self.struct_example(info)?;
+            return Some(format!("{}::example()", rust_type));
+        }
+        if let Some(inline_enum) = self.types.enums.get(rust_type) {
+            let (variant, _) = enum_variants(inline_enum).into_iter().next()?;
+            return Some(format!("{}::{}", rust_type, variant));
+        }
+        if let Some(union) = self.types.unions.get(rust_type) {
+            return union.variants.iter().zip(&union.constraints).find_map(
+                |((variant, inner), constraints)| {
+                    let value = match constraints {
+                        Some(constraints) => {
+                            self.constrained_example(owner, &constraints.inner_field())?
+                        }
+                        None => self.type_example(owner, inner)?,
+                    };
+                    Some(format!("{}::{}({})", rust_type, variant, value))
+                },
+            );
+        }
+        if let Some(newtype) = self.types.newtypes.get(rust_type) {
+            let inner = newtype.inner_field();
+            return Some(format!(
+                "{}({})",
+                rust_type,
+                self.constrained_example(owner, &inner)?
+            ));
+        }
+        // 从 datatypes 模块导入的数据类型同样由生成器以 --examples 生成，委托给其 example()
+        if rust_type.ends_with("Type") && !rust_type.ends_with("EnumType") {
+            return Some(format!("{}::example()", rust_type));
+        }
+        None
+    }
+}
+
+/// 示例的元素数：满足最小元素数的最少元素（至少一个），上下限矛盾时返回 None
+fn example_count(min: Option<u32>, max: Option<u32>) -> Option<u32> {
+    let min = min.unwrap_or(0);
+    match max {
+        Some(max) if max < min => None,
+        Some(max) => Some(min.max(1).min(max)),
+        None => Some(min.max(1)),
+    }
+}
+
+/// 字符串示例：最小长度（至少一个字符）的 `a`，email 与 uri 取固定地址，带 pattern 时取匹配的字符串
+fn string_example(field: &FieldInfo, config: &Config) -> Option<String> {
+    let min = field.min_length.unwrap_or(0);
+    let max = string_max_length(field, config);
+    let value = match (field.pattern.as_deref(), field.string_format.as_deref()) {
+        (Some(pattern), _) => pattern_example(pattern, min, max)?,
+        (None, Some("email")) => "user@example.com".to_string(),
+        (None, Some("uri")) => "https://example.com".to_string(),
+        (None, _) => "a".repeat(example_count(Some(min), max)? as usize),
+    };
+    let length = value.chars().count() as u32;
+    if length < min || max.is_some_and(|max| length > max) {
+        return None;
+    }
+    if value.is_empty() {
+        Some("String::new()".to_string())
+    } else {
+        Some(format!("{:?}.to_string()", value))
+    }
+}
+
+/// 匹配 pattern 且长度在上下限之内的示例字符串
+///
+/// 分支取第一个，字符类优先取字母或数字；重复先取最少次数，长度不足时逐次多重复一次。
+/// 结果再用编译后的正则检查（pattern 未锚定时同样适用），无法构造时返回 None。
+fn pattern_example(pattern: &str, min: u32, max: Option<u32>) -> Option<String> {
+    let hir = regex_syntax::Parser::new().parse(pattern).ok()?;
+    let regex = regex::Regex::new(pattern).ok()?;
+    (0..=min.max(1)).find_map(|extra| {
+        let mut value = String::new();
+        hir_example(&hir, extra, &mut value)?;
+        let length = value.chars().count() as u32;
+        let fits = length >= min && max.is_none_or(|max| length <= max);
+        (fits && regex.is_match(&value)).then_some(value)
+    })
+}
+
+/// 将正则语法树的一个匹配写入 `out`，每个重复在最少次数之外多取 `extra` 次（不超过上限）
+fn hir_example(hir: &regex_syntax::hir::Hir, extra: u32, out: &mut String) -> Option<()> {
+    use regex_syntax::hir::{Class, HirKind};
+
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => out.push_str(std::str::from_utf8(&literal.0).ok()?),
+        HirKind::Class(Class::Unicode(class)) => {
+            let chars = || {
+                class
+                    .ranges()
+                    .iter()
+                    .flat_map(|range| range.start()..=range.end())
+            };
+            let c = chars()
+                .find(char::is_ascii_alphanumeric)
+                .or_else(|| chars().find(|c| !c.is_control()))?;
+            out.push(c);
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            let bytes = || {
+                class
+                    .ranges()
+                    .iter()
+                    .flat_map(|range| range.start()..=range.end())
+            };
+            let b = bytes()
+                .find(u8::is_ascii_alphanumeric)
+                .or_else(|| bytes().find(u8::is_ascii_graphic))?;
+            out.push(b as char);
+        }
+        HirKind::Repetition(repetition) => {
+            let count = repetition.max.map_or(repetition.min + extra, |max| {
+                (repetition.min + extra).min(max)
+            });
+            for _ in 0..count {
+                hir_example(&repetition.sub, extra, out)?;
+            }
+        }
+        HirKind::Capture(capture) => hir_example(&capture.sub, extra, out)?,
+        HirKind::Concat(hirs) => {
+            for hir in hirs {
+                hir_example(hir, extra, out)?;
+            }
+        }
+        HirKind::Alternation(hirs) => hir_example(hirs.first()?, extra, out)?,
+    }
+    Some(())
+}
+
+/// 非整数字段的示例值：同时声明上下限时取中点，否则取最接近 0 的可取值
+fn number_example(field: &FieldInfo) -> Option<f64> {
+    if let (Some(min), Some(max)) = (field.min_value, field.max_value) {
+        let empty = min > max || (min == max && (field.exclusive_min || field.exclusive_max));
+        return (!empty).then_some((min + max) / 2.0);
+    }
+    let mut value = 0.0;
+    if let Some(min) = field
+        .min_value
+        .filter(|min| *min > value || (*min == value && field.exclusive_min))
+    {
+        value = if field.exclusive_min { min + 1.0 } else { min };
+    }
+    if let Some(max) = field
+        .max_value
+        .filter(|max| *max < value || (*max == value && field.exclusive_max))
+    {
+        value = if field.exclusive_max { max - 1.0 } else { max };
+    }
+    Some(value)
+}
+
+/// multipleOf 示例值 k * multiple_of 中的 k：落在上下限内且最接近 `number_example`
+fn multiple_example(field: &FieldInfo, multiple_of: f64) -> Option<i64> {
+    let (low, high) = multiple_range(field, multiple_of)?;
+    let target = (number_example(field)? / multiple_of).round() as i64;
+    Some(target.clamp(low, high))
+}
+
+/// 整数示例：同时声明上下限时取中点，否则取最接近 0 的可取值；整数 multipleOf 取范围内最近的倍数
+fn integer_example(field: &FieldInfo) -> Option<String> {
+    let (type_min, type_max) = integer_type_range(&field.rust_type);
+    let (min, max) = numeric_bounds(field);
+    let parse = |bound: Option<String>| bound.and_then(|b| b.parse::<i128>().ok());
+    let (min, max) = (parse(min), parse(max));
+    let target = match (min, max) {
+        (Some(min), Some(max)) => (min + max).div_euclid(2),
+        _ => 0,
+    };
+    let min = min.map_or(type_min, |min| min.max(type_min));
+    let max = max.map_or(type_max, |max| max.min(type_max));
+    if min > max {
+        return None;
+    }
+
+    let value = match field.multiple_of.filter(|m| m.fract() == 0.0) {
+        Some(multiple_of) if has_multiple_of_check(field) => {
+            let multiple_of = multiple_of as i128;
+            let low = min.div_euclid(multiple_of) + (min.rem_euclid(multiple_of) != 0) as i128;
+            let high = max.div_euclid(multiple_of);
+            if low > high {
+                return None;
+            }
+            target.div_euclid(multiple_of).clamp(low, high) * multiple_of
+        }
+        _ => target.clamp(min, max),
+    };
+    Some(value.to_string())
+}
+
+/// 浮点示例：数值的 Debug 形式（如 `0.5`）是可无损往返的字面量
+fn float_example(field: &FieldInfo) -> Option<String> {
+    let value = match field.multiple_of.filter(|_| has_multiple_of_check(field)) {
+        // f32 的舍入误差超出生成的 multipleOf 检查的容差
+        Some(multiple_of) if field.rust_type == "f32" && multiple_of.fract() != 0.0 => return None,
+        Some(multiple_of) => multiple_example(field, multiple_of)? as f64 * multiple_of,
+        None => number_example(field)?,
+    };
+    match field.rust_type.as_str() {
+        "f32" => Some(format!("{:?}", value as f32)),
+        _ => Some(format!("{:?}", value)),
+    }
+}
+
+/// Decimal 示例：在 schema 边界的小数位数（至少两位）下取最接近 `number_example` 的值
+fn decimal_example(field: &FieldInfo) -> Option<String> {
+    if let Some(multiple_of) = field.multiple_of.filter(|_| has_multiple_of_check(field)) {
+        return Some(format!(
+            "Decimal::from({}) * {}",
+            multiple_example(field, multiple_of)?,
+            decimal_literal(&multiple_of.to_string())
+        ));
+    }
+    let (low, high, scale) = decimal_units(field)?;
+    let units = (number_example(field)? * 10f64.powi(scale as i32)).round() as i64;
+    Some(format!(
+        "Decimal::new({}, {})",
+        units.clamp(low, high),
+        scale
+    ))
+}
+
+/// 生成 proptest 策略模块（--gen-proptest）：为每个结构体生成 `{name}_strategy()`
+///
+/// 字段取值遵循与验证属性相同的约束，派生 Validate 时附带检查每个生成值都通过 validate() 的测试。
+/// 必填字段引用了本文件之外的类型时无法生成策略，该结构体改为一行说明注释；
+/// 可选字段取 None，空数组允许时取空数组。
+fn generate_proptest_strategies(pair: &MessagePair, config: &Config) -> String {
+    let mut types = SampleTypes::default();
+    for info in pair.structs() {
+        types.add_struct(info);
+    }
+    // 从 shared.rs 导入的结构体及其嵌套类型不在本文件中，所需的导入也可能缺失
+    let shared_prefix = format!("pub use super::{}::", SHARED_MODULE);
+    let mut shared_structs = HashSet::new();
+    let mut pending: Vec<&str> = pair
+        .combined_imports
+        .iter()
+        .filter_map(|import| import.strip_prefix(&shared_prefix)?.strip_suffix(';'))
+        .collect();
+    while let Some(name) = pending.pop() {
+        if let Some(info) = types
+            .structs
+            .get(name)
+            .filter(|_| shared_structs.insert(name))
+        {
+            pending.extend(
+                info.fields
+                    .iter()
+                    .filter_map(|field| field.inline_struct.as_deref())
+                    .map(|inline| inline.name.as_str()),
+            );
+        }
+    }
+    let strategies = StrategyTypes {
+        types: &types,
+        shared_structs,
+        config,
+    };
+
+    let mut code = String::new();
+    let mut generated = Vec::new();
+    for info in types
+        .structs
+        .values()
+        .filter(|info| !strategies.shared_structs.contains(info.name.as_str()))
+    {
+        code.push('\n');
+        let missing = info
+            .fields
+            .iter()
+            .find(|field| strategies.field_strategy(&info.name, field).is_none());
+        if let Some(field) = missing {
+            code.push_str(&format!(
+                "    // No strategy for {}: no strategy for `{}` of type {}\n",
+                info.name, field.name, field.rust_type
+            ));
+            continue;
+        }
+        let Some(strategy) = strategies.struct_strategy(info) else {
+            continue;
+        };
+
+        code.push_str(&format!(
+            "    /// Generates `{}` values that satisfy its schema constraints.\n",
+            info.name
+        ));
+        code.push_str(&format!(
+            "    pub fn {}() -> impl Strategy<Value = {}> {{\n",
+            strategy_fn_name(&info.name),
+            info.name
+        ));
+        code.push_str(&format!("        {}\n", strategy));
+        code.push_str("    }\n");
+        generated.push(&info.name);
+    }
+
+    // 生成的值都应通过 validate()
+    if config.has_derive("Validate") && !generated.is_empty() {
+        code.push('\n');
+        code.push_str("    proptest! {\n");
+        for (i, name) in generated.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+            code.push_str("        #[test]\n");
+            code.push_str(&format!(
+                "        fn {}_values_validate(message in {}()) {{\n",
+                name.to_case(Case::Snake),
+                strategy_fn_name(name)
+            ));
+            code.push_str(
+                "            prop_assert!(message.validate().is_ok(), \"{:?}\", message.validate());\n",
+            );
+            code.push_str("        }\n");
+        }
+        code.push_str("    }\n");
+    }
+
+    // 没有任何策略时不导入，避免 unused import 警告
+    let mut module = String::new();
+    module.push_str("#[cfg(test)]\n");
+    module.push_str("pub mod strategies {\n");
+    if generated.is_empty() {
+        module.push_str(code.trim_start_matches('\n'));
+    } else {
+        module.push_str("    use super::*;\n");
+        module.push_str("    use proptest::prelude::*;\n");
+        module.push_str(&code);
+    }
+    module.push_str("}\n");
+    module
+}
+
+/// 结构体的 proptest 策略函数名
+fn strategy_fn_name(struct_name: &str) -> String {
+    format!("{}_strategy", struct_name.to_case(Case::Snake))
+}
+
+/// 未声明上下限的数值在策略中使用的取值范围
+const STRATEGY_NUMBER_SPAN: f64 = 1e9;
+
+/// 未声明最大元素数的数组在策略中比最小元素数多出的上限
+const STRATEGY_EXTRA_ITEMS: u32 = 3;
+
+/// 为同文件类型生成 proptest 策略表达式
+///
+/// 结构体之间的引用委托给被引用类型的策略函数；引用构成环时（如递归的本地定义）
+/// 环上的引用不委托，可选字段取 None，否则该结构体没有策略。
+struct StrategyTypes<'a> {
+    types: &'a SampleTypes<'a>,
+    shared_structs: HashSet<&'a str>,
+    config: &'a Config,
+}
+
+impl StrategyTypes<'_> {
+    /// 结构体的策略：各字段策略组成元组后映射为结构体（元组最多 12 项，超出时分组嵌套）
+    fn struct_strategy(&self, info: &StructInfo) -> Option<String> {
+        if info.fields.is_empty() {
+            return Some(format!("Just({} {{}})", info.name));
+        }
+        let fields = info
+            .fields
+            .iter()
+            .map(|field| Some((field.name.as_str(), self.field_strategy(&info.name, field)?)))
+            .collect::<Option<Vec<_>>>()?;
+
+        let names: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+        let strategies: Vec<&str> = fields.iter().map(|(_, s)| s.as_str()).collect();
+        let (tuple, pattern) = if fields.len() <= 12 {
+            (
+                format!("({},)", strategies.join(", ")),
+                format!("({},)", names.join(", ")),
+            )
+        } else {
+            let tuple: Vec<String> = strategies
+                .chunks(10)
+                .map(|chunk| format!("({},)", chunk.join(", ")))
+                .collect();
+            let pattern: Vec<String> = names
+                .chunks(10)
+                .map(|chunk| format!("({},)", chunk.join(", ")))
+                .collect();
+            (
+                format!("({},)", tuple.join(", ")),
+                format!("({},)", pattern.join(", ")),
+            )
+        };
+        Some(format!(
+            "{}.prop_map(|{}| {} {{ {} }})",
+            tuple,
+            pattern,
+            info.name,
+            names.join(", ")
+        ))
+    }
+
+    /// 字段的策略：const 固定为常量，可选字段包装为 Option，无法生成的可选字段取 None
+    fn field_strategy(&self, owner: &str, field: &FieldInfo) -> Option<String> {
+        let value = match field.const_value.as_ref() {
+            Some(value) => {
+                default_literal(value, &field.rust_type).map(|expr| format!("Just({})", expr))
+            }
+            None => self.constrained_strategy(owner, field),
+        };
+        match (field.is_optional, value) {
+            (true, Some(value)) => Some(format!("proptest::option::of({})", value)),
+            (true, None) => Some("Just(None)".to_string()),
+            (false, value) => value,
+        }
+    }
+
+    /// 满足字段约束（长度、范围、元素数、pattern、format 与 multipleOf）的策略
+    fn constrained_strategy(&self, owner: &str, field: &FieldInfo) -> Option<String> {
+        let rust_type = field.rust_type.as_str();
+        if rust_type == "String" {
+            return Some(string_strategy(
+                field.min_length.unwrap_or(0),
+                string_max_length(field, self.config),
+                field.pattern.as_deref(),
+                field.string_format.as_deref(),
+            ));
+        }
+        if rust_type == "Vec<u8>" {
+            let (min, max) = size_bounds(field.min_length, field.max_length)?;
+            return Some(format!(
+                "proptest::collection::vec(any::<u8>(), {}..={})",
+                min, max
+            ));
+        }
+        if let Some(item_type) = rust_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            let (min, max) = size_bounds(field.min_items, field.max_items)?;
+            return match self.type_strategy(owner, item_type) {
+                Some(item) => Some(format!(
+                    "proptest::collection::vec({}, {}..={})",
+                    item, min, max
+                )),
+                None if min == 0 => Some("Just(Vec::new())".to_string()),
+                None => None,
+            };
+        }
+        if let Some(item_type) = rust_type
+            .strip_prefix("HashSet<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            let (min, mut max) = size_bounds(field.min_items, field.max_items)?;
+            // 枚举集合的元素数不能超过变体数
+            if let Some(inline_enum) = self.types.enums.get(item_type) {
+                max = max.min(inline_enum.values.len() as u32);
+            }
+            return match self.type_strategy(owner, item_type) {
+                Some(item) if min <= max => Some(format!(
+                    "proptest::collection::hash_set({}, {}..={})",
+                    item, min, max
+                )),
+                None if min == 0 => Some("Just(HashSet::new())".to_string()),
+                _ => None,
+            };
+        }
+        if is_integer_type(rust_type) {
+            return integer_strategy(field);
+        }
+        if matches!(rust_type, "f32" | "f64") {
+            return float_strategy(field);
+        }
+        if rust_type == "Decimal" {
+            return decimal_strategy(field);
+        }
+        self.type_strategy(owner, rust_type)
+    }
+
+    /// 不带字段约束的类型策略；`owner` 为正在生成策略的结构体，用于切断引用环
+    fn type_strategy(&self, owner: &str, rust_type: &str) -> Option<String> {
+        if let Some(inner) = rust_type
+            .strip_prefix("Box<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            return Some(format!(
+                "{}.prop_map(Box::new)",
+                self.type_strategy(owner, inner)?
+            ));
+        }
+        if is_integer_type(rust_type) || rust_type == "bool" {
+            return Some(format!("any::<{}>()", rust_type));
+        }
+        if let Some(item_type) = rust_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            return Some(format!(
+                "proptest::collection::vec({}, 0..={})",
+                self.type_strategy(owner, item_type)?,
+                STRATEGY_EXTRA_ITEMS
+            ));
+        }
+        if let Some(value_type) = rust_type
+            .strip_prefix("HashMap<String, ")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            return Some(format!(
+                "proptest::collection::hash_map({}, {}, 0..={})",
+                string_strategy(0, Some(16), None, None),
+                self.type_strategy(owner, value_type)?,
+                STRATEGY_EXTRA_ITEMS
+            ));
+        }
+
+        let value = match rust_type {
+            "String" => Some(string_strategy(0, Some(16), None, None)),
+            "f32" | "f64" | "Decimal" => {
+                let field = FieldInfo {
+                    rust_type: rust_type.to_string(),
+                    ..FieldInfo::default()
+                };
+                self.constrained_strategy(owner, &field)
+            }
+            "Value" => Some("Just(Value::Null)".to_string()),
+            // 到 2100 年为止的整秒时间，序列化后可无损往返
+            "DateTime<Utc>" => Some(
+                "(0i64..=4_102_444_800i64).prop_map(|secs| DateTime::<Utc>::from_timestamp(secs, 0).unwrap())"
+                    .to_string(),
+            ),
+            "Uuid" => Some("any::<[u8; 16]>().prop_map(Uuid::from_bytes)".to_string()),
+            "Ipv4Addr" | "Ipv6Addr" => Some(format!("any::<{}>()", rust_type)),
+            _ => None,
+        };
+        if value.is_some() {
+            return value;
+        }
+
+        if let Some(info) = self.types.structs.get(rust_type) {
+            if self.shared_structs.contains(rust_type)
+                || self.types.reaches(rust_type, owner, &mut Vec::new())
+            {
+                return None;
+            }
+            self.struct_strategy(info)?;
+            return Some(format!("{}()", strategy_fn_name(rust_type)));
+        }
+        if let Some(inline_enum) = self.types.enums.get(rust_type) {
+            let variants: Vec<String> = enum_variants(inline_enum)
+                .into_iter()
+                .map(|(variant, _)| format!("{}::{}", rust_type, variant))
+                .collect();
+            return Some(format!(
+                "proptest::sample::select(vec![{}])",
+                variants.join(", ")
+            ));
+        }
+        if let Some(union) = self.types.unions.get(rust_type) {
+            let variants: Vec<String> = union
+                .variants
+                .iter()
+                .zip(&union.constraints)
+                .filter_map(|((variant, inner), constraints)| {
+                    let strategy = match constraints {
+                        Some(constraints) => {
+                            self.constrained_strategy(owner, &constraints.inner_field())?
+                        }
+                        None => self.type_strategy(owner, inner)?,
+                    };
+                    Some(format!(
+                        "{}.prop_map({}::{})",
+                        strategy_receiver(strategy),
+                        rust_type,
+                        variant
+                    ))
+                })
+                .collect();
+            return match variants.as_slice() {
+                [] => None,
+                [variant] => Some(variant.clone()),
+                variants => Some(format!("prop_oneof![{}]", variants.join(", "))),
+            };
+        }
+        if let Some(newtype) = self.types.newtypes.get(rust_type) {
+            let inner = newtype.inner_field();
+            return Some(format!(
+                "{}.prop_map({})",
+                strategy_receiver(self.constrained_strategy(owner, &inner)?),
+                rust_type
+            ));
+        }
+        None
+    }
+}
+
+/// 作为 `.prop_map` 接收者的策略表达式：裸范围（如 `1u32..=8u32`）需加括号
+fn strategy_receiver(strategy: String) -> String {
+    let bare_range =
+        strategy.contains("..") && strategy.starts_with(|c: char| c.is_ascii_digit() || c == '-');
+    if bare_range {
+        format!("({})", strategy)
+    } else {
+        strategy
+    }
+}
+
+/// 类型表达式中出现的类型名（如 `Vec<Box<FooType>>` 中的 Vec、Box 与 FooType）
+fn type_names(rust_type: &str) -> Vec<&str> {
+    rust_type
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// 元素数的上下限：未声明上限时比下限多 `STRATEGY_EXTRA_ITEMS`，上下限矛盾时返回 None
+fn size_bounds(min: Option<u32>, max: Option<u32>) -> Option<(u32, u32)> {
+    let min = min.unwrap_or(0);
+    let max = max.unwrap_or(min + STRATEGY_EXTRA_ITEMS);
+    (min <= max).then_some((min, max))
+}
+
+/// 字符串策略：pattern 与 format 由正则生成并按字符数过滤，其余生成可打印字符
+fn string_strategy(
+    min_length: u32,
+    max_length: Option<u32>,
+    pattern: Option<&str>,
+    format: Option<&str>,
+) -> String {
+    let regex = match (pattern, format) {
+        (Some(pattern), _) => strip_anchors(pattern).to_string(),
+        (None, Some("email")) => "[a-z]{1,16}@example\\.com".to_string(),
+        (None, Some("uri")) => "https://example\\.com/[a-z]{0,16}".to_string(),
+        (None, _) => {
+            let max = max_length.map(|max| max.to_string()).unwrap_or_default();
+            return format!(
+                "proptest::string::string_regex({:?}).unwrap()",
+                format!("\\PC{{{},{}}}", min_length, max)
+            );
+        }
+    };
+
+    let strategy = format!("proptest::string::string_regex({:?}).unwrap()", regex);
+    let length = match max_length {
+        Some(max) => format!("({}..={}).contains(&s.chars().count())", min_length, max),
+        None if min_length > 0 => format!("s.chars().count() >= {}", min_length),
+        None => return strategy,
+    };
+    format!("{}.prop_filter(\"length\", |s| {})", strategy, length)
+}
+
+/// 去掉 pattern 首尾的 `^`/`$`：proptest 不支持锚点，而生成的字符串本身就整体匹配
+fn strip_anchors(pattern: &str) -> &str {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    match pattern.strip_suffix('$') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => pattern,
+    }
+}
+
+/// 整数类型的取值范围
+fn integer_type_range(rust_type: &str) -> (i128, i128) {
+    match rust_type {
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        _ => (0, u64::MAX as i128),
+    }
+}
+
+/// 整数策略：范围取 schema 上下限与类型范围的交集，整数 multipleOf 按倍数生成
+fn integer_strategy(field: &FieldInfo) -> Option<String> {
+    let rust_type = field.rust_type.as_str();
+    let (type_min, type_max) = integer_type_range(rust_type);
+    let (min, max) = numeric_bounds(field);
+    let parse = |bound: Option<String>| bound.and_then(|b| b.parse::<i128>().ok());
+    let min = parse(min).map_or(type_min, |min| min.max(type_min));
+    let max = parse(max).map_or(type_max, |max| max.min(type_max));
+    if min > max {
+        return None;
+    }
+
+    match field.multiple_of.filter(|m| m.fract() == 0.0) {
+        Some(multiple_of) if has_multiple_of_check(field) => {
+            let multiple_of = multiple_of as i128;
+            let low = min.div_euclid(multiple_of) + (min.rem_euclid(multiple_of) != 0) as i128;
+            let high = max.div_euclid(multiple_of);
+            (low <= high).then(|| {
+                format!(
+                    "({}i128..={}i128).prop_map(|k| (k * {}) as {})",
+                    low, high, multiple_of, rust_type
+                )
+            })
+        }
+        _ if (min, max) == (type_min, type_max) => Some(format!("any::<{}>()", rust_type)),
+        _ => Some(format!("{}{}..={}{}", min, rust_type, max, rust_type)),
+    }
+}
+
+/// 非整数的上下限；未声明的一侧取距另一侧 `STRATEGY_NUMBER_SPAN` 的有限值
+fn number_bounds(field: &FieldInfo) -> (f64, f64) {
+    let min = field.min_value.unwrap_or_else(|| {
+        field
+            .max_value
+            .map_or(-STRATEGY_NUMBER_SPAN, |max| max - STRATEGY_NUMBER_SPAN)
+    });
+    let max = field.max_value.unwrap_or(min + 2.0 * STRATEGY_NUMBER_SPAN);
+    (min, max)
+}
+
+/// multipleOf 的倍数 k 的范围，使 k * multiple_of 落在（可能排他的）上下限内
+fn multiple_range(field: &FieldInfo, multiple_of: f64) -> Option<(i64, i64)> {
+    let (min, max) = number_bounds(field);
+    let mut low = (min / multiple_of).ceil();
+    if field.exclusive_min && low * multiple_of <= min {
+        low += 1.0;
+    }
+    let mut high = (max / multiple_of).floor();
+    if field.exclusive_max && high * multiple_of >= max {
+        high -= 1.0;
+    }
+    (low <= high).then_some((low as i64, high as i64))
+}
+
+/// 浮点策略：包含边界用闭区间，排他上限用半开区间，排他下限再过滤
+fn float_strategy(field: &FieldInfo) -> Option<String> {
+    let rust_type = field.rust_type.as_str();
+    let literal = |value: f64| match rust_type {
+        "f32" => format!("{:?}f32", value as f32),
+        _ => format!("{:?}f64", value),
+    };
+
+    if let Some(multiple_of) = field.multiple_of.filter(|_| has_multiple_of_check(field)) {
+        // f32 的舍入误差超出生成的 multipleOf 检查的容差
+        if rust_type == "f32" && multiple_of.fract() != 0.0 {
+            return None;
+        }
+        let (low, high) = multiple_range(field, multiple_of)?;
+        return Some(format!(
+            "({}i64..={}i64).prop_map(|k| (k as f64 * {:?}) as {})",
+            low, high, multiple_of, rust_type
+        ));
+    }
+
+    let (min, max) = number_bounds(field);
+    if min > max || (min == max && (field.exclusive_min || field.exclusive_max)) {
+        return None;
+    }
+    let range = if field.exclusive_max {
+        format!("{}..{}", literal(min), literal(max))
+    } else {
+        format!("{}..={}", literal(min), literal(max))
+    };
+    if field.exclusive_min {
+        Some(format!(
+            "({}).prop_filter(\"exclusive minimum\", |v| *v > {})",
+            range,
+            literal(min)
+        ))
+    } else {
+        Some(format!("({})", range))
+    }
+}
+
+/// Decimal 策略：在 schema 边界的小数位数（至少两位）下按最小单位取整数倍
+fn decimal_strategy(field: &FieldInfo) -> Option<String> {
+    if let Some(multiple_of) = field.multiple_of.filter(|_| has_multiple_of_check(field)) {
+        let (low, high) = multiple_range(field, multiple_of)?;
+        return Some(format!(
+            "({}i64..={}i64).prop_map(|k| Decimal::from(k) * {})",
+            low,
+            high,
+            decimal_literal(&multiple_of.to_string())
+        ));
+    }
+
+    let (low, high, scale) = decimal_units(field)?;
+    Some(format!(
+        "({}i64..={}i64).prop_map(|units| Decimal::new(units, {}))",
+        low, high, scale
+    ))
+}
+
+/// Decimal 的小数位数（schema 边界的位数，至少两位）及该位数下落在上下限内的最小单位数范围
+fn decimal_units(field: &FieldInfo) -> Option<(i64, i64, u32)> {
+    let scale = [&field.min_literal, &field.max_literal]
+        .into_iter()
+        .flatten()
+        .filter_map(|literal| literal.split_once('.').map(|(_, frac)| frac.len() as u32))
+        .fold(2, u32::max);
+    let unit = 10f64.powi(scale as i32);
+    // 限制在 i64 与 Decimal 均可精确表示的范围内
+    let limit = 1e15;
+    let (min, max) = number_bounds(field);
+    let mut low = (min * unit).ceil().max(-limit);
+    if field.exclusive_min && low / unit <= min {
+        low += 1.0;
+    }
+    let mut high = (max * unit).floor().min(limit);
+    if field.exclusive_max && high / unit >= max {
+        high -= 1.0;
+    }
+    (low <= high).then_some((low as i64, high as i64, scale))
+}
+
+/// 生成 TryFrom<serde_json::Value> 实现，从已解析的 JSON 值反序列化并验证消息
+fn generate_try_from_value_impl(struct_info: &StructInfo) -> String {
+    let mut code = String::new();
+
+    code.push_str(&format!(
+        "impl TryFrom<serde_json::Value> for {} {{\n",
+        struct_info.name
+    ));
+    code.push_str("    type Error = FromValueError;\n");
+    code.push('\n');
+    code.push_str("    /// Deserializes the message from a JSON value and validates it.\n");
+    code.push_str("    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {\n");
+    code.push_str(
+        "        let message: Self = serde_json::from_value(value).map_err(FromValueError::Json)?;\n",
+    );
+    code.push_str("        message\n");
+    code.push_str("            .validate()\n");
+    code.push_str("            .map_err(FromValueError::Validation)?;\n");
+    code.push_str("        Ok(message)\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    code
+}
+
+/// 生成 FromStr 实现，从 JSON 字符串解析并验证消息
+fn generate_from_str_impl(struct_info: &StructInfo) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    code.push_str(&format!(
+        "impl std::str::FromStr for {} {{\n",
+        struct_info.name
+    ));
+    code.push_str("    type Err = Box<dyn std::error::Error + Send + Sync>;\n");
+    code.push('\n');
+    code.push_str("    /// Parses the message from its JSON representation and validates it.\n");
+    code.push_str("    fn from_str(s: &str) -> Result<Self, Self::Err> {\n");
+    code.push_str("        let message: Self = serde_json::from_str(s)?;\n");
+    code.push_str("        message.validate()?;\n");
+    code.push_str("        Ok(message)\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    Ok(code)
+}
+
+/// 生成 Display 实现，输出紧凑 JSON，序列化失败时输出结构体名
+fn generate_display_impl(struct_info: &StructInfo) -> String {
+    let mut code = String::new();
+
+    code.push_str(&format!("impl fmt::Display for {} {{\n", struct_info.name));
+    code.push_str(
+        "    /// Writes the message as compact JSON, or the struct name if serialization fails.\n",
+    );
+    code.push_str("    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {\n");
+    code.push_str("        match serde_json::to_string(self) {\n");
+    code.push_str("            Ok(json) => f.write_str(&json),\n");
+    code.push_str(&format!(
+        "            Err(_) => f.write_str(\"{}\"),\n",
+        struct_info.name
+    ));
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    code
+}
+
+/// 生成 validate_fast 方法，按声明顺序检查字段并在第一个错误处返回
+fn generate_validate_fast_method(
+    struct_info: &StructInfo,
+    config: &Config,
+) -> Result<String, GenError> {
+    let mut code = String::new();
+
+    code.push_str(
+        "    /// Validates the fields in declaration order, stopping at the first failure.\n",
+    );
+    code.push_str("    ///\n");
+    code.push_str("    /// # Returns\n");
+    code.push_str("    ///\n");
+    code.push_str("    /// `Ok(())` if all constraints hold, otherwise the name of the first invalid field.\n");
+    code.push_str("    pub fn validate_fast(&self) -> Result<(), &'static str> {\n");
+
+    for field in &struct_info.fields {
+        let mut violations = constraint_violations(field, "value", config);
+        violations.extend(const_violation(field, "value"));
+        if violations.is_empty() {
+            continue;
+        }
+
+        let condition = violations.join(" || ");
+        if field.is_optional {
+            code.push_str(&format!(
+                "        if let Some(value) = &self.{} {{\n",
+                field.name
+            ));
+            code.push_str(&format!("            if {} {{\n", condition));
+            code.push_str(&format!(
+                "                return Err(\"{}\");\n",
+                field.name
+            ));
+            code.push_str("            }\n");
+            code.push_str("        }\n");
+        } else {
+            code.push_str(&format!("        let value = &self.{};\n", field.name));
+            code.push_str(&format!("        if {} {{\n", condition));
+            code.push_str(&format!("            return Err(\"{}\");\n", field.name));
+            code.push_str("        }\n");
+        }
+    }
+
+    code.push_str("        Ok(())\n");
+    code.push_str("    }\n");
+
+    Ok(code)
+}
+
+/// 根据字段约束生成违反条件表达式，`value` 为指向字段值的引用表达式
+fn constraint_violations(field: &FieldInfo, value: &str, config: &Config) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    if !field.needs_validation {
+        return violations;
+    }
+
+    // 下限为 1 时写作 is_empty()（clippy::len_zero），下限为 0 时无需检查
+    let below = |length: &str, min: u32| match min {
+        0 => None,
+        1 => Some(format!("{}.is_empty()", value)),
+        min => Some(format!("{} < {}", length, min)),
+    };
+
+    if field.rust_type == "String" {
+        let length = format!("{}.chars().count()", value);
+        violations.extend(field.min_length.and_then(|min| below(&length, min)));
+        // 与 #[validate] 属性保持一致的最大长度（含默认值）
+        if let Some(max_length) = string_max_length(field, config) {
+            violations.push(format!("{} > {}", length, max_length));
+        }
+    } else if field.rust_type == "Vec<u8>" {
+        let length = format!("{}.len()", value);
+        violations.extend(field.min_length.and_then(|min| below(&length, min)));
+        if let Some(max_length) = field.max_length {
+            violations.push(format!("{}.len() > {}", value, max_length));
+        }
+    } else if field.rust_type.starts_with("Vec<") || field.rust_type.starts_with("HashSet<") {
+        let length = format!("{}.len()", value);
+        violations.extend(field.min_items.and_then(|min| below(&length, min)));
+        if let Some(max_items) = field.max_items {
+            violations.push(format!("{}.len() > {}", value, max_items));
+        }
+    } else if is_integer_type(&field.rust_type)
+        || field.rust_type == "f32"
+        || field.rust_type == "f64"
+    {
+        let (min, max) = numeric_bounds(field);
+        let (exclusive_min, exclusive_max) = exclusive_bounds(field);
+        if let Some(min) = min {
+            let op = if exclusive_min { "<=" } else { "<" };
+            violations.push(format!("*{} {} {}", value, op, min));
+        }
+        if let Some(max) = max {
+            let op = if exclusive_max { ">=" } else { ">" };
+            violations.push(format!("*{} {} {}", value, op, max));
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::extract_struct_info;
+    use crate::shared::collect_shared_types;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    fn struct_from_schema(schema: &Value, name: &str) -> StructInfo {
+        extract_struct_info(schema, name, &Config::default()).unwrap()
+    }
+
+    #[test]
+    fn test_validate_fast_checks_fields_in_declaration_order() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 2, "maxLength": 10 },
+                "count": { "type": "integer", "minimum": 1, "maximum": 5 },
+                "tags": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+                "flag": { "type": "boolean" }
+            },
+            "required": ["name", "count"]
+        });
+        let struct_info = struct_from_schema(&schema, "FooRequest");
+        let config = Config {
+            validate_fast: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&struct_info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains("pub fn validate_fast(&self) -> Result<(), &'static str> {"));
+        assert!(code.contains("if value.chars().count() < 2 || value.chars().count() > 10 {"));
+        assert!(code.contains("if *value < 1 || *value > 5 {"));
+        assert!(code.contains("if let Some(value) = &self.tags {"));
+        assert!(!code.contains("return Err(\"flag\")"));
+
+        let name_pos = code.find("return Err(\"name\")").unwrap();
+        let count_pos = code.find("return Err(\"count\")").unwrap();
+        let tags_pos = code.find("return Err(\"tags\")").unwrap();
+        assert!(name_pos < count_pos && count_pos < tags_pos);
+    }
+
+    fn pair_from_schemas(
+        base_name: &str,
+        request: &Value,
+        response: Option<&Value>,
+    ) -> MessagePair {
+        let mut pair = MessagePair::new(base_name.to_string());
+        pair.add_request(struct_from_schema(
+            request,
+            &format!("{}Request", base_name),
+        ));
+        if let Some(response) = response {
+            pair.add_response(struct_from_schema(
+                response,
+                &format!("{}Response", base_name),
+            ));
+        }
+        pair
+    }
+
+    #[test]
+    fn test_mod_file_preserves_lines_outside_markers() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let mod_path = output_dir.path().join("mod.rs");
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            header: false,
+            ..Config::default()
+        };
+        let schema = json!({ "type": "object", "properties": {} });
+        let authorize = pair_from_schemas("Authorize", &schema, Some(&schema));
+        let reset = pair_from_schemas("Reset", &schema, Some(&schema));
+
+        // First run inserts the markers around the generated block
+        generate_mod_file(&[&authorize], &config, &Manifest::default()).unwrap();
+        let first = fs::read_to_string(&mod_path).unwrap();
+        assert!(first.starts_with("// GEN-BEGIN\npub mod authorize;\n"));
+        assert!(first.ends_with("// GEN-END\n"));
+
+        // Hand-written lines around the generated region
+        let custom = format!(
+            "//! Hand-written module docs.\n\n{}\npub mod custom;\npub use custom::Helper;\n",
+            first.trim_end()
+        );
+        fs::write(&mod_path, custom).unwrap();
+
+        generate_mod_file(&[&authorize, &reset], &config, &Manifest::default()).unwrap();
+        let second = fs::read_to_string(&mod_path).unwrap();
+
+        assert!(second.starts_with("//! Hand-written module docs.\n\n// GEN-BEGIN\n"));
+        assert!(second.contains("pub mod reset;\n"));
+        assert!(second.contains("pub use reset::{ResetRequest, ResetResponse};\n"));
+        assert!(second.ends_with("// GEN-END\npub mod custom;\npub use custom::Helper;\n"));
+        assert_eq!(second.matches("// GEN-BEGIN").count(), 1);
+    }
+
+    #[test]
+    fn test_mod_file_keeps_hand_written_file_without_markers() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let mod_path = output_dir.path().join("mod.rs");
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            header: false,
+            ..Config::default()
+        };
+        let schema = json!({ "type": "object", "properties": {} });
+        let authorize = pair_from_schemas("Authorize", &schema, Some(&schema));
+
+        // Hand-maintained mod.rs that predates the markers
+        fs::write(
+            &mod_path,
+            "//! Hand-written module docs.\n\npub mod authorize;\npub mod custom;\n\npub use custom::Helper;\n",
+        )
+        .unwrap();
+
+        generate_mod_file(&[&authorize], &config, &Manifest::default()).unwrap();
+        let merged = fs::read_to_string(&mod_path).unwrap();
+
+        assert!(merged.starts_with(
+            "//! Hand-written module docs.\n\npub mod custom;\n\npub use custom::Helper;\n\n// GEN-BEGIN\n"
+        ));
+        assert!(merged
+            .ends_with("pub use authorize::{AuthorizeRequest, AuthorizeResponse};\n// GEN-END\n"));
+        // The declaration also written by the generator is kept only inside the markers
+        assert_eq!(merged.matches("pub mod authorize;").count(), 1);
+
+        // Later runs only replace the marked region
+        generate_mod_file(&[&authorize], &config, &Manifest::default()).unwrap();
+        assert_eq!(fs::read_to_string(&mod_path).unwrap(), merged);
+    }
+
+    #[test]
+    fn test_deprecated_message_gets_attribute_and_skips_reexport() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            skip_deprecated_exports: true,
+            ..Config::default()
+        };
+        let request = json!({
+            "type": "object",
+            "deprecated": true,
+            "x-deprecation-note": "Use GetCertificateChainStatus instead",
+            "properties": {}
+        });
+        let response = json!({ "type": "object", "properties": {} });
+        let pair = pair_from_schemas("GetCertificateStatus", &request, Some(&response));
+
+        let code = generate_struct_code(
+            pair.request.as_ref().unwrap(),
+            "request",
+            &pair.base_name,
+            &config,
+        )
+        .unwrap();
+        assert!(code.contains(
+            "#[deprecated(note = \"Use GetCertificateChainStatus instead\")]\n#[derive("
+        ));
+        let code = generate_struct_code(
+            pair.response.as_ref().unwrap(),
+            "response",
+            &pair.base_name,
+            &config,
+        )
+        .unwrap();
+        assert!(!code.contains("#[deprecated"));
+
+        generate_mod_file(&[&pair], &config, &Manifest::default()).unwrap();
+        let mod_file = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_file.contains("pub mod get_certificate_status;\n"));
+        assert!(
+            mod_file.contains("pub use get_certificate_status::GetCertificateStatusResponse;\n")
+        );
+        assert!(!mod_file.contains("GetCertificateStatusRequest"));
+    }
+
+    #[test]
+    fn test_from_str_impl_parses_and_validates() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "idTag": { "type": "string", "maxLength": 20 } },
+            "required": ["idTag"]
+        });
+        let struct_info = struct_from_schema(&schema, "AuthorizeRequest");
+        let config = Config {
+            generate_from_str: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&struct_info, "request", "Authorize", &config).unwrap();
+
+        assert!(code.contains("impl std::str::FromStr for AuthorizeRequest {"));
+        assert!(code.contains("let message: Self = serde_json::from_str(s)?;"));
+        assert!(code.contains("message.validate()?;"));
+
+        let default_code =
+            generate_struct_code(&struct_info, "request", "Authorize", &Config::default()).unwrap();
+        assert!(!default_code.contains("FromStr"));
+    }
+
+    #[test]
+    fn test_enum_set_field_gets_contains_and_insert() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "modes": {
+                    "type": "array",
+                    "items": { "$ref": "#/definitions/EnergyTransferModeEnumType" },
+                    "uniqueItems": true,
+                    "maxItems": 4
+                }
+            }
+        });
+        let config = Config {
+            enum_sets: true,
+            ..Config::default()
+        };
+        let struct_info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+
+        let code = generate_struct_code(&struct_info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains("pub modes: Option<HashSet<EnergyTransferModeEnumType>>,"));
+        assert!(code.contains("#[validate(length(max = 4))]"));
+        assert!(!code.contains("#[validate(nested)]"));
+        assert!(code.contains(
+            "pub fn contains_modes(&self, value: &EnergyTransferModeEnumType) -> bool {"
+        ));
+        assert!(code.contains("self.modes.as_ref().is_some_and(|set| set.contains(value))"));
+        assert!(code.contains(
+            "pub fn insert_modes(&mut self, value: EnergyTransferModeEnumType) -> bool {"
+        ));
+        assert!(code.contains("self.modes.get_or_insert_with(HashSet::new).insert(value)"));
+    }
+
+    #[test]
+    fn test_long_new_signature_and_docs_wrap() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "chargingStationIdentifier": {
+                    "type": "string",
+                    "description": "Identifier of the charging station that is sending this notification to the central system."
+                },
+                "connectorIdentifier": { "type": "integer" },
+                "transactionIdentifier": { "type": "string" }
+            },
+            "required": ["chargingStationIdentifier", "connectorIdentifier", "transactionIdentifier"]
+        });
+        let struct_info = struct_from_schema(&schema, "FooRequest");
+
+        let code =
+            generate_struct_code(&struct_info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(code.contains(
+            "    pub fn new(\n        charging_station_identifier: String,\n        connector_identifier: i32,\n        transaction_identifier: String,\n    ) -> Self {\n"
+        ));
+        assert!(code.contains(
+            "    /// * `charging_station_identifier` - Identifier of the charging station that is sending this\n    ///   notification to the central system.\n"
+        ));
+        assert!(code.contains(
+            "    pub fn set_charging_station_identifier(\n        &mut self,\n        charging_station_identifier: String,\n    ) -> &mut Self {\n"
+        ));
+        assert!(code.lines().all(|line| line.chars().count() <= 100));
+
+        // A generous limit keeps the signature on one line
+        let wide = Config {
+            max_line_length: 200,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&struct_info, "request", "Foo", &wide).unwrap();
+        assert!(code.contains("    pub fn new(charging_station_identifier: String, connector_identifier: i32, transaction_identifier: String) -> Self {\n"));
+    }
+
+    #[test]
+    fn test_x_rename_all_overrides_struct_casing() {
+        let schema = json!({
+            "type": "object",
+            "x-rename-all": "snake_case",
+            "properties": {
+                "vendor_id": { "type": "string" },
+                "legacyId": { "type": "string" }
+            },
+            "required": ["vendor_id", "legacyId"]
+        });
+        let struct_info = struct_from_schema(&schema, "LegacyDataType");
+
+        let code = generate_struct_code(
+            &struct_info,
+            "message",
+            "LegacyDataType",
+            &Config::default(),
+        )
+        .unwrap();
+
+        assert!(code.contains("#[serde(rename_all = \"snake_case\")]\n"));
+        assert!(code.contains("    #[validate(length(max = 512))]\n    pub vendor_id: String,\n"));
+        assert!(code.contains("    #[serde(rename = \"legacyId\")]\n"));
+
+        // Without the override the global camelCase default applies
+        let schema = json!({
+            "type": "object",
+            "properties": { "legacyId": { "type": "string" } },
+            "required": ["legacyId"]
+        });
+        let code = generate_struct_code(
+            &struct_from_schema(&schema, "LegacyDataType"),
+            "message",
+            "LegacyDataType",
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(code.contains("#[serde(rename_all = \"camelCase\")]\n"));
+        assert!(!code.contains("rename = \"legacyId\""));
+    }
+
+    #[test]
+    fn test_apply_serde_rename_rule() {
+        assert_eq!(apply_serde_rename_rule("evse_id", "camelCase"), "evseId");
+        assert_eq!(apply_serde_rename_rule("evse_id", "PascalCase"), "EvseId");
+        assert_eq!(apply_serde_rename_rule("evse_id", "snake_case"), "evse_id");
+        assert_eq!(apply_serde_rename_rule("evse_id", "kebab-case"), "evse-id");
+        assert_eq!(
+            apply_serde_rename_rule("evse_id", "SCREAMING_SNAKE_CASE"),
+            "EVSE_ID"
+        );
+    }
+
+    #[test]
+    fn test_validate_fast_is_opt_in() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } }
+        });
+        let struct_info = struct_from_schema(&schema, "FooRequest");
+
+        let code =
+            generate_struct_code(&struct_info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(!code.contains("validate_fast"));
+    }
+
+    #[test]
+    fn test_binary_field_uses_serde_bytes() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "payload": { "type": "string", "format": "binary", "maxLength": 512 },
+                "signature": { "type": "string", "format": "binary" }
+            },
+            "required": ["payload"]
+        });
+        let struct_info = struct_from_schema(&schema, "DataTransferRequest");
+
+        let code =
+            generate_struct_code(&struct_info, "request", "DataTransfer", &Config::default())
+                .unwrap();
+
+        assert!(code.contains(
+            "    #[serde(with = \"serde_bytes\")]\n    #[validate(length(max = 512))]\n    pub payload: Vec<u8>,"
+        ));
+        assert!(code.contains(
+            "        with = \"serde_bytes\",\n        default,\n        skip_serializing_if = \"Option::is_none\"\n"
+        ));
+        assert!(code.contains("pub signature: Option<Vec<u8>>,"));
+    }
+
+    #[test]
+    fn test_iter_accessors_for_array_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "evseIds": { "type": "array", "items": { "type": "integer" } },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "name": { "type": "string" }
+            },
+            "required": ["evseIds"]
+        });
+        let struct_info = struct_from_schema(&schema, "FooRequest");
+        let config = Config {
+            iter_accessors: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&struct_info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains(
+            "    pub fn iter_evse_ids(&self) -> impl Iterator<Item = &i32> {\n        self.evse_ids.iter()\n"
+        ));
+        assert!(code.contains(
+            "    pub fn iter_tags(&self) -> impl Iterator<Item = &String> {\n        self.tags.iter().flatten()\n"
+        ));
+        assert!(!code.contains("iter_name"));
+
+        let code =
+            generate_struct_code(&struct_info, "request", "Foo", &Config::default()).unwrap();
+        assert!(!code.contains("iter_evse_ids"));
+    }
+
+    #[test]
+    fn test_import_block_is_deterministic() {
+        let imports = [
+            "use serde::{Deserialize, Serialize};",
+            "use validator::Validate;",
+            "use crate::v2_1::datatypes::CustomDataType;",
+            "use crate::v2_1::datatypes::StatusInfoType;",
+            "use crate::v2_1::enumerations::GenericStatusEnumType;",
+            "use chrono::{DateTime, Utc};",
+            "use rust_decimal::Decimal;",
+        ];
+        let forward: HashSet<String> = imports.iter().map(|s| s.to_string()).collect();
+        let reverse: HashSet<String> = imports.iter().rev().map(|s| s.to_string()).collect();
+
+        let expected = optimize_imports(&forward);
+        for _ in 0..10 {
+            assert_eq!(optimize_imports(&forward), expected);
+            assert_eq!(optimize_imports(&reverse), expected);
+        }
+        assert_eq!(
+            expected,
+            vec![
+                "use crate::v2_1::datatypes::{CustomDataType, StatusInfoType};",
+                "use crate::v2_1::enumerations::GenericStatusEnumType;",
+                "use chrono::{DateTime, Utc};",
+                "use rust_decimal::Decimal;",
+                "use serde::{Deserialize, Serialize};",
+                "use validator::Validate;",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repr_c_only_for_scalar_structs() {
+        let config = Config {
+            repr_c: true,
+            ..Config::default()
+        };
+
+        let scalar = struct_from_schema(
+            &json!({
+                "type": "object",
+                "properties": {
+                    "evseId": { "type": "integer" },
+                    "available": { "type": "boolean" }
+                },
+                "required": ["evseId", "available"]
+            }),
+            "EvseStatusRequest",
+        );
+        let code = generate_struct_code(&scalar, "request", "EvseStatus", &config).unwrap();
+        assert!(code.contains("#[serde(rename_all = \"camelCase\")]\n#[repr(C)]\npub struct"));
+
+        let with_string = struct_from_schema(
+            &json!({
+                "type": "object",
+                "properties": {
+                    "evseId": { "type": "integer" },
+                    "idTag": { "type": "string" }
+                },
+                "required": ["evseId", "idTag"]
+            }),
+            "AuthorizeRequest",
+        );
+        assert!(!is_ffi_safe(&with_string));
+        let code = generate_struct_code(&with_string, "request", "Authorize", &config).unwrap();
+        assert!(!code.contains("#[repr(C)]"));
+
+        let code =
+            generate_struct_code(&scalar, "request", "EvseStatus", &Config::default()).unwrap();
+        assert!(!code.contains("#[repr(C)]"));
+    }
+
+    #[test]
+    fn test_into_setters_accept_impl_into() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string" },
+                "note": { "type": "string" },
+                "evseIds": { "type": "array", "items": { "type": "integer" } },
+                "count": { "type": "integer" }
+            },
+            "required": ["idTag", "evseIds", "count"]
+        });
+        let struct_info = struct_from_schema(&schema, "FooRequest");
+        let config = Config {
+            into_setters: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&struct_info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains(
+            "pub fn set_id_tag(&mut self, id_tag: impl Into<String>) -> &mut Self {\n        self.id_tag = id_tag.into();\n"
+        ));
+        assert!(code.contains("pub fn set_evse_ids(&mut self, evse_ids: impl Into<Vec<i32>>)"));
+        assert!(code.contains("pub fn set_note(&mut self, note: Option<String>)"));
+        assert!(code.contains(
+            "pub fn with_note(mut self, note: impl Into<String>) -> Self {\n        self.note = Some(note.into());\n"
+        ));
+        assert!(code.contains("pub fn set_count(&mut self, count: i32)"));
+
+        let code =
+            generate_struct_code(&struct_info, "request", "Foo", &Config::default()).unwrap();
+        assert!(code.contains("pub fn set_id_tag(&mut self, id_tag: String)"));
+    }
+
+    #[test]
+    fn test_action_returns_pair_base_name() {
+        let request = json!({
+            "type": "object",
+            "properties": { "idTag": { "type": "string" } },
+            "required": ["idTag"]
+        });
+        let response = json!({ "type": "object", "properties": {} });
+        let pair = pair_from_schemas("Foo", &request, Some(&response));
+
+        for info in [&pair.request, &pair.response].into_iter().flatten() {
+            let code =
+                generate_struct_code(info, "request", &pair.base_name, &Config::default()).unwrap();
+            assert!(code
+                .contains("    pub fn action(&self) -> &'static str {\n        \"Foo\"\n    }\n"));
+        }
+    }
+
+    #[test]
+    fn test_validation_messages_are_opt_in() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "stringWithMinMax": { "type": "string", "minLength": 5, "maxLength": 10 },
+                "note": { "type": "string" },
+                "evseId": { "type": "integer", "minimum": 1 }
+            },
+            "required": ["stringWithMinMax", "note", "evseId"]
+        });
+        let struct_info = struct_from_schema(&schema, "FooRequest");
+        let config = Config {
+            validation_messages: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&struct_info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains(
+            "#[validate(length(min = 5, max = 10, message = \"string_with_min_max must be between 5 and 10 chars\"))]"
+        ));
+        assert!(code.contains(
+            "#[validate(length(max = 512, message = \"note must be at most 512 chars\"))]"
+        ));
+        assert!(
+            code.contains("#[validate(range(min = 1, message = \"evse_id must be at least 1\"))]")
+        );
+
+        let code =
+            generate_struct_code(&struct_info, "request", "Foo", &Config::default()).unwrap();
+        assert!(!code.contains("message ="));
+    }
+
+    #[test]
+    fn test_message_type_id_constants() {
+        let request = json!({ "type": "object", "properties": {} });
+        let response = json!({ "type": "object", "properties": {} });
+        let pair = pair_from_schemas("Heartbeat", &request, Some(&response));
+        let config = Config {
+            message_type_ids: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(
+            pair.request.as_ref().unwrap(),
+            "request",
+            &pair.base_name,
+            &config,
+        )
+        .unwrap();
+        assert!(code.contains("impl HeartbeatRequest {\n    /// OCPP-J message type ID of the Call frame carrying this message.\n    pub const MESSAGE_TYPE_ID: u8 = 2;\n"));
+
+        let code = generate_struct_code(
+            pair.response.as_ref().unwrap(),
+            "response",
+            &pair.base_name,
+            &config,
+        )
+        .unwrap();
+        assert!(code.contains("    pub const MESSAGE_TYPE_ID: u8 = 3;\n"));
+
+        let code = generate_struct_code(
+            pair.request.as_ref().unwrap(),
+            "request",
+            &pair.base_name,
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(!code.contains("MESSAGE_TYPE_ID"));
+    }
+
+    #[test]
+    fn test_optional_constrained_array_keeps_length_and_nested() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "statusInfos": {
+                    "type": "array",
+                    "items": { "$ref": "#/definitions/StatusInfoType" },
+                    "minItems": 1,
+                    "maxItems": 2
+                }
+            }
+        });
+        let struct_info = struct_from_schema(&schema, "FooRequest");
+        let config = Config {
+            validate_fast: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&struct_info, "request", "Foo", &config).unwrap();
+
+        // validator 对 Option 透明：值为 None 时跳过，Some 时同时检查长度与嵌套元素
+        assert!(code.contains(
+            "    #[validate(length(min = 1, max = 2))]\n    #[validate(nested)]\n    pub status_infos: Option<Vec<StatusInfoType>>,\n"
+        ));
+        assert!(code.contains(
+            "        if let Some(value) = &self.status_infos {\n            if value.is_empty() || value.len() > 2 {\n"
+        ));
+    }
+
+    #[test]
+    fn test_constants_module_collects_limits() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let request = json!({
+            "type": "object",
+            "properties": {
+                "string": { "type": "string", "minLength": 5, "maxLength": 10 },
+                "evseId": { "type": "integer", "minimum": 1, "maximum": 8 },
+                "limit": { "type": "number", "maximum": 22.5 },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 4 }
+            }
+        });
+        let response = json!({ "type": "object", "properties": {} });
+        let pair = pair_from_schemas("Foo", &request, Some(&response));
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            emit_constants_module: true,
+            ..Config::default()
+        };
+
+        generate_constants_module(&[&pair], &config, &Manifest::default()).unwrap();
+        generate_mod_file(&[&pair], &config, &Manifest::default()).unwrap();
+
+        let constants = fs::read_to_string(output_dir.path().join("constants.rs")).unwrap();
+        assert!(constants.contains("pub mod foo_request {\n"));
+        assert!(constants.contains("    pub const STRING_MIN_LEN: usize = 5;\n"));
+        assert!(constants.contains("    pub const STRING_MAX_LEN: usize = 10;\n"));
+        assert!(constants.contains("    pub const EVSE_ID_MIN: u32 = 1;\n"));
+        assert!(constants.contains("    pub const EVSE_ID_MAX: u32 = 8;\n"));
+        assert!(constants.contains("    pub const LIMIT_MAX: f64 = 22.5;\n"));
+        assert!(constants.contains("    pub const TAGS_MAX_ITEMS: usize = 4;\n"));
+        // 没有限制的结构体不生成模块
+        assert!(!constants.contains("foo_response"));
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod constants;\n"));
+    }
+
+    #[test]
+    fn test_newtype_scalar_wrapper_code() {
+        let newtype = NewtypeInfo {
+            name: "CiString20Type".to_string(),
+            inner_type: "String".to_string(),
+            max_length: Some(20),
+            ..NewtypeInfo::default()
+        };
+
+        let code = generate_newtype_code(&newtype, &Config::default());
+
+        assert!(code.contains("#[serde(transparent)]\npub struct CiString20Type(pub String);\n"));
+        assert!(code.contains("impl Validate for CiString20Type {\n"));
+        assert!(code
+            .contains("        let value = &self.0;\n        if value.chars().count() > 20 {\n"));
+        assert!(
+            code.contains("impl std::ops::Deref for CiString20Type {\n    type Target = String;\n")
+        );
+        assert!(code.contains("impl From<String> for CiString20Type {"));
+        assert!(code.contains("impl From<&str> for CiString20Type {"));
+
+        // Numeric wrappers check the same bounds as fields, exclusive ones included
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "meter": { "$ref": "#/definitions/MeterType" },
+                "price": { "$ref": "#/definitions/PriceType" }
+            },
+            "definitions": {
+                "MeterType": { "type": "integer", "maximum": 4294967295i64 },
+                "PriceType": { "type": "number", "exclusiveMinimum": 0, "maximum": 99.5 }
+            }
+        });
+        let config = Config {
+            newtype_scalars: true,
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        let meter = info.fields[0].newtype.as_ref().unwrap();
+        assert_eq!(meter.inner_type, "i64");
+        let code = generate_newtype_code(meter, &Config::default());
+        assert!(code.contains("        if *value > 4294967295 {\n"));
+
+        let price = info.fields[1].newtype.as_ref().unwrap();
+        let code = generate_newtype_code(price, &Config::default());
+        assert!(code.contains(
+            "        if *value <= Decimal::new(0, 0) || *value > Decimal::new(995, 1) {\n"
+        ));
+    }
+
+    #[test]
+    fn test_iter_field_delegates_to_batch_vec() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "generatedAt": { "type": "string" },
+                "data": { "type": "array", "items": { "$ref": "#/definitions/StreamDataElementType" } }
+            },
+            "required": ["generatedAt", "data"]
+        });
+        let struct_info = struct_from_schema(&schema, "NotifyPeriodicEventStream");
+        let explicit = Config {
+            iter_fields: vec!["NotifyPeriodicEventStream.data".to_string()],
+            ..Config::default()
+        };
+        let inferred = Config {
+            iter_fields: vec!["NotifyPeriodicEventStream".to_string()],
+            ..Config::default()
+        };
+
+        for config in [&explicit, &inferred] {
+            let code =
+                generate_struct_code(&struct_info, "message", "NotifyPeriodicEventStream", config)
+                    .unwrap();
+            assert!(code.contains(
+                "    pub fn iter(&self) -> std::slice::Iter<'_, StreamDataElementType> {\n        self.data.iter()\n"
+            ));
+            assert!(code.contains("impl IntoIterator for NotifyPeriodicEventStream {\n    type Item = StreamDataElementType;\n"));
+            assert!(code.contains("impl<'a> IntoIterator for &'a NotifyPeriodicEventStream {"));
+        }
+
+        let code = generate_struct_code(
+            &struct_info,
+            "message",
+            "NotifyPeriodicEventStream",
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(!code.contains("IntoIterator"));
+    }
+
+    #[test]
+    fn test_validate_dispatch_matches_request_actions() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let request = json!({
+            "type": "object",
+            "properties": { "idTag": { "type": "string", "maxLength": 20 } },
+            "required": ["idTag"]
+        });
+        let response = json!({ "type": "object", "properties": {} });
+        let authorize = pair_from_schemas("Authorize", &request, Some(&response));
+        let heartbeat = pair_from_schemas("Heartbeat", &response, Some(&response));
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            emit_validate_dispatch: true,
+            ..Config::default()
+        };
+
+        generate_validate_dispatch(&[&heartbeat, &authorize], &config, &Manifest::default())
+            .unwrap();
+        generate_mod_file(&[&heartbeat, &authorize], &config, &Manifest::default()).unwrap();
+
+        let code = fs::read_to_string(output_dir.path().join("dispatch.rs")).unwrap();
+        let authorize_pos = code
+            .find("        \"Authorize\" => {\n            let message: super::authorize::AuthorizeRequest =\n")
+            .unwrap();
+        let heartbeat_pos = code
+            .find("        \"Heartbeat\" => {\n            let message: super::heartbeat::HeartbeatRequest =\n")
+            .unwrap();
+        assert!(authorize_pos < heartbeat_pos);
+        assert!(code.contains(
+            "        _ => Err(ValidateMessageError::UnknownAction(action.to_string())),\n"
+        ));
+        assert!(!code.contains("AuthorizeResponse"));
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod dispatch;\n"));
+    }
+
+    #[test]
+    fn test_inline_enum_emitted_before_struct_with_renames() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "status": {
+                    "type": "string",
+                    "enum": ["Accepted", "ISO15118-20", "2G", ""]
+                }
+            },
+            "required": ["status"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        let enum_pos = code.find("pub enum FooRequestStatusEnumType {\n").unwrap();
+        let struct_pos = code.find("pub struct FooRequest {").unwrap();
+        assert!(enum_pos < struct_pos);
+        assert!(code.contains("    #[serde(rename = \"Accepted\")]\n    Accepted,\n"));
+        assert!(code.contains("    #[serde(rename = \"ISO15118-20\")]\n    Iso1511820,\n"));
+        assert!(code.contains("    #[serde(rename = \"2G\")]\n    Value2G,\n"));
+        assert!(code.contains("    #[serde(rename = \"\")]\n    Empty,\n"));
+        assert!(code.contains("    pub status: FooRequestStatusEnumType,\n"));
+        assert!(!code.contains("#[validate(length"));
+        // as_str 与 FromStr 使用与 rename 相同的线上字符串
+        assert!(code.contains("            Self::Iso1511820 => \"ISO15118-20\",\n"));
+        assert!(code.contains("            \"ISO15118-20\" => Ok(Self::Iso1511820),\n"));
+        assert!(code.contains("            \"\" => Ok(Self::Empty),\n"));
+        assert!(code.contains(
+            "            other => Err(serde::de::Error::unknown_variant(other, &[\"Accepted\", \"ISO15118-20\", \"2G\", \"\"])),\n"
+        ));
+    }
+
+    #[test]
+    fn test_enum_all_lists_every_variant_in_schema_order() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "status": { "type": "string", "enum": ["Accepted", "ISO15118-20", "2G"] }
+            },
+            "required": ["status"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+        assert!(!code.contains("pub const ALL"));
+
+        let config = Config {
+            enum_all: true,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+        assert!(code.contains(
+            "    pub const ALL: &[Self] = &[Self::Accepted, Self::Iso1511820, Self::Value2G];\n"
+        ));
+    }
+
+    #[test]
+    fn test_enum_default_uses_schema_default_or_first_variant() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "status": { "type": "string", "enum": ["Accepted", "Rejected"] },
+                "mode": { "type": "string", "enum": ["Auto", "Manual"], "default": "Manual" }
+            },
+            "required": ["status"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+        assert!(code.contains(
+            "impl Default for FooRequestModeEnumType {\n    /// Returns `Manual`.\n    fn default() -> Self {\n        Self::Manual\n    }\n}\n"
+        ));
+        // 没有 schema default 且未开启 --enum-default-first 时不实现 Default
+        assert!(!code.contains("impl Default for FooRequestStatusEnumType"));
+
+        let config = Config {
+            enum_default_first: true,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+        assert!(code.contains(
+            "impl Default for FooRequestStatusEnumType {\n    /// Returns `Accepted`.\n    fn default() -> Self {\n        Self::Accepted\n    }\n}\n"
+        ));
+        assert!(code.contains("        Self::Manual\n"));
+    }
+
+    #[test]
+    fn test_enum_str_eq_compares_wire_values() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "status": { "type": "string", "enum": ["Accepted", "ISO15118-20"] }
+            },
+            "required": ["status"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+        assert!(!code.contains("impl PartialEq<"));
+
+        let config = Config {
+            enum_str_eq: true,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+        assert!(code.contains(
+            "impl PartialEq<&str> for FooRequestStatusEnumType {\n    /// Compares the wire value of the variant with the string.\n    fn eq(&self, other: &&str) -> bool {\n        self.as_str() == *other\n    }\n}\n"
+        ));
+        assert!(code.contains(
+            "impl PartialEq<String> for FooRequestStatusEnumType {\n    /// Compares the wire value of the variant with the string.\n    fn eq(&self, other: &String) -> bool {\n        self.as_str() == other.as_str()\n    }\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_pattern_emits_regex_static_and_validation() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "maxLength": 20, "pattern": "^[0-9A-F]+\\d\"$" },
+                "count": { "type": "integer", "pattern": "^\\d+$" }
+            },
+            "required": ["idTag"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        assert!(info.imports.contains("use regex::Regex;"));
+        assert!(info.imports.contains("use once_cell::sync::Lazy;"));
+
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+        assert!(code.contains(
+            "static FOO_REQUEST_ID_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(\"^[0-9A-F]+\\\\d\\\"$\").unwrap());\n"
+        ));
+        assert!(code.contains(
+            "    #[validate(length(max = 20))]\n    #[validate(regex(path = *FOO_REQUEST_ID_TAG_REGEX))]\n    pub id_tag: String,\n"
+        ));
+        assert!(!code.contains("COUNT_REGEX"));
+    }
+
+    #[test]
+    fn test_decimal_range_uses_custom_validator() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "both": { "type": "number", "minimum": -1.5, "maximum": 100 },
+                "minOnly": { "type": "number", "minimum": 0 },
+                "maxOnly": { "type": "number", "maximum": 0.25 },
+                "unbounded": { "type": "number" }
+            },
+            "required": ["both"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(code.contains(
+            "fn validate_foo_request_both_range(value: &Decimal) -> Result<(), validator::ValidationError> {\n    if *value < Decimal::new(-15, 1) || *value > Decimal::new(100, 0) {\n"
+        ));
+        assert!(code.contains("    if *value < Decimal::new(0, 0) {\n"));
+        assert!(code.contains("    if *value > Decimal::new(25, 2) {\n"));
+        assert!(code.contains(
+            "    #[validate(custom(function = \"validate_foo_request_both_range\"))]\n    pub both: Decimal,\n"
+        ));
+        assert!(code.contains(
+            "    #[validate(custom(function = \"validate_foo_request_min_only_range\"))]\n    pub min_only: Option<Decimal>,\n"
+        ));
+        assert!(!code.contains("validate_foo_request_unbounded_range"));
+    }
+
+    #[test]
+    fn test_exclusive_bounds_translate_per_type() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer", "exclusiveMinimum": 0, "exclusiveMaximum": 10 },
+                "ratio": { "type": "number", "exclusiveMinimum": 0 },
+                "amount": { "type": "number", "exclusiveMaximum": 2.5 }
+            },
+            "required": ["count", "ratio", "amount"]
+        });
+        let config = Config {
+            type_overrides: vec!["ratio=f64".to_string()],
+            validate_fast: true,
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        // Integers: the value equal to the exclusive bound is excluded by the inclusive range
+        assert!(code.contains("    #[validate(range(min = 1, max = 9))]\n    pub count: u32,\n"));
+        assert!(code.contains("    #[validate(range(exclusive_min = 0.0))]\n    pub ratio: f64,\n"));
+        assert!(code.contains("    if *value >= Decimal::new(25, 1) {\n"));
+        assert!(code.contains("        if *value < 1 || *value > 9 {\n"));
+    }
+
+    #[test]
+    fn test_derive_default_uses_zero_values_and_skips_unknown_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "count": { "type": "integer" },
+                "amount": { "type": "number" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "note": { "type": "string" }
+            },
+            "required": ["name", "count", "amount", "tags"]
+        });
+        let config = Config {
+            derive_default: true,
+            ..Config::default()
+        };
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+        assert!(code.contains(
+            "impl Default for FooRequest {\n    /// Returns an instance with empty or zero required fields and no optional fields.\n    fn default() -> Self {\n        Self {\n            name: String::new(),\n            count: 0,\n            amount: Decimal::ZERO,\n            tags: Vec::new(),\n            note: None,\n        }\n    }\n}\n"
+        ));
+
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idToken": { "$ref": "#/definitions/IdTokenType" }
+            },
+            "required": ["idToken"]
+        });
+        let info = struct_from_schema(&schema, "BarRequest");
+        let code = generate_struct_code(&info, "request", "Bar", &config).unwrap();
+        assert!(!code.contains("impl Default"));
+    }
+
+    #[test]
+    fn test_builder_reports_missing_required_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "maxLength": 20 },
+                "note": { "type": "string" }
+            },
+            "required": ["idTag"]
+        });
+        let config = Config {
+            builders: true,
+            ..Config::default()
+        };
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains("    pub fn builder() -> FooRequestBuilder {\n"));
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Default)]\npub struct FooRequestBuilder {\n    id_tag: Option<String>,\n    note: Option<String>,\n}\n"
+        ));
+        assert!(code.contains("    pub fn id_tag(mut self, id_tag: String) -> Self {\n"));
+        assert!(code.contains(
+            "            id_tag: self.id_tag.ok_or(\"missing required field `id_tag`\")?,\n            note: self.note,\n"
+        ));
+        assert!(code.contains("        message.validate().map_err(|e| e.to_string())?;\n"));
+    }
+
+    #[test]
+    fn test_multiple_of_generates_custom_validator_per_type() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "step": { "type": "integer", "multipleOf": 5 },
+                "ratio": { "type": "number", "multipleOf": 0.1 },
+                "amount": { "type": "number", "multipleOf": 0.01, "minimum": 0 },
+                "plain": { "type": "integer" }
+            },
+            "required": ["step", "ratio", "amount"]
+        });
+        let config = Config {
+            type_overrides: vec!["ratio=f64".to_string()],
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains(
+            "fn validate_foo_request_step_multiple_of(value: i32) -> Result<(), validator::ValidationError> {\n    if value % 5 != 0 {\n"
+        ));
+        assert!(code.contains("    const EPSILON: f64 = 1e-9;\n    let quotient = value / 0.1;\n"));
+        assert!(code.contains("    if *value % Decimal::new(1, 2) != Decimal::ZERO {\n"));
+        assert!(code.contains(
+            "    #[validate(custom(function = \"validate_foo_request_amount_range\"))]\n    #[validate(custom(function = \"validate_foo_request_amount_multiple_of\"))]\n    pub amount: Decimal,\n"
+        ));
+        assert!(!code.contains("validate_foo_request_plain_multiple_of"));
+    }
+
+    #[test]
+    fn test_string_formats_emit_validator_attributes() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "contact": { "type": "string", "format": "email", "maxLength": 100 },
+                "endpoint": { "type": "string", "format": "uri" }
+            },
+            "required": ["contact", "endpoint"]
+        });
+        let config = Config {
+            format_types: true,
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains(
+            "    #[validate(length(max = 100))]\n    #[validate(email)]\n    pub contact: String,\n"
+        ));
+        assert!(code.contains(
+            "    #[validate(length(max = 512))]\n    #[validate(url)]\n    pub endpoint: String,\n"
+        ));
+    }
+
+    #[test]
+    fn test_nullable_integer_generates_option() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": ["integer", "null"] }
+            },
+            "required": ["count"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(code.contains("    pub count: Option<i32>,\n"));
+        assert!(code.contains("pub fn new() -> Self {"));
+    }
+
+    #[test]
+    fn test_display_impl_round_trips_with_from_str() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "idTag": { "type": "string", "maxLength": 20 } },
+            "required": ["idTag"]
+        });
+        let config = Config {
+            display: true,
+            generate_from_str: true,
+            ..Config::default()
+        };
+        let struct_info = extract_struct_info(&schema, "AuthorizeRequest", &config).unwrap();
+        assert!(struct_info.imports.contains("use std::fmt;"));
+
+        let code = generate_struct_code(&struct_info, "request", "Authorize", &config).unwrap();
+
+        // to_string() 输出的紧凑 JSON 正是 from_str 解析的输入
+        assert!(code.contains(
+            "impl fmt::Display for AuthorizeRequest {\n    /// Writes the message as compact JSON, or the struct name if serialization fails.\n    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {\n        match serde_json::to_string(self) {\n            Ok(json) => f.write_str(&json),\n            Err(_) => f.write_str(\"AuthorizeRequest\"),\n"
+        ));
+        assert!(code.contains("let message: Self = serde_json::from_str(s)?;"));
+
+        let default_info = struct_from_schema(&schema, "AuthorizeRequest");
+        assert!(!default_info.imports.contains("use std::fmt;"));
+        let default_code =
+            generate_struct_code(&default_info, "request", "Authorize", &Config::default())
+                .unwrap();
+        assert!(!default_code.contains("impl fmt::Display"));
+    }
+
+    #[test]
+    fn test_rustfmt_formats_code_and_keeps_invalid_code() {
+        let path = Path::new("authorize.rs");
+
+        // 无法解析的代码原样写入
+        let broken = "pub struct {\n".to_string();
+        assert_eq!(format_with_rustfmt(broken.clone(), path), broken);
+
+        if Command::new("rustfmt").arg("--version").output().is_err() {
+            return;
+        }
+        let messy = "use crate::v2_1::datatypes::{\n    A, \n    B,\n};\npub struct Foo {   pub a: A,pub b: B }\n";
+        assert_eq!(
+            format_with_rustfmt(messy.to_string(), path),
+            "use crate::v2_1::datatypes::{A, B};\npub struct Foo {\n    pub a: A,\n    pub b: B,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_derives_are_configurable_and_checked_against_field_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "maxLength": 20 },
+                "amount": { "type": "number", "maximum": 10 },
+                "kind": { "type": "string", "enum": ["A", "B"] }
+            },
+            "required": ["idTag", "amount"]
+        });
+        let config = Config {
+            derives: ["Debug", "Clone", "PartialEq", "Eq", "Hash", "Validate"]
+                .map(String::from)
+                .to_vec(),
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        // Decimal 支持 Eq/Hash；serde 派生总是补齐
+        assert!(code.contains(
+            "#[derive(Debug, Clone, PartialEq, Eq, Hash, Validate, Deserialize, Serialize)]\n#[serde(rename_all = \"camelCase\")]\npub struct FooRequest {"
+        ));
+
+        let mut float_info = info.clone();
+        float_info.fields[1].rust_type = "f64".to_string();
+        let error = generate_struct_code(&float_info, "request", "Foo", &config).unwrap_err();
+        assert!(matches!(error, GenError::InvalidConfig(_)));
+        assert_eq!(
+            error.to_string(),
+            "FooRequest cannot derive Eq: field `amount` has type f64 which does not implement it"
+        );
+
+        // 必填的内联枚举没有默认值
+        let config = Config {
+            derives: ["Debug", "Default", "Validate"].map(String::from).to_vec(),
+            ..Config::default()
+        };
+        let required_kind = json!({
+            "type": "object",
+            "properties": { "kind": { "type": "string", "enum": ["A", "B"] } },
+            "required": ["kind"]
+        });
+        let info = extract_struct_info(&required_kind, "BarRequest", &config).unwrap();
+        let error = generate_struct_code(&info, "request", "Bar", &config).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("cannot derive Default: field `kind`"));
+    }
+
+    #[test]
+    fn test_dropping_validate_omits_validation_code() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "hex": { "type": "string", "pattern": "^[0-9A-F]+$", "maxLength": 8 },
+                "amount": { "type": "number", "maximum": 10 }
+            },
+            "required": ["hex", "amount"]
+        });
+        let config = Config {
+            derives: ["Debug", "Clone"].map(String::from).to_vec(),
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains("#[derive(Debug, Clone, Deserialize, Serialize)]\n"));
+        assert!(!code.contains("validate"));
+        assert!(!code.contains("REGEX"));
+        assert!(!info.imports.contains("use validator::Validate;"));
+        assert!(!info.imports.contains("use regex::Regex;"));
+    }
+
+    #[test]
+    fn test_one_of_generates_untagged_enum_delegating_validation() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "token": {
+                    "oneOf": [
+                        { "$ref": "#/definitions/IdTokenType" },
+                        { "type": "string", "maxLength": 36 }
+                    ]
+                }
+            },
+            "required": ["token"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(code.contains(
+            "/// Alternative shapes of `FooRequestTokenUnionType`, tried in order.\n#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]\n#[serde(untagged)]\npub enum FooRequestTokenUnionType {\n    IdToken(IdTokenType),\n    String(String),\n}\n"
+        ));
+        assert!(code.contains(
+            "        match self {\n            Self::IdToken(value) => value.validate(),\n            Self::String(value) => {\n                if value.chars().count() > 36 {\n                    let mut errors = validator::ValidationErrors::new();\n                    errors.add(\"String\", validator::ValidationError::new(\"length\"));\n                    return Err(errors);\n                }\n                Ok(())\n            }\n        }\n"
+        ));
+        assert!(
+            code.contains("    #[validate(nested)]\n    pub token: FooRequestTokenUnionType,\n")
+        );
+    }
+
+    #[test]
+    fn test_one_of_with_discriminator_generates_tagged_enum() {
+        let branch = |kind: &str| {
+            json!({
+                "type": "object",
+                "properties": { "eventType": { "type": "string", "const": kind } },
+                "required": ["eventType"]
+            })
+        };
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "event": {
+                    "oneOf": [
+                        { "$ref": "#/definitions/StartedEventType" },
+                        { "$ref": "#/definitions/EndedEventType" }
+                    ]
+                }
+            },
+            "required": ["event"],
+            "definitions": { "StartedEventType": branch("Started"), "EndedEventType": branch("Ended") }
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(!code.contains("#[serde(untagged)]"));
+        assert!(code.contains(
+            "#[serde(tag = \"eventType\")]\npub enum FooRequestEventUnionType {\n    #[serde(rename = \"Started\")]\n    StartedEvent(\n        #[serde(\n            deserialize_with = \"foo_request_event_union_type_started_event\",\n            serialize_with = \"foo_request_event_union_type_without_tag\"\n        )]\n        StartedEventType,\n    ),\n"
+        ));
+        // 分支类型自身的判别字段在反序列化时补回、序列化时去掉
+        assert!(code.contains("    fields.insert(\"eventType\".to_string(), value.into());\n"));
+        assert!(code.contains(
+            "fn foo_request_event_union_type_ended_event<'de, D>(deserializer: D) -> Result<EndedEventType, D::Error>\nwhere\n    D: serde::Deserializer<'de>,\n{\n    foo_request_event_union_type_with_tag(deserializer, \"Ended\")\n}\n"
+        ));
+        assert!(code.contains("        fields.remove(\"eventType\");\n"));
+        assert!(code.contains("            Self::EndedEvent(value) => value.validate(),\n"));
+    }
+
+    #[test]
+    fn test_inline_object_struct_is_emitted_before_parent() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "limits": {
+                    "type": "object",
+                    "properties": { "maxCurrent": { "type": "integer", "maximum": 32 } },
+                    "required": ["maxCurrent"]
+                }
+            },
+            "required": ["limits"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        let nested = code
+            .find("/// FooRequestLimitsType data type.\n#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]\n#[serde(rename_all = \"camelCase\")]\npub struct FooRequestLimitsType {")
+            .unwrap();
+        let parent = code.find("pub struct FooRequest {").unwrap();
+        assert!(nested < parent);
+        assert!(code.contains("    #[validate(range(max = 32))]\n    pub max_current: i32,\n"));
+        assert!(code.contains("    pub fn new(max_current: i32) -> Self {"));
+        assert!(code.contains("    #[validate(nested)]\n    pub limits: FooRequestLimitsType,\n"));
+    }
+
+    #[test]
+    fn test_hash_map_of_types_gets_nested_validation() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "tokens": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/IdTokenType" }
+                },
+                "counters": { "type": "object", "additionalProperties": { "type": "integer" } }
+            },
+            "required": ["tokens", "counters"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(code
+            .contains("    #[validate(nested)]\n    pub tokens: HashMap<String, IdTokenType>,\n"));
+        assert!(code.contains("\n\n    pub counters: HashMap<String, i32>,\n"));
+    }
+
+    #[test]
+    fn test_getters_return_copy_primitives_by_value() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "evseId": { "type": "integer" },
+                "enabled": { "type": "boolean" },
+                "idTag": { "type": "string" },
+                "amount": { "type": "number" }
+            },
+            "required": ["evseId", "idTag", "amount"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(code.contains(
+            "    /// Gets the value of the evse_id field.\n    ///\n    /// # Returns\n    ///\n    /// The evse_id field\n    pub fn get_evse_id(&self) -> i32 {\n        self.evse_id\n    }\n"
+        ));
+        assert!(code.contains(
+            "    pub fn get_enabled(&self) -> Option<bool> {\n        self.enabled\n    }\n"
+        ));
+        assert!(code
+            .contains("    pub fn get_id_tag(&self) -> &String {\n        &self.id_tag\n    }\n"));
+        assert!(code
+            .contains("    pub fn get_amount(&self) -> &Decimal {\n        &self.amount\n    }\n"));
+    }
+
+    #[test]
+    fn test_checked_setters_validate_constrained_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "maxLength": 20 },
+                "evseId": { "type": "integer", "minimum": 1, "maximum": 10 },
+                "amount": { "type": "number", "maximum": 5 },
+                "enabled": { "type": "boolean" }
+            },
+            "required": ["idTag"]
+        });
+        let config = Config {
+            checked_setters: true,
+            ..Config::default()
+        };
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains(
+            "    pub fn set_id_tag(&mut self, id_tag: String) -> Result<&mut Self, validator::ValidationError> {\n        let value = &id_tag;\n        if value.chars().count() > 20 {\n            return Err(validator::ValidationError::new(\"length\"));\n        }\n        self.id_tag = id_tag;\n        Ok(self)\n    }\n"
+        ));
+        assert!(code.contains(
+            "        if let Some(value) = &evse_id {\n            if *value < 1 || *value > 10 {\n                return Err(validator::ValidationError::new(\"range\"));\n            }\n        }\n        self.evse_id = evse_id;\n"
+        ));
+        // Decimal 复用生成的范围检查函数
+        assert!(code.contains(
+            "        if let Some(value) = &amount {\n            validate_foo_request_amount_range(value)?;\n        }\n"
+        ));
+        // 无约束字段保持原有签名
+        assert!(code
+            .contains("    pub fn set_enabled(&mut self, enabled: Option<bool>) -> &mut Self {\n"));
     }
 
-    if !range_constraints.is_empty() {
-        code.push_str(&format!(
-            "    #[validate(range({}))]\n",
-            range_constraints.join(", ")
+    #[test]
+    fn test_try_new_validates_after_constructing() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "maxLength": 20 },
+                "evseId": { "type": "integer" }
+            },
+            "required": ["idTag"]
+        });
+        let config = Config {
+            try_new: true,
+            ..Config::default()
+        };
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains(
+            "    pub fn try_new(id_tag: String) -> Result<Self, validator::ValidationErrors> {\n        let message = Self::new(id_tag);\n        message.validate()?;\n        Ok(message)\n    }\n"
         ));
-    } else if field.name.contains("id") && (field.rust_type == "i32" || field.rust_type == "u32") {
-        // 为 ID 字段添加默认的非负验证
-        code.push_str("    #[validate(range(min = 0))]\n");
+        // 保留原有的 new
+        assert!(code.contains("    pub fn new(id_tag: String) -> Self {\n"));
+
+        let default_code =
+            generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+        assert!(!default_code.contains("try_new"));
     }
-}
 
-/// 生成模块文件
-pub fn generate_mod_file(
-    message_pairs: &[String],
-    standalone_messages: &[String],
-    output_dir: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mod_path = PathBuf::from(output_dir).join("mod.rs");
-    let mut code = String::new();
+    #[test]
+    fn test_deprecated_field_marks_field_and_accessors() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "maxLength": 20 },
+                "legacyId": {
+                    "type": "integer",
+                    "deprecated": true,
+                    "x-deprecation-note": "Use idTag instead"
+                },
+                "oldFlag": { "type": "boolean", "deprecated": true }
+            },
+            "required": ["idTag"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        assert_eq!(
+            info.fields[2].deprecated.as_deref(),
+            Some("oldFlag is deprecated")
+        );
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
 
-    // 收集所有模块名并排序
-    let mut all_modules = Vec::new();
+        let note = "    #[deprecated(note = \"Use idTag instead\")]\n";
+        assert!(code.contains(&format!(
+            "{}    #[serde(skip_serializing_if = \"Option::is_none\")]\n    #[validate(range(min = 0))]\n    pub legacy_id: Option<i32>,\n",
+            note
+        )));
+        assert!(code.contains(&format!("{}    pub fn set_legacy_id(", note)));
+        assert!(code.contains(&format!("{}    pub fn get_legacy_id(", note)));
+        assert!(code.contains(&format!("{}    pub fn with_legacy_id(", note)));
+        assert!(code.contains("    #[deprecated(note = \"oldFlag is deprecated\")]\n"));
+        assert!(!code.contains("#[deprecated(note = \"idTag"));
 
-    // 添加配对消息的模块名
-    for base_name in message_pairs {
-        let module_name = base_name.to_case(Case::Snake);
-        all_modules.push((module_name, base_name.clone(), true)); // true 表示是配对消息
+        let mut pair = MessagePair::new("Foo".to_string());
+        pair.add_request(info);
+        assert!(pair.structs().any(has_deprecations));
     }
 
-    // 添加独立消息的模块名
-    for base_name in standalone_messages {
-        let module_name = base_name.to_case(Case::Snake);
-        all_modules.push((module_name, base_name.clone(), false)); // false 表示是独立消息
-    }
+    #[test]
+    fn test_action_registry_lists_actions_and_message_types() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let schema = json!({ "type": "object", "properties": {} });
+        let heartbeat = pair_from_schemas("Heartbeat", &schema, Some(&schema));
+        let authorize = pair_from_schemas("Authorize", &schema, Some(&schema));
+        let mut stream = MessagePair::new("NotifyPeriodicEventStream".to_string());
+        stream.add_response(struct_from_schema(&schema, "NotifyPeriodicEventStream"));
+        let mut types = MessagePair::new("Common".to_string());
+        types.add_definition(struct_from_schema(&schema, "IdTokenType"));
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            emit_action_registry: true,
+            ..Config::default()
+        };
 
-    // 按模块名排序
-    all_modules.sort_by(|a, b| a.0.cmp(&b.0));
+        let pairs = [&heartbeat, &stream, &types, &authorize];
+        generate_action_registry(&pairs, &config, &Manifest::default()).unwrap();
+        generate_mod_file(&pairs, &config, &Manifest::default()).unwrap();
 
-    // 添加模块声明
-    for (module_name, _, _) in &all_modules {
-        code.push_str(&format!("pub mod {};\n", module_name));
+        let code = fs::read_to_string(output_dir.path().join("actions.rs")).unwrap();
+        assert!(code.contains(
+            "pub enum Action {\n    /// The `Authorize` action.\n    Authorize,\n    /// The `Heartbeat` action.\n    Heartbeat,\n    /// The `NotifyPeriodicEventStream` action.\n    NotifyPeriodicEventStream,\n}\n"
+        ));
+        assert!(code.contains("            Self::Heartbeat => \"Heartbeat\",\n"));
+        assert!(code.contains("            Self::Authorize => Some(\"AuthorizeRequest\"),\n"));
+        assert!(code.contains("            Self::Authorize => Some(\"AuthorizeResponse\"),\n"));
+        // 无后缀的独立消息作为请求，没有响应
+        assert!(code.contains(
+            "            Self::NotifyPeriodicEventStream => Some(\"NotifyPeriodicEventStream\"),\n"
+        ));
+        assert!(code.contains("            Self::NotifyPeriodicEventStream => None,\n"));
+        assert!(!code.contains("Common"));
+        assert!(code.contains("impl FromStr for Action {\n    type Err = UnknownAction;\n"));
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod actions;\n"));
+        assert!(mod_rs.contains("pub use actions::Action;\n"));
     }
 
-    code.push('\n');
+    #[test]
+    fn test_schema_defaults_fill_absent_optional_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string" },
+                "label": { "type": "string", "default": "none" },
+                "retries": { "type": "integer", "minimum": 0, "default": 3 },
+                "limit": { "type": "number", "default": 12.5 },
+                "enabled": { "type": "boolean", "default": true },
+                "levels": { "type": "array", "items": { "type": "integer" }, "default": [1, 2] },
+                "bogus": { "type": "integer", "default": "x" }
+            },
+            "required": ["idTag"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let config = Config {
+            builders: true,
+            derive_default: true,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
 
-    // 添加重新导出，按类型分组
-    let mut paired_exports = Vec::new();
-    let mut standalone_exports = Vec::new();
+        assert!(code.contains(
+            "/// Returns the schema default of `label` in `FooRequest`.\nfn default_foo_request_label() -> Option<String> {\n    Some(\"none\".to_string())\n}\n"
+        ));
+        assert!(
+            code.contains("fn default_foo_request_retries() -> Option<u32> {\n    Some(3)\n}\n")
+        );
+        assert!(code.contains("    Some(Decimal::new(125, 1))\n"));
+        assert!(code.contains("    Some(true)\n"));
+        assert!(code.contains("    Some(vec![1, 2])\n"));
+        assert!(!code.contains("default_foo_request_bogus"));
 
-    for (module_name, base_name, is_paired) in &all_modules {
-        if *is_paired {
-            paired_exports.push((module_name.clone(), base_name.clone()));
-        } else {
-            standalone_exports.push((module_name.clone(), base_name.clone()));
-        }
+        assert!(code.contains(
+            "    #[serde(\n        default = \"default_foo_request_retries\",\n        skip_serializing_if = \"Option::is_none\"\n    )]\n"
+        ));
+        assert!(code.contains("            retries: default_foo_request_retries(),\n"));
+        assert!(code.contains("            bogus: None,\n"));
+        assert!(code
+            .contains("            retries: self.retries.or_else(default_foo_request_retries),\n"));
     }
 
-    // 导出配对消息
-    for (module_name, base_name) in &paired_exports {
-        code.push_str(&format!(
-            "pub use {}::{{{}Request, {}Response}};\n",
-            module_name, base_name, base_name
+    #[test]
+    fn test_unset_and_is_set_helpers_for_optional_fields() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string" },
+                "evseId": { "type": "integer" },
+                "type": { "type": "string" }
+            },
+            "required": ["idTag"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
+
+        assert!(code.contains(
+            "    /// Clears the evse_id field.\n    ///\n    /// # Returns\n    ///\n    /// A mutable reference to self for method chaining.\n    pub fn unset_evse_id(&mut self) -> &mut Self {\n        self.evse_id = None;\n        self\n    }\n"
         ));
+        assert!(code.contains(
+            "    /// Checks whether the evse_id field is set.\n    ///\n    /// # Returns\n    ///\n    /// `true` if the field has a value.\n    pub fn is_evse_id_set(&self) -> bool {\n        self.evse_id.is_some()\n    }\n"
+        ));
+        // 关键字字段去掉后缀下划线
+        assert!(code.contains(
+            "    pub fn unset_type(&mut self) -> &mut Self {\n        self.type_ = None;\n"
+        ));
+        assert!(code.contains("    pub fn is_type_set(&self) -> bool {\n"));
+        assert!(!code.contains("unset_id_tag"));
+        assert!(!code.contains("is_id_tag_set"));
     }
 
-    // 导出独立消息
-    for (module_name, base_name) in &standalone_exports {
-        code.push_str(&format!("pub use {}::{};\n", module_name, base_name));
+    #[test]
+    fn test_fractional_minimum_survives_into_output() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "ratio": { "type": "number", "minimum": 0.5 },
+                "limit": { "type": "number", "minimum": 0.5 },
+                "count": { "type": "integer", "minimum": 0.5, "maximum": 9.5 }
+            },
+            "required": ["ratio", "limit", "count"]
+        });
+        let config = Config {
+            type_overrides: vec!["ratio=f64".to_string()],
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        assert_eq!(info.fields[0].min_value, Some(0.5));
+        // Float overrides of number fields keep the schema bounds
+        assert!(info.fields[0].needs_validation);
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
+
+        assert!(code.contains("    #[validate(range(min = 0.5))]\n    pub ratio: f64,\n"));
+        assert!(code.contains("    if *value < Decimal::new(5, 1) {\n"));
+        // 整数字段的小数边界收紧到范围内的整数
+        assert!(code.contains("    #[validate(range(min = 1, max = 9))]\n    pub count: u32,\n"));
     }
 
-    fs::write(mod_path, code)?;
-    Ok(())
-}
+    #[test]
+    fn test_fractional_bounds_keep_schema_precision() {
+        // 从文本解析才能保留超出 f64 精度的原文
+        let schema: Value = serde_json::from_str(
+            r#"{
+                "type": "object",
+                "properties": {
+                    "price": { "type": "number", "maximum": 99.9 },
+                    "share": { "type": "number", "minimum": 0.30000000000000001 },
+                    "ratio": { "type": "number", "exclusiveMaximum": 99.9 }
+                },
+                "required": ["price", "share", "ratio"]
+            }"#,
+        )
+        .unwrap();
+        let config = Config {
+            type_overrides: vec!["ratio=f64".to_string()],
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        let code = generate_struct_code(&info, "request", "Foo", &config).unwrap();
 
-/// 优化导入语句，将同一模块的导入合并，使用现有项目的多行格式
-fn optimize_imports(imports: &HashSet<String>) -> Vec<String> {
-    let mut grouped_imports: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    let mut other_imports = Vec::new();
+        assert!(code.contains("    if *value > Decimal::new(999, 1) {\n"));
+        assert!(code.contains("    if *value < Decimal::new(30000000000000001, 17) {\n"));
+        assert!(
+            code.contains("    #[validate(range(exclusive_max = 99.9))]\n    pub ratio: f64,\n")
+        );
+    }
 
-    for import in imports {
-        if let Some(parsed) = parse_import(import) {
-            match parsed {
-                ImportType::Crate { module, types } => {
-                    grouped_imports
-                        .entry(module)
-                        .or_insert_with(Vec::new)
-                        .extend(types);
-                }
-                ImportType::Other(import_str) => {
-                    other_imports.push(import_str);
-                }
-            }
-        }
+    #[test]
+    fn test_round_trip_tests_use_constraint_satisfying_values() {
+        let request = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "minLength": 3 },
+                "level": { "type": "integer", "minimum": 2 },
+                "price": { "type": "number", "exclusiveMinimum": 0 },
+                "tags": { "type": "array", "items": { "type": "string" }, "minItems": 2 },
+                "note": { "type": "string" }
+            },
+            "required": ["idTag", "level", "price", "tags"]
+        });
+        let response = json!({
+            "type": "object",
+            "properties": {
+                "status": { "$ref": "#/definitions/GenericStatusEnumType" }
+            },
+            "required": ["status"]
+        });
+        let pair = pair_from_schemas("Foo", &request, Some(&response));
+        let code = generate_round_trip_tests(&pair);
+
+        assert!(code.starts_with("#[cfg(test)]\nmod tests {\n    use super::*;\n"));
+        assert!(code.contains(
+            "        let message = FooRequest::new(\"aaa\".to_string(), 2, Decimal::new(1, 0), vec![String::new(); 2]);\n"
+        ));
+        assert!(code
+            .contains("        let decoded: FooRequest = serde_json::from_str(&json).unwrap();\n"));
+        assert!(code.contains("        assert_eq!(decoded, message);\n"));
+        // 引用共享类型的必填字段无法构造，只留下说明
+        assert!(code.contains(
+            "    // No round-trip test for FooResponse: no sample value for `status` of type GenericStatusEnumType\n"
+        ));
+        assert!(!code.contains("fn foo_response_round_trips"));
     }
 
-    let mut result = Vec::new();
+    #[test]
+    fn test_default_string_max_is_configurable() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "note": { "type": "string" },
+                "code": { "type": "string", "minLength": 2 },
+                "name": { "type": "string", "maxLength": 20 }
+            },
+            "required": ["note", "code", "name"]
+        });
+        let generate = |config: &Config| {
+            let info = extract_struct_info(&schema, "FooRequest", config).unwrap();
+            generate_struct_code(&info, "request", "Foo", config).unwrap()
+        };
 
-    // Add grouped crate imports first
-    for (module, mut types) in grouped_imports {
-        types.sort();
-        types.dedup();
+        let code = generate(&Config {
+            default_string_max: Some(1000),
+            validate_fast: true,
+            ..Config::default()
+        });
+        assert!(code.contains("    #[validate(length(max = 1000))]\n    pub note: String,\n"));
+        assert!(
+            code.contains("    #[validate(length(min = 2, max = 1000))]\n    pub code: String,\n")
+        );
+        assert!(code.contains("    #[validate(length(max = 20))]\n    pub name: String,\n"));
+        assert!(code.contains("value.chars().count() > 1000"));
 
-        if types.len() == 1 {
-            result.push(format!("use crate::{}::{};", module, types[0]));
-        } else if types.len() <= 3 {
-            // 短列表使用单行格式
-            result.push(format!("use crate::{}::{{{}}};", module, types.join(", ")));
-        } else {
-            // 长列表使用多行格式，匹配现有项目风格
-            let mut multi_line_import = format!("use crate::{}::{{\n", module);
-            for (i, type_name) in types.iter().enumerate() {
-                if i == types.len() - 1 {
-                    multi_line_import.push_str(&format!("    {},\n", type_name));
-                } else {
-                    multi_line_import.push_str(&format!("    {}, \n", type_name));
-                }
-            }
-            multi_line_import.push_str("};");
-            result.push(multi_line_import);
-        }
+        // 关闭默认值后只保留 schema 声明的长度约束
+        let code = generate(&Config {
+            default_string_max: None,
+            validate_fast: true,
+            ..Config::default()
+        });
+        assert!(code.contains("    pub note: String,\n"));
+        assert!(!code.contains("#[validate(length(max = 1000))]"));
+        assert!(code.contains("    #[validate(length(min = 2))]\n    pub code: String,\n"));
+        assert!(code.contains("    #[validate(length(max = 20))]\n    pub name: String,\n"));
+        assert!(!code.contains("let value = &self.note;"));
     }
 
-    // Add other imports after crate imports
-    other_imports.sort();
-    result.extend(other_imports);
+    #[test]
+    fn test_vec_fields_get_len_is_empty_and_push_helpers() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "meterValue": { "type": "array", "items": { "type": "integer" } },
+                "type": { "type": "array", "items": { "type": "string" } },
+                "note": { "type": "string" }
+            },
+            "required": ["meterValue"]
+        });
+        let code = generate_struct_code(
+            &struct_from_schema(&schema, "FooRequest"),
+            "request",
+            "Foo",
+            &Config::default(),
+        )
+        .unwrap();
 
-    result
-}
+        assert!(code.contains(
+            "    pub fn meter_value_len(&self) -> usize {\n        self.meter_value.len()\n    }\n"
+        ));
+        assert!(code.contains(
+            "    pub fn meter_value_is_empty(&self) -> bool {\n        self.meter_value.is_empty()\n    }\n"
+        ));
+        assert!(code.contains(
+            "    pub fn push_meter_value(&mut self, item: i32) -> &mut Self {\n        self.meter_value.push(item);\n        self\n    }\n"
+        ));
+        // 可选列表在 push 时创建，关键字后缀不出现在方法名中
+        assert!(code.contains("        self.type_.as_ref().map_or(0, Vec::len)\n"));
+        assert!(code.contains("    pub fn type_is_empty(&self) -> bool {\n        self.type_.as_ref().is_none_or(Vec::is_empty)\n"));
+        assert!(code.contains(
+            "    pub fn push_type(&mut self, item: String) -> &mut Self {\n        self.type_.get_or_insert_with(Vec::new).push(item);\n"
+        ));
+        assert!(!code.contains("fn note_len"));
+    }
 
-#[derive(Debug)]
-enum ImportType {
-    Crate { module: String, types: Vec<String> },
-    Other(String),
-}
+    #[test]
+    fn test_single_file_shares_imports_and_definitions() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            single_file: true,
+            newtype_scalars: true,
+            rustfmt: false,
+            ..Config::default()
+        };
+        let schema = json!({
+            "definitions": {
+                "CiString20Type": { "type": "string", "maxLength": 20 },
+                "CustomDataType": { "type": "object", "properties": {} }
+            },
+            "type": "object",
+            "properties": {
+                "idTag": { "$ref": "#/definitions/CiString20Type" },
+                "customData": { "$ref": "#/definitions/CustomDataType" }
+            },
+            "required": ["idTag"]
+        });
+        let message = |name: &str| {
+            let mut pair = MessagePair::new(name.to_string());
+            let request = format!("{}Request", name);
+            pair.add_request(extract_struct_info(&schema, &request, &config).unwrap());
+            pair
+        };
+        let authorize = message("Authorize");
+        let reset = message("Reset");
+        let mut types = MessagePair::new("CommonTypes".to_string());
+        types.add_definition(struct_from_schema(
+            &json!({ "type": "object", "properties": {} }),
+            "CustomDataType",
+        ));
 
-/// 解析导入语句
-fn parse_import(import: &str) -> Option<ImportType> {
-    if import.starts_with("use crate::") {
-        // 解析 crate 导入
-        let import_part = import.strip_prefix("use crate::")?.strip_suffix(";")?;
+        generate_single_file(
+            &[&authorize, &reset, &types],
+            &SharedTypes::default(),
+            &config,
+            &Manifest::default(),
+        )
+        .unwrap();
+        let code = fs::read_to_string(output_dir.path().join("messages.rs")).unwrap();
 
-        // 找到最后一个 :: 来分离模块和类型
-        if let Some(last_colon_pos) = import_part.rfind("::") {
-            let module = import_part[..last_colon_pos].to_string();
-            let type_name = import_part[last_colon_pos + 2..].to_string();
+        assert!(code.contains("pub struct AuthorizeRequest {"));
+        assert!(code.contains("pub struct ResetRequest {"));
+        assert!(code.contains("pub struct CustomDataType {"));
+        // 两个消息引用的同一新类型只生成一次，本文件定义的类型不再导入
+        assert_eq!(code.matches("pub struct CiString20Type(").count(), 1);
+        assert!(!code.contains("CustomDataType;"));
+        assert_eq!(
+            code.matches("use serde::{Deserialize, Serialize};").count(),
+            1
+        );
+        check_duplicate_definitions(&code).unwrap();
 
-            Some(ImportType::Crate {
-                module,
-                types: vec![type_name],
-            })
-        } else {
-            // 如果没有找到 ::，说明是直接导入模块
-            Some(ImportType::Crate {
-                module: String::new(),
-                types: vec![import_part.to_string()],
-            })
-        }
-    } else {
-        // 其他类型的导入
-        Some(ImportType::Other(import.to_string()))
+        let duplicated = "pub struct Foo {}\n\nimpl Foo {\n    pub fn new() {}\n}\n\nstruct Foo;\n";
+        let error = check_duplicate_definitions(duplicated).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Foo is defined more than once in messages.rs"
+        );
     }
-}
 
-/// 生成结构体的实现块
-fn generate_impl_block(struct_info: &StructInfo) -> Result<String, Box<dyn std::error::Error>> {
-    let mut code = String::new();
+    #[test]
+    fn test_share_helpers_moves_repeated_inline_types_to_shared_rs() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            share_helpers: true,
+            rustfmt: false,
+            ..Config::default()
+        };
+        let request = |field: &str| {
+            json!({
+                "type": "object",
+                "properties": {
+                    field: {
+                        "type": "object",
+                        "properties": {
+                            "phase": { "type": "string", "enum": ["L1", "L2"] }
+                        },
+                        "required": ["phase"]
+                    },
+                    "note": { "type": "string", "maxLength": 8 }
+                },
+                "required": [field]
+            })
+        };
+        let mut pairs = HashMap::new();
+        pairs.insert(
+            "Meter".to_string(),
+            pair_from_schemas("Meter", &request("sample"), None),
+        );
+        pairs.insert(
+            "Reading".to_string(),
+            pair_from_schemas("Reading", &request("value"), None),
+        );
 
-    code.push_str(&format!("impl {} {{\n", struct_info.name));
+        let shared = collect_shared_types(&mut pairs);
+        generate_shared_file(&shared, &config, &Manifest::default()).unwrap();
+        generate_paired_file(&pairs["Reading"], &config, &Manifest::default()).unwrap();
+        let shared_code = fs::read_to_string(output_dir.path().join("shared.rs")).unwrap();
+        let reading = fs::read_to_string(output_dir.path().join("reading.rs")).unwrap();
 
-    // Generate new method
-    code.push_str(&generate_new_method(struct_info)?);
-    code.push('\n');
+        // 结构相同的辅助类型沿用首个消息中的名称，只在 shared.rs 中生成一次
+        assert!(shared_code.contains("pub struct MeterRequestSampleType {"));
+        assert!(shared_code.contains("pub enum MeterRequestSampleTypePhaseEnumType {"));
+        assert!(shared_code.contains("use serde::{Deserialize, Serialize};"));
+        assert!(reading.contains("pub use super::shared::MeterRequestSampleType;"));
+        assert!(reading.contains("    pub value: MeterRequestSampleType,\n"));
+        assert!(!reading.contains("pub struct ReadingRequestValueType"));
+        assert!(!reading.contains("pub enum"));
+        // 嵌套的枚举只在 shared.rs 内部引用，消息文件无需导入
+        assert!(!reading.contains("MeterRequestSampleTypePhaseEnumType"));
+    }
 
-    // Generate setter methods
-    for field in &struct_info.fields {
-        code.push_str(&generate_setter_method(field)?);
-        code.push('\n');
+    #[test]
+    fn test_zero_field_struct_generates_cleanly() {
+        let info = struct_from_schema(
+            &json!({ "type": "object", "properties": {} }),
+            "HeartbeatRequest",
+        );
+        let config = Config {
+            try_new: true,
+            builders: true,
+            derive_default: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&info, "request", "Heartbeat", &config).unwrap();
+
+        assert!(code.contains(
+            "#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]\npub struct HeartbeatRequest {}\n"
+        ));
+        assert!(!code.contains("rename_all"));
+        assert!(code.contains(
+            "    /// Creates a new instance of the struct.\n    ///\n    /// # Returns\n"
+        ));
+        assert!(code.contains("    pub fn new() -> Self {\n        Self {}\n    }\n"));
+        assert!(
+            code.contains("    pub fn try_new() -> Result<Self, validator::ValidationErrors> {")
+        );
+        assert!(code.contains("pub struct HeartbeatRequestBuilder {}\n"));
+        assert!(code.contains("        let message = HeartbeatRequest {};\n"));
+        assert!(code.contains("    fn default() -> Self {\n        Self {}\n    }\n"));
+        // 没有多余的空行或空的文档行
+        assert!(!code.contains("\n\n}"));
+        assert!(!code.contains("{\n}"));
+        assert!(!code.contains("    ///\n    ///\n"));
     }
 
-    // Generate getter methods
-    for field in &struct_info.fields {
-        code.push_str(&generate_getter_method(field)?);
-        code.push('\n');
+    #[test]
+    fn test_deny_unknown_fields_is_opt_in_and_spares_extensible_objects() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "idTag": { "type": "string" },
+                "vendorData": {
+                    "type": "object",
+                    "additionalProperties": true,
+                    "properties": { "vendorId": { "type": "string" } }
+                }
+            },
+            "required": ["idTag"]
+        });
+        let info = struct_from_schema(&schema, "AuthorizeRequest");
+        let custom_data = struct_from_schema(
+            &json!({ "type": "object", "properties": { "vendorId": { "type": "string" } } }),
+            "CustomDataType",
+        );
+        let strict = Config {
+            deny_unknown_fields: true,
+            ..Config::default()
+        };
+
+        let code = generate_struct_code(&info, "request", "Authorize", &strict).unwrap();
+        assert!(code.contains(
+            "#[serde(rename_all = \"camelCase\")]\n#[serde(deny_unknown_fields)]\npub struct AuthorizeRequest {"
+        ));
+        // additionalProperties 允许的扩展对象与 customData 保持宽松
+        assert!(code.contains(
+            "#[serde(rename_all = \"camelCase\")]\npub struct AuthorizeRequestVendorDataType {"
+        ));
+        let code = generate_struct_code(&custom_data, "definition", "Authorize", &strict).unwrap();
+        assert!(!code.contains("deny_unknown_fields"));
+        let code = generate_struct_code(&info, "request", "Authorize", &Config::default()).unwrap();
+        assert!(!code.contains("deny_unknown_fields"));
     }
 
-    // Generate with methods for optional fields
-    for field in &struct_info.fields {
-        if field.is_optional {
-            code.push_str(&generate_with_method(field)?);
-            code.push('\n');
-        }
+    #[test]
+    fn test_round_trip_tests_check_enum_wire_values() {
+        let request = json!({
+            "type": "object",
+            "properties": {
+                "status": { "type": "string", "enum": ["Accepted", "Unknown"] }
+            },
+            "required": ["status"]
+        });
+        let pair = pair_from_schemas("Foo", &request, None);
+        let code = generate_round_trip_tests(&pair);
+
+        assert!(code.contains("    fn foo_request_status_enum_type_parses_wire_values() {\n"));
+        assert!(code.contains(
+            "        for value in [FooRequestStatusEnumType::Accepted, FooRequestStatusEnumType::Unknown] {\n"
+        ));
+        assert!(code.contains(
+            "            assert_eq!(serde_json::to_value(&value).unwrap(), value.as_str());\n"
+        ));
+        // 未知字符串避开 schema 中已有的值
+        assert!(code.contains(
+            "        assert!(\"Unknown_\".parse::<FooRequestStatusEnumType>().is_err());\n"
+        ));
+
+        // 没有可生成的测试时不导入 super::*
+        let response = json!({
+            "type": "object",
+            "properties": { "status": { "$ref": "#/definitions/GenericStatusEnumType" } },
+            "required": ["status"]
+        });
+        let pair = pair_from_schemas("Bar", &response, None);
+        let code = generate_round_trip_tests(&pair);
+        assert!(
+            code.starts_with("#[cfg(test)]\nmod tests {\n    // No round-trip test for BarRequest")
+        );
     }
 
-    code.push_str("}\n");
-    Ok(code)
-}
+    #[test]
+    fn test_const_field_is_fixed_in_new_and_rejects_other_values() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "kind": { "const": "Heartbeat" },
+                "version": { "type": "integer", "const": 2 },
+                "name": { "type": "string", "maxLength": 10 }
+            },
+            "required": ["kind", "version", "name"]
+        });
+        let info = struct_from_schema(&schema, "PingRequest");
+        let config = Config {
+            builders: true,
+            validate_fast: true,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Ping", &config).unwrap();
 
-/// 生成 new 方法
-fn generate_new_method(struct_info: &StructInfo) -> Result<String, Box<dyn std::error::Error>> {
-    let mut code = String::new();
+        // 任何不等于常量的取值都验证失败
+        assert!(code.contains(
+            "fn validate_ping_request_kind_const(value: &str) -> Result<(), validator::ValidationError> {\n    if value != \"Heartbeat\" {\n        return Err(validator::ValidationError::new(\"const\"));\n    }\n    Ok(())\n}\n"
+        ));
+        assert!(code.contains("fn validate_ping_request_version_const(value: i32)"));
+        assert!(code.contains(
+            "    #[validate(custom(function = \"validate_ping_request_kind_const\"))]\n    pub kind: String,\n"
+        ));
+        assert!(code.contains("        if *value != 2 {\n            return Err(\"version\");\n"));
 
-    // Collect required fields
-    let required_fields: Vec<&crate::types::FieldInfo> = struct_info
-        .fields
+        // new 与 builder 直接填入常量，调用方只需提供其余必填字段
+        assert!(code.contains("    pub fn new(name: String) -> Self {\n"));
+        assert!(code
+            .contains("            kind: \"Heartbeat\".to_string(),\n            version: 2,\n"));
+        assert!(code.contains(
+            "            kind: self.kind.unwrap_or_else(|| \"Heartbeat\".to_string()),\n            version: self.version.unwrap_or(2),\n"
+        ));
+    }
+
+    #[test]
+    fn test_imports_of_the_same_path_merge_for_any_crate() {
+        let imports: HashSet<String> = [
+            "use chrono::{DateTime, Utc};",
+            "use chrono::NaiveDate;",
+            "use chrono::DateTime;",
+            "use crate::v2_1::datatypes::{CustomDataType, StatusInfoType};",
+            "use crate::v2_1::datatypes::CustomDataType;",
+            "pub use super::shared::FooType;",
+            "pub use super::shared::BarType;",
+            "use serde::{de::Error, Deserialize};",
+        ]
         .iter()
-        .filter(|field| !field.is_optional)
+        .map(|s| s.to_string())
         .collect();
 
-    // Generate method signature
-    code.push_str("    /// Creates a new instance of the struct.\n");
-    code.push_str("    ///\n");
+        assert_eq!(
+            optimize_imports(&imports),
+            vec![
+                "use crate::v2_1::datatypes::{CustomDataType, StatusInfoType};",
+                "pub use super::shared::{BarType, FooType};",
+                "use chrono::{DateTime, NaiveDate, Utc};",
+                // 嵌套路径的分组无法安全合并，保持原样
+                "use serde::{de::Error, Deserialize};",
+            ]
+        );
+    }
 
-    // Add parameter documentation
-    for field in &required_fields {
-        let param_doc = if let Some(description) = &field.description {
-            description.clone()
-        } else {
-            format!("The {} field", field.name)
+    #[test]
+    fn test_proptest_strategies_follow_field_constraints() {
+        let request = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "minLength": 1, "maxLength": 20 },
+                "connectorId": { "type": "integer", "minimum": 1, "maximum": 10 },
+                "kind": { "const": "Start" },
+                "status": { "type": "string", "enum": ["Accepted", "Rejected"] },
+                "customData": { "$ref": "#/definitions/CustomDataType" }
+            },
+            "required": ["idTag", "connectorId", "kind", "status"]
+        });
+        let response = json!({
+            "type": "object",
+            "properties": {
+                "idTokenInfo": { "$ref": "#/definitions/IdTokenInfoType" }
+            },
+            "required": ["idTokenInfo"]
+        });
+        let pair = pair_from_schemas("Start", &request, Some(&response));
+        let code = generate_proptest_strategies(&pair, &Config::default());
+
+        assert!(code.starts_with("#[cfg(test)]\npub mod strategies {\n    use super::*;\n"));
+        assert!(code.contains(
+            "    pub fn start_request_strategy() -> impl Strategy<Value = StartRequest> {\n"
+        ));
+        assert!(code.contains("proptest::string::string_regex(\"\\\\PC{1,20}\").unwrap(), "));
+        assert!(code.contains("1u32..=10u32, "));
+        assert!(code.contains("Just(\"Start\".to_string()), "));
+        assert!(code.contains(
+            "proptest::sample::select(vec![StartRequestStatusEnumType::Accepted, StartRequestStatusEnumType::Rejected]), "
+        ));
+        // 本文件之外的类型：可选字段取 None，必填字段无法生成
+        assert!(code.contains("Just(None),)"));
+        assert!(code.contains(
+            "    // No strategy for StartResponse: no strategy for `id_token_info` of type IdTokenInfoType\n"
+        ));
+        assert!(code.contains(
+            "        fn start_request_values_validate(message in start_request_strategy()) {\n"
+        ));
+        assert!(!code.contains("start_response_strategy"));
+    }
+
+    #[test]
+    fn test_example_method_respects_constraints_and_is_opt_in() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "minLength": 3, "maxLength": 20 },
+                "connectorId": { "type": "integer", "minimum": 1, "maximum": 9 },
+                "ratio": { "type": "number", "minimum": 0, "maximum": 1 },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "minItems": 2
+                },
+                "kind": { "const": "Start" },
+                "code": { "type": "string", "pattern": "^[0-9]{4}$" },
+                "meter": {
+                    "type": "object",
+                    "properties": { "value": { "type": "integer", "minimum": 10 } },
+                    "required": ["value"]
+                }
+            },
+            "required": ["idTag", "connectorId", "ratio", "tags", "kind", "meter"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
+        let config = Config {
+            examples: true,
+            ..Config::default()
+        };
+
+        let example = generate_example_method(&info, &config);
+        assert!(example.contains("    pub fn example() -> Self {\n        Self {\n"));
+        assert!(example.contains("            id_tag: \"aaa\".to_string(),\n"));
+        assert!(example.contains("            connector_id: 5,\n"));
+        assert!(example.contains("            ratio: Decimal::new(50, 2),\n"));
+        assert!(example.contains("            tags: vec![\"a\".to_string(), \"a\".to_string()],\n"));
+        assert!(example.contains("            kind: \"Start\".to_string(),\n"));
+        // pattern 取匹配的字符串；嵌套结构体委托给其 example()
+        assert!(example.contains("            code: Some(\"0000\".to_string()),\n"));
+        assert!(example.contains("            meter: FooRequestMeterType::example(),\n"));
+        let meter = info
+            .fields
+            .iter()
+            .find_map(|field| field.inline_struct.as_deref())
+            .unwrap();
+        assert!(generate_example_method(meter, &config).contains("            value: 10,\n"));
+
+        // 导入的数据类型委托给其 example()；必填字段无法构造示例值时以注释说明
+        let external = json!({
+            "type": "object",
+            "properties": {
+                "idToken": { "$ref": "#/definitions/IdTokenType" },
+                "status": { "$ref": "#/definitions/ResetEnumType" }
+            },
+            "required": ["idToken", "status"]
+        });
+        let info = struct_from_schema(&external, "BarRequest");
+        let example = generate_example_method(&info, &config);
+        assert_eq!(
+            example,
+            "    // No example() for BarRequest: no sample value for `status` of type ResetEnumType\n"
+        );
+        let info = StructInfo {
+            fields: info.fields[..1].to_vec(),
+            ..info
         };
-        code.push_str(&format!("    /// * `{}` - {}\n", field.name, param_doc));
+        assert!(generate_example_method(&info, &config)
+            .contains("            id_token: IdTokenType::example(),\n"));
+
+        let code = generate_struct_code(meter, "request", "Foo", &Config::default()).unwrap();
+        assert!(!code.contains("pub fn example()"));
     }
 
-    code.push_str("    ///\n");
-    code.push_str("    /// # Returns\n");
-    code.push_str("    ///\n");
-    code.push_str("    /// A new instance of the struct with required fields set and optional fields as None.\n");
-    code.push_str("    pub fn new(");
+    #[test]
+    fn test_ord_derive_rejects_floats_and_json_values() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": { "type": "string", "maxLength": 20 },
+                "amount": { "type": "number", "maximum": 10 },
+                "reading": {
+                    "oneOf": [{ "type": "integer" }, { "type": "string" }]
+                }
+            },
+            "required": ["idTag", "amount"]
+        });
+        let ord = Config {
+            derives: ["Debug", "PartialEq", "Eq", "PartialOrd", "Ord", "Validate"]
+                .map(String::from)
+                .to_vec(),
+            ..Config::default()
+        };
+        let info = extract_struct_info(&schema, "FooRequest", &ord).unwrap();
 
-    // Add parameters
-    for (i, field) in required_fields.iter().enumerate() {
-        if i > 0 {
-            code.push_str(", ");
+        // Decimal 实现了 Ord
+        let code = generate_struct_code(&info, "request", "Foo", &ord).unwrap();
+        assert!(code.contains(
+            "#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Validate, Deserialize, Serialize)]\n"
+        ));
+
+        let partial_ord = Config {
+            derives: ["Debug", "PartialEq", "PartialOrd", "Validate"]
+                .map(String::from)
+                .to_vec(),
+            ..Config::default()
+        };
+        let mut float_info = info.clone();
+        float_info.fields[1].rust_type = "f64".to_string();
+        let error = generate_struct_code(&float_info, "request", "Foo", &ord).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "FooRequest cannot derive Ord: field `amount` has type f64 which does not implement it; derive PartialOrd without Ord instead"
+        );
+        assert!(generate_struct_code(&float_info, "request", "Foo", &partial_ord).is_ok());
+
+        // 联合的变体随结构体派生相同的 trait
+        let mut union_info = info.clone();
+        let union = union_info.fields[2].union.as_mut().unwrap();
+        union.variants[0].1 = "f32".to_string();
+        let error = generate_struct_code(&union_info, "request", "Foo", &ord).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("FooRequest cannot derive Ord: field `reading` has type f32"));
+
+        let mut value_info = info;
+        value_info.fields[1].rust_type = "Value".to_string();
+        for config in [&ord, &partial_ord] {
+            let error = generate_struct_code(&value_info, "request", "Foo", config).unwrap_err();
+            assert!(error
+                .to_string()
+                .ends_with("field `amount` has type Value which does not implement it"));
         }
-        code.push_str(&format!("{}: {}", field.name, field.rust_type));
     }
 
-    code.push_str(") -> Self {\n");
-    code.push_str("        Self {\n");
+    #[test]
+    fn test_schema_titles_lead_doc_comments() {
+        let schema = json!({
+            "title": "Start transaction",
+            "type": "object",
+            "properties": {
+                "idTag": {
+                    "title": "ID tag",
+                    "description": "Identifier\r\nof the token.",
+                    "type": "string"
+                },
+                "note": { "title": "Note", "type": "string" },
+                "count": { "type": "integer" }
+            },
+            "required": ["idTag"]
+        });
+        let info = struct_from_schema(&schema, "StartRequest");
+        assert_eq!(info.fields[0].title.as_deref(), Some("ID tag"));
 
-    // Initialize fields
-    for field in &struct_info.fields {
-        if field.is_optional {
-            code.push_str(&format!("            {}: None,\n", field.name));
-        } else {
-            code.push_str(&format!("            {},\n", field.name));
-        }
+        let code = generate_struct_code(&info, "request", "Start", &Config::default()).unwrap();
+
+        assert!(code.contains(
+            "/// Start transaction\n///\n/// Request body for the Start request.\n#[derive("
+        ));
+        assert!(code.contains(
+            "    /// ID tag\n    ///\n    /// Identifier of the token.\n    #[validate(length(max = 512))]\n"
+        ));
+        assert!(code.contains("    /// Note\n    #[serde(skip_serializing_if"));
+        assert!(code.contains("\n\n    #[serde(skip_serializing_if = \"Option::is_none\")]\n    pub count: Option<i32>,"));
     }
 
-    code.push_str("        }\n");
-    code.push_str("    }\n");
+    #[test]
+    fn test_doc_examples_show_schema_examples() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idTag": {
+                    "description": "Identifier of the token.",
+                    "type": "string",
+                    "examples": ["ABC123", "DEF456"]
+                }
+            },
+            "required": ["idTag"],
+            "examples": [{ "idTag": "ABC123" }]
+        });
+        let info = struct_from_schema(&schema, "StartRequest");
+        assert_eq!(info.examples, vec![json!({ "idTag": "ABC123" })]);
 
-    Ok(code)
-}
+        let code = generate_struct_code(&info, "request", "Start", &Config::default()).unwrap();
+        assert!(!code.contains("# Examples"));
 
-/// 生成 setter 方法
-fn generate_setter_method(
-    field: &crate::types::FieldInfo,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let mut code = String::new();
+        let config = Config {
+            doc_examples: true,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Start", &config).unwrap();
+        assert!(code.contains(
+            "    /// Identifier of the token.\n    ///\n    /// # Examples\n    ///\n    /// ```json\n    /// \"ABC123\"\n    /// \"DEF456\"\n    /// ```\n"
+        ));
+        // 输出目录不在 crate 的 src 之下，只能列出 JSON
+        assert!(code
+            .contains("/// # Examples\n///\n/// ```json\n/// {\"idTag\":\"ABC123\"}\n/// ```\n"));
 
-    let param_doc = if let Some(description) = &field.description {
-        description.clone()
-    } else {
-        format!("The {} field", field.name)
-    };
+        let crate_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            crate_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"ocpp-types\"\n",
+        )
+        .unwrap();
+        let output_dir = crate_dir.path().join("src").join("messages");
+        fs::create_dir_all(&output_dir).unwrap();
+        let config = Config {
+            doc_examples: true,
+            output_dir: output_dir.to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Start", &config).unwrap();
+        assert!(code.contains(concat!(
+            "/// ```no_run\n",
+            "/// # use ocpp_types::messages::start::StartRequest;\n",
+            "/// let json = r#\"{\"idTag\":\"ABC123\"}\"#;\n",
+            "/// let message: StartRequest = serde_json::from_str(json).unwrap();\n",
+            "/// ```\n"
+        )));
+    }
 
-    let field_type = if field.is_optional {
-        format!("Option<{}>", field.rust_type)
-    } else {
-        field.rust_type.clone()
-    };
+    #[test]
+    fn test_try_from_value_impl_is_generated_for_messages_only() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "idTag": { "type": "string", "maxLength": 20 } },
+            "required": ["idTag"]
+        });
+        let info = struct_from_schema(&schema, "StartRequest");
 
-    code.push_str(&format!("    /// Sets the {} field.\n", field.name));
-    code.push_str("    ///\n");
-    code.push_str(&format!("    /// * `{}` - {}\n", field.name, param_doc));
-    code.push_str("    ///\n");
-    code.push_str("    /// # Returns\n");
-    code.push_str("    ///\n");
-    code.push_str("    /// A mutable reference to self for method chaining.\n");
-    code.push_str(&format!(
-        "    pub fn set_{}(&mut self, {}: {}) -> &mut Self {{\n",
-        field.name, field.name, field_type
-    ));
-    code.push_str(&format!("        self.{} = {};\n", field.name, field.name));
-    code.push_str("        self\n");
-    code.push_str("    }\n");
+        let code = generate_struct_code(&info, "request", "Start", &Config::default()).unwrap();
+        assert!(!code.contains("TryFrom"));
 
-    Ok(code)
-}
+        let config = Config {
+            try_from_value: true,
+            ..Config::default()
+        };
+        let code = generate_struct_code(&info, "request", "Start", &config).unwrap();
+        assert!(code.contains(concat!(
+            "impl TryFrom<serde_json::Value> for StartRequest {\n",
+            "    type Error = FromValueError;\n",
+            "\n",
+            "    /// Deserializes the message from a JSON value and validates it.\n",
+            "    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {\n",
+            "        let message: Self = serde_json::from_value(value).map_err(FromValueError::Json)?;\n",
+            "        message\n",
+            "            .validate()\n",
+            "            .map_err(FromValueError::Validation)?;\n",
+            "        Ok(message)\n",
+            "    }\n",
+            "}\n"
+        )));
 
-/// 生成 getter 方法
-fn generate_getter_method(
-    field: &crate::types::FieldInfo,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let mut code = String::new();
+        let code = generate_struct_code(&info, "definition", "Start", &config).unwrap();
+        assert!(!code.contains("TryFrom"));
 
-    let param_doc = if let Some(description) = &field.description {
-        description.clone()
-    } else {
-        format!("The {} field", field.name)
-    };
+        let error = generate_from_value_error();
+        assert!(error.contains("    Json(serde_json::Error),\n"));
+        assert!(error.contains("    Validation(validator::ValidationErrors),\n"));
+    }
 
-    let return_type = if field.is_optional {
-        format!("Option<&{}>", field.rust_type)
-    } else {
-        format!("&{}", field.rust_type)
-    };
+    #[test]
+    fn test_ocpp_j_frame_methods_use_the_base_name_as_action() {
+        let request_schema = json!({
+            "type": "object",
+            "properties": { "idTag": { "type": "string" } },
+            "required": ["idTag"]
+        });
+        let response_schema = json!({
+            "type": "object",
+            "properties": { "status": { "type": "string" } }
+        });
+        let pair = pair_from_schemas("Authorize", &request_schema, Some(&response_schema));
+        let config = Config {
+            ocpp_j: true,
+            ..Config::default()
+        };
 
-    code.push_str(&format!(
-        "    /// Gets a reference to the {} field.\n",
-        field.name
-    ));
-    code.push_str("    ///\n");
-    code.push_str("    /// # Returns\n");
-    code.push_str("    ///\n");
-    code.push_str(&format!("    /// {}\n", param_doc));
-    code.push_str(&format!(
-        "    pub fn get_{}(&self) -> {} {{\n",
-        field.name, return_type
-    ));
+        let request = pair.request.as_ref().unwrap();
+        let code = generate_struct_code(request, "request", &pair.base_name, &config).unwrap();
+        assert!(code.contains(concat!(
+            "    /// The `[2, unique_id, \"Authorize\", payload]` array.\n",
+            "    pub fn to_call(&self, unique_id: &str) -> serde_json::Value {\n",
+            "        serde_json::json!([2, unique_id, \"Authorize\", self])\n",
+            "    }\n"
+        )));
+        assert!(!code.contains("to_call_result"));
 
-    if field.is_optional {
-        code.push_str(&format!("        self.{}.as_ref()\n", field.name));
-    } else {
-        code.push_str(&format!("        &self.{}\n", field.name));
+        let response = pair.response.as_ref().unwrap();
+        let code = generate_struct_code(response, "response", &pair.base_name, &config).unwrap();
+        assert!(code.contains(concat!(
+            "    pub fn to_call_result(&self, unique_id: &str) -> serde_json::Value {\n",
+            "        serde_json::json!([3, unique_id, self])\n",
+            "    }\n"
+        )));
+
+        let code = generate_struct_code(request, "definition", &pair.base_name, &config).unwrap();
+        assert!(!code.contains("to_call"));
+        let code =
+            generate_struct_code(request, "request", &pair.base_name, &Config::default()).unwrap();
+        assert!(!code.contains("to_call"));
     }
 
-    code.push_str("    }\n");
+    #[test]
+    fn test_self_references_get_nested_validation() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "maxLength": 10 },
+                "parent": { "$ref": "#" },
+                "children": { "type": "array", "items": { "$ref": "#" } }
+            },
+            "required": ["name"]
+        });
+        let info = struct_from_schema(&schema, "NodeRequest");
 
-    Ok(code)
-}
+        let code = generate_struct_code(&info, "request", "Node", &Config::default()).unwrap();
 
-/// 生成 with 方法（仅用于可选字段）
-fn generate_with_method(
-    field: &crate::types::FieldInfo,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let mut code = String::new();
+        assert!(
+            code.contains("    #[validate(nested)]\n    pub parent: Option<Box<NodeRequest>>,\n")
+        );
+        assert!(
+            code.contains("    #[validate(nested)]\n    pub children: Option<Vec<NodeRequest>>,\n")
+        );
+    }
 
-    let param_doc = if let Some(description) = &field.description {
-        description.clone()
-    } else {
-        format!("The {} field", field.name)
-    };
+    #[test]
+    fn test_required_nullable_fields_are_serialized_and_required() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": ["integer", "null"] },
+                "note": { "type": ["string", "null"] }
+            },
+            "required": ["count"]
+        });
+        let info = struct_from_schema(&schema, "FooRequest");
 
-    code.push_str(&format!(
-        "    /// Sets the {} field and returns self for builder pattern.\n",
-        field.name
-    ));
-    code.push_str("    ///\n");
-    code.push_str(&format!("    /// * `{}` - {}\n", field.name, param_doc));
-    code.push_str("    ///\n");
-    code.push_str("    /// # Returns\n");
-    code.push_str("    ///\n");
-    code.push_str("    /// Self with the field set.\n");
-    code.push_str(&format!(
-        "    pub fn with_{}(mut self, {}: {}) -> Self {{\n",
-        field.name, field.name, field.rust_type
-    ));
-    code.push_str(&format!(
-        "        self.{} = Some({});\n",
-        field.name, field.name
-    ));
-    code.push_str("        self\n");
-    code.push_str("    }\n");
+        let code = generate_struct_code(&info, "request", "Foo", &Config::default()).unwrap();
 
-    Ok(code)
+        assert!(code.contains(
+            "    #[serde(deserialize_with = \"Option::deserialize\")]\n    pub count: Option<i32>,\n"
+        ));
+        assert!(code.contains(
+            "    #[serde(skip_serializing_if = \"Option::is_none\")]\n    #[validate(length(max = 512))]\n    pub note: Option<String>,\n"
+        ));
+    }
+
+    #[test]
+    fn test_pattern_example_matches_pattern_and_length() {
+        assert_eq!(
+            pattern_example("^[0-9]{4}$", 0, None).as_deref(),
+            Some("0000")
+        );
+        // 未锚定的 pattern 与长度下限：重复逐次增加直到满足长度
+        assert_eq!(
+            pattern_example("[A-F]+", 3, Some(5)).as_deref(),
+            Some("AAA")
+        );
+        assert_eq!(
+            pattern_example("^(urn|URN):[a-z]{2,}(\\.[a-z]+)?$", 0, Some(20)).as_deref(),
+            Some("urn:aa")
+        );
+        // 无法满足长度限制时返回 None
+        assert_eq!(pattern_example("^[0-9]{4}$", 0, Some(3)), None);
+    }
 }
@@ -0,0 +1,53 @@
+pub mod config;
+pub mod error;
+pub mod generator;
+pub mod manifest;
+pub mod parser;
+pub mod processor;
+pub mod shared;
+pub mod types;
+
+use serde_json::Value;
+
+use config::Config;
+use error::GenError;
+use generator::generate_struct_code;
+use parser::{extract_struct_info, parse_message_type};
+
+/// 从内存中的 schema 值直接生成单个结构体的代码（便于 build script 和测试使用）
+pub fn generate_struct_from_value(
+    schema: &Value,
+    name: &str,
+    message_type: &str,
+) -> Result<String, GenError> {
+    let config = Config::default();
+    let struct_info = extract_struct_info(schema, name, &config)?;
+    let (action, _) = parse_message_type(name);
+    generate_struct_code(&struct_info, message_type, &action, &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_generate_struct_from_inline_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "idToken": { "type": "string", "maxLength": 36 },
+                "evseId": { "type": "integer", "minimum": 1 }
+            },
+            "required": ["idToken"]
+        });
+
+        let code = generate_struct_from_value(&schema, "AuthorizeRequest", "request").unwrap();
+
+        assert!(code.contains("/// Request body for the Authorize request.\n"));
+        assert!(code.contains("pub struct AuthorizeRequest {\n"));
+        assert!(code.contains("    #[validate(length(max = 36))]\n    pub id_token: String,\n"));
+        assert!(code.contains("    pub evse_id: Option<u32>,\n"));
+        assert!(code.find("pub id_token").unwrap() < code.find("pub evse_id").unwrap());
+    }
+}
@@ -1,22 +1,42 @@
-mod config;
-mod generator;
-mod parser;
-mod processor;
-mod types;
+use std::io::Write;
 
-use config::Config;
-use processor::SchemaProcessor;
+use log::Level;
+use ocpp_messages_gen_tool::config::Config;
+use ocpp_messages_gen_tool::error::GenError;
+use ocpp_messages_gen_tool::processor::SchemaProcessor;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() {
     let config = Config::from_args();
+    init_logger(&config);
 
+    if let Err(e) = run(config) {
+        log::error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+/// 初始化 env_logger：级别由 --quiet/--verbose 决定，RUST_LOG 可按模块进一步调整
+///
+/// 警告与错误保留原有的 `Warning:`/`Error:` 前缀，其余日志只输出消息本身。
+fn init_logger(config: &Config) {
+    env_logger::Builder::new()
+        .filter_level(config.log_level())
+        .parse_default_env()
+        .format(|buf, record| match record.level() {
+            Level::Error => writeln!(buf, "Error: {}", record.args()),
+            Level::Warn => writeln!(buf, "Warning: {}", record.args()),
+            _ => writeln!(buf, "{}", record.args()),
+        })
+        .init();
+}
+
+fn run(config: Config) -> Result<(), GenError> {
     let processor = SchemaProcessor::new(config.clone());
 
-    // Print statistics before processing if enabled
+    // Log statistics before processing if enabled
     if config.show_statistics {
         let stats = processor.get_stats()?;
         stats.print();
-        println!();
     }
 
     // Process all schemas
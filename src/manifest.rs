@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use similar::TextDiff;
+
+use crate::error::GenError;
+
+/// 生成清单在输出目录中的文件名
+pub const MANIFEST_FILE: &str = ".gen_manifest.json";
+
+/// 生成清单：记录每个输出文件内容的哈希，内容未变化的文件不再重写
+///
+/// 跳过写入可以保留文件的修改时间，避免下游 crate 无谓的增量编译。
+/// 只有磁盘上的文件与新输出完全一致时才跳过，手工修改或损坏的文件总会被重新生成。
+/// 预演模式（--dry-run）下不写任何文件，只记录与磁盘内容不同的文件及其差异。
+#[derive(Debug, Default)]
+pub struct Manifest {
+    previous: BTreeMap<String, String>,
+    current: Mutex<BTreeMap<String, String>>,
+    dry_run: bool,
+    changes: Mutex<Vec<(PathBuf, String)>>,
+}
+
+impl Manifest {
+    /// 读取输出目录中的清单，不存在或无法解析时视为空清单
+    pub fn load(output_dir: &Path) -> Self {
+        let path = output_dir.join(MANIFEST_FILE);
+        let previous: BTreeMap<String, String> = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("ignoring unreadable manifest {}: {}", path.display(), e);
+                tracing::warn!(path = %path.display(), error = %e, "ignoring unreadable manifest");
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+
+        Self {
+            current: Mutex::new(previous.clone()),
+            previous,
+            ..Self::default()
+        }
+    }
+
+    /// 预演模式的清单：与磁盘上的现有文件比较，而不是与记录的哈希比较
+    pub fn dry_run() -> Self {
+        Self {
+            dry_run: true,
+            ..Self::default()
+        }
+    }
+
+    /// 写入输出文件；清单中的哈希与内容一致且磁盘上的文件未被改动时跳过，返回是否写入
+    pub fn write(&self, path: &Path, content: &str) -> Result<bool, GenError> {
+        let key = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if self.dry_run {
+            return Ok(self.record_change(path, content));
+        }
+        let hash = content_hash(content);
+
+        let unchanged = self.previous.get(&key) == Some(&hash)
+            && fs::read_to_string(path).is_ok_and(|existing| existing == content);
+        if !unchanged {
+            fs::write(path, content).map_err(GenError::io(path))?;
+        }
+        self.current
+            .lock()
+            .expect("manifest lock poisoned")
+            .insert(key, hash);
+        Ok(!unchanged)
+    }
+
+    /// 预演模式下记录内容将要变化的文件及其统一差异，返回是否有变化
+    fn record_change(&self, path: &Path, content: &str) -> bool {
+        let existing = fs::read_to_string(path).ok();
+        if existing.as_deref() == Some(content) {
+            return false;
+        }
+
+        let target = path.display().to_string();
+        let diff = TextDiff::from_lines(existing.as_deref().unwrap_or(""), content)
+            .unified_diff()
+            .header(
+                if existing.is_some() {
+                    &target
+                } else {
+                    "/dev/null"
+                },
+                &target,
+            )
+            .to_string();
+        self.changes
+            .lock()
+            .expect("manifest lock poisoned")
+            .push((path.to_path_buf(), diff));
+        true
+    }
+
+    /// 预演模式下将会变化的文件及其差异，按路径排序
+    pub fn changes(&self) -> Vec<(PathBuf, String)> {
+        let mut changes = self.changes.lock().expect("manifest lock poisoned").clone();
+        changes.sort();
+        changes
+    }
+
+    /// 清单有变化时写回输出目录（预演模式下不写入）
+    pub fn save(&self, output_dir: &Path) -> Result<(), GenError> {
+        if self.dry_run {
+            return Ok(());
+        }
+        let path = output_dir.join(MANIFEST_FILE);
+        let current = self.current.lock().expect("manifest lock poisoned");
+        if *current == self.previous && path.exists() {
+            return Ok(());
+        }
+
+        let mut content =
+            serde_json::to_string_pretty(&*current).expect("string map always serializes");
+        content.push('\n');
+        fs::write(&path, content).map_err(GenError::io(&path))
+    }
+}
+
+/// 64 位 FNV-1a 内容哈希，与平台和编译器版本无关
+fn content_hash(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
@@ -1,10 +1,24 @@
 use convert_case::{Case, Casing};
-use serde_json::Value;
-use std::collections::HashSet;
+use regex::Regex;
+use serde_json::{Number, Value};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
+use std::rc::Rc;
 
-use crate::types::{FieldInfo, StructInfo};
+use crate::config::{Config, TypeMapping};
+use crate::error::GenError;
+use crate::types::{FieldInfo, InlineEnumInfo, NewtypeInfo, StructInfo, UnionInfo};
+
+/// 规范化文件名，去除匹配的后缀（如 `_v1p2` 版本后缀）
+pub fn normalize_schema_name(filename: &str, strip_suffix: Option<&Regex>) -> String {
+    match strip_suffix {
+        Some(pattern) => pattern.replace_all(filename, "").into_owned(),
+        None => filename.to_string(),
+    }
+}
 
 /// 解析消息类型，返回基础名称和是否为请求
 pub fn parse_message_type(filename: &str) -> (String, bool) {
@@ -20,28 +34,194 @@ pub fn parse_message_type(filename: &str) -> (String, bool) {
     }
 }
 
+/// 判断文件扩展名是否为支持的 schema 格式（JSON 或 YAML）
+pub fn is_schema_extension(extension: &str) -> bool {
+    matches!(extension, "json" | "yaml" | "yml")
+}
+
+/// 根据文件扩展名解析 schema 内容（JSON 或 YAML），保持对象键的源顺序
+///
+/// `file` 只用于确定格式和错误信息，不会被读取。
+pub fn parse_schema_content(content: &str, file: &Path) -> Result<Value, GenError> {
+    match file.extension().and_then(|e| e.to_str()) {
+        Some("yaml" | "yml") => {
+            serde_yaml::from_str(content).map_err(|source| GenError::YamlParse {
+                file: file.to_path_buf(),
+                source,
+            })
+        }
+        _ => serde_json::from_str(content).map_err(|source| GenError::JsonParse {
+            file: file.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// 从 OpenAPI 文档的 components.schemas 中提取所有结构体信息
+pub fn extract_struct_infos_from_openapi(
+    document_path: &Path,
+    config: &Config,
+) -> Result<Vec<StructInfo>, GenError> {
+    let content = fs::read_to_string(document_path).map_err(GenError::io(document_path))?;
+    let document = parse_schema_content(&content, document_path)?;
+
+    let schemas = document
+        .pointer("/components/schemas")
+        .and_then(|s| s.as_object())
+        .ok_or_else(|| {
+            GenError::unsupported("OpenAPI document has no components.schemas")
+                .in_file(document_path)
+        })?;
+
+    let mut struct_infos = Vec::new();
+    for (name, schema) in schemas {
+        struct_infos
+            .push(extract_struct_info(schema, name, config).map_err(|e| e.in_file(document_path))?);
+    }
+
+    Ok(struct_infos)
+}
+
+/// 读取并解析 schema 文件
+pub fn load_schema_file(schema_path: &Path) -> Result<Value, GenError> {
+    let content = fs::read_to_string(schema_path).map_err(GenError::io(schema_path))?;
+    parse_schema_content(&content, schema_path)
+}
+
 /// 从文件中提取结构体信息
 pub fn extract_struct_info_from_file(
     schema_path: &Path,
     struct_name: &str,
-) -> Result<StructInfo, Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(schema_path)?;
-    let schema: Value = serde_json::from_str(&content)?;
-    extract_struct_info_with_content(&schema, struct_name, &content)
+    config: &Config,
+) -> Result<StructInfo, GenError> {
+    let schema = load_schema_file(schema_path)?;
+    extract_struct_info(&schema, struct_name, config).map_err(|e| e.in_file(schema_path))
+}
+
+/// 判断 schema 是否为仅包含 definitions 的类型库（没有顶层 properties）
+pub fn is_definitions_library(schema: &Value) -> bool {
+    schema.get("properties").is_none()
+        && schema
+            .get("definitions")
+            .and_then(|d| d.as_object())
+            .is_some_and(|d| !d.is_empty())
+}
+
+/// 为 definitions 库中的每个对象定义提取结构体信息
+///
+/// 同一文件内的定义（含字符串 enum 定义）互相引用时不生成导入，因为它们输出到同一个模块。
+pub fn extract_struct_infos_from_definitions(
+    schema: &Value,
+    config: &Config,
+) -> Result<Vec<StructInfo>, GenError> {
+    let definitions = schema
+        .get("definitions")
+        .and_then(|d| d.as_object())
+        .ok_or_else(|| GenError::unsupported("schema has no definitions"))?;
+
+    let mut struct_infos = Vec::new();
+    for (name, definition) in definitions {
+        if definition.get("properties").is_none() {
+            // 字符串 enum 定义由 extract_enum_infos_from_definitions 生成
+            if string_enum_info(definition, name.clone()).is_none() {
+                log::warn!(
+                    "skipping definition '{}' (only object and string enum definitions are generated)",
+                    name
+                );
+                tracing::warn!(definition = %name, "skipping unsupported definition");
+            }
+            continue;
+        }
+        struct_infos.push(extract_struct_info(definition, name, config)?);
+    }
+
+    let local_names: Vec<String> = struct_infos
+        .iter()
+        .map(|s| s.name.clone())
+        .chain(
+            extract_enum_infos_from_definitions(schema)
+                .into_iter()
+                .map(|e| e.name),
+        )
+        .collect();
+    for struct_info in &mut struct_infos {
+        struct_info.imports.retain(|import| {
+            !local_names
+                .iter()
+                .any(|name| import.ends_with(&format!("::{};", name)))
+        });
+    }
+
+    Ok(struct_infos)
+}
+
+/// 为 definitions 库中的每个字符串 enum 定义提取枚举信息，与对象定义输出到同一模块
+pub fn extract_enum_infos_from_definitions(schema: &Value) -> Vec<InlineEnumInfo> {
+    schema
+        .get("definitions")
+        .and_then(|d| d.as_object())
+        .into_iter()
+        .flatten()
+        .filter(|(_, definition)| definition.get("properties").is_none())
+        .filter_map(|(name, definition)| string_enum_info(definition, name.clone()))
+        .collect()
+}
+
+/// 从 JSON schema 中提取结构体信息
+///
+/// 字段按 `properties` 在源文件中的顺序输出（serde_json 启用了 preserve_order）。
+pub fn extract_struct_info(
+    schema: &Value,
+    struct_name: &str,
+    config: &Config,
+) -> Result<StructInfo, GenError> {
+    let mut struct_info = extract_object_info(schema, schema, struct_name, config)?;
+    let mut used = HashSet::from([struct_name.to_string()]);
+    let local_names: HashSet<&str> = if config.local_definitions {
+        schema
+            .get("definitions")
+            .and_then(|d| d.as_object())
+            .map(|d| d.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    } else {
+        HashSet::new()
+    };
+    dedup_inline_struct_names(&mut struct_info, &mut used, &local_names);
+    Ok(struct_info)
 }
 
-/// 从 JSON schema 中提取结构体信息（带原始内容以保持字段顺序）
-pub fn extract_struct_info_with_content(
+/// 提取对象 schema 的结构体信息，`root_schema` 为 definitions 所在的顶层 schema
+fn extract_object_info(
     schema: &Value,
+    root_schema: &Value,
     struct_name: &str,
-    content: &str,
-) -> Result<StructInfo, Box<dyn std::error::Error>> {
+    config: &Config,
+) -> Result<StructInfo, GenError> {
     let mut imports = HashSet::new();
     let mut fields = Vec::new();
 
     // Add common imports
     imports.insert("use serde::{Deserialize, Serialize};".to_string());
-    imports.insert("use validator::Validate;".to_string());
+    // 新类型包装手写 Validate 实现，同样需要该导入
+    if config.has_derive("Validate") || config.newtype_scalars {
+        imports.insert("use validator::Validate;".to_string());
+    }
+    if config.display {
+        imports.insert("use std::fmt;".to_string());
+    }
+
+    // 检查 required 中列出但 properties 中不存在的字段
+    for missing in missing_required_properties(schema) {
+        let message = format!(
+            "{} lists required property '{}' which is not defined in properties",
+            struct_name, missing
+        );
+        if config.strict {
+            return Err(GenError::unsupported(message));
+        }
+        log::warn!("{}", message);
+        tracing::warn!(struct_name, property = %missing, "required property not defined in properties");
+    }
 
     if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
         let required_fields: HashSet<String> = schema
@@ -55,46 +235,149 @@ pub fn extract_struct_info_with_content(
             })
             .unwrap_or_default();
 
-        // 保持字段顺序：从原始JSON字符串中解析字段顺序
-        let field_order = extract_field_order_from_content(content)?;
-
-        // 如果字段顺序提取失败，回退到原有方式
-        if field_order.is_empty() {
-            // 使用原有的无序遍历方式作为回退
-            for (field_name, field_schema) in properties {
-                let field_info = extract_field_info(
-                    field_name,
-                    field_schema,
-                    &required_fields,
-                    &mut imports,
-                    schema,
-                )?;
-                fields.push(field_info);
-            }
-        } else {
-            // 按照JSON中的顺序处理字段
-            for field_name in field_order {
-                if let Some(field_schema) = properties.get(&field_name) {
-                    let field_info = extract_field_info(
-                        &field_name,
-                        field_schema,
-                        &required_fields,
-                        &mut imports,
-                        schema,
-                    )?;
-                    fields.push(field_info);
-                }
-            }
+        for (field_name, field_schema) in properties {
+            let field_info = extract_field_info(
+                field_name,
+                field_schema,
+                &required_fields,
+                &mut imports,
+                root_schema,
+                struct_name,
+                config,
+            )?;
+            fields.push(field_info);
         }
     }
 
+    // 消息级别的弃用标记
+    let deprecated = if schema.get("deprecated").and_then(|d| d.as_bool()) == Some(true) {
+        let note = schema
+            .get("x-deprecation-note")
+            .and_then(|n| n.as_str())
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| format!("{} is deprecated", struct_name));
+        Some(note)
+    } else {
+        None
+    };
+
+    // 显式允许额外属性的对象；OCPP 的 CustomDataType 用于厂商扩展，同样必须保持宽松
+    let allows_additional_properties = struct_name == "CustomDataType"
+        || schema
+            .get("additionalProperties")
+            .is_some_and(|additional| additional != &Value::Bool(false));
+
+    // 结构体级别的命名规则覆盖
+    let rename_all = match schema.get("x-rename-all").and_then(|r| r.as_str()) {
+        Some(rule) if SERDE_RENAME_RULES.contains(&rule) => Some(rule.to_string()),
+        Some(rule) => {
+            return Err(GenError::unsupported(format!(
+                "{} has unsupported x-rename-all value '{}'",
+                struct_name, rule
+            )))
+        }
+        None => None,
+    };
+
     Ok(StructInfo {
         name: struct_name.to_string(),
+        title: doc_text(schema, "title"),
+        examples: schema_examples(schema),
         fields,
         imports,
+        deprecated,
+        rename_all,
+        allows_additional_properties,
+        emitted_elsewhere: false,
     })
 }
 
+/// 读取用作文档注释的文本（description、title），换行合并为空格
+fn doc_text(schema: &Value, key: &str) -> Option<String> {
+    schema
+        .get(key)
+        .and_then(|d| d.as_str())
+        .map(|s| s.replace('\r', "").replace('\n', " ").trim().to_string())
+}
+
+/// 读取 schema 的 examples 数组
+fn schema_examples(schema: &Value) -> Vec<Value> {
+    schema
+        .get("examples")
+        .and_then(|e| e.as_array())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// 为重名的内联结构体追加序号（保留 `Type` 后缀），并同步更新引用它的字段类型
+///
+/// 重复引用的本地定义（`local_names`）是同一个类型，不改名，只在首次出现处生成。
+fn dedup_inline_struct_names(
+    struct_info: &mut StructInfo,
+    used: &mut HashSet<String>,
+    local_names: &HashSet<&str>,
+) {
+    for field in &mut struct_info.fields {
+        if let Some(inline_enum) = field.inline_enum.as_mut() {
+            if local_names.contains(inline_enum.name.as_str())
+                && !used.insert(inline_enum.name.clone())
+            {
+                inline_enum.emitted_elsewhere = true;
+            }
+        }
+        let Some(inline) = field.inline_struct.as_mut() else {
+            continue;
+        };
+        if local_names.contains(inline.name.as_str()) && used.contains(&inline.name) {
+            inline.emitted_elsewhere = true;
+            continue;
+        }
+        if used.contains(&inline.name) {
+            let stem = inline.name.strip_suffix("Type").unwrap_or(&inline.name);
+            let renamed = (2..)
+                .map(|n| format!("{}{}Type", stem, n))
+                .find(|name| !used.contains(name))
+                .unwrap();
+            field.rust_type = field.rust_type.replace(&inline.name, &renamed);
+            inline.name = renamed;
+        }
+        used.insert(inline.name.clone());
+        dedup_inline_struct_names(inline, used, local_names);
+    }
+}
+
+/// serde 支持的 rename_all 命名规则
+pub const SERDE_RENAME_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// 找出 required 中列出但 properties 中未定义的字段名
+fn missing_required_properties(schema: &Value) -> Vec<String> {
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+
+    schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .filter(|name| !properties.is_some_and(|p| p.contains_key(*name)))
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 保持为 String 的时间字段的默认最大长度
+const DATETIME_STRING_MAX_LENGTH: u32 = 64;
+
 /// 提取字段信息
 fn extract_field_info(
     field_name: &str,
@@ -102,8 +385,17 @@ fn extract_field_info(
     required_fields: &HashSet<String>,
     imports: &mut HashSet<String>,
     root_schema: &Value,
-) -> Result<FieldInfo, Box<dyn std::error::Error>> {
-    let is_optional = !required_fields.contains(field_name);
+    struct_name: &str,
+    config: &Config,
+) -> Result<FieldInfo, GenError> {
+    let field_schema = inline_local_scalar(field_schema, root_schema, config);
+    let field_schema = const_typed(&field_schema);
+
+    // 可为 null 的字段（如 "type": ["integer", "null"]）按可选字段处理
+    let (field_schema, nullable) = split_nullable(&field_schema);
+    let field_schema = field_schema.as_ref();
+    let is_optional = !required_fields.contains(field_name) || nullable;
+    let required_nullable = required_fields.contains(field_name) && nullable;
 
     // 处理 Rust 关键字，特别是 "type"
     let rust_field_name = if field_name == "type" {
@@ -112,28 +404,161 @@ fn extract_field_info(
         field_name.to_case(Case::Snake)
     };
 
-    let (rust_type, needs_validation) = determine_rust_type(field_schema, imports, root_schema)?;
+    // false 属性不可能出现合法值，生成任何类型都会误导
+    if field_schema.as_bool() == Some(false) {
+        return Err(GenError::unsupported(format!(
+            "{}.{} has boolean schema `false`, so no value is valid",
+            struct_name, field_name
+        )));
+    }
 
-    let description = field_schema
-        .get("description")
-        .and_then(|d| d.as_str())
-        .map(|s| s.replace('\r', "").replace('\n', " ").trim().to_string());
+    let is_date_time = field_schema.get("type").and_then(|t| t.as_str()) == Some("string")
+        && field_schema.get("format").and_then(|f| f.as_str()) == Some("date-time");
+
+    // 非 RFC3339 的厂商时间字段保持为 String，避免反序列化失败
+    let datetime_as_string =
+        is_date_time && config.keeps_datetime_as_string(struct_name, field_name);
+
+    // 受约束的标量定义可生成新类型包装，使约束随类型传播
+    let newtype = if config.newtype_scalars {
+        scalar_newtype(field_schema, root_schema, imports)
+    } else {
+        None
+    };
+
+    // 内联的字符串 enum 生成专用枚举类型，避免 String 接受任意值
+    let inline_enum =
+        if config.type_override(struct_name, field_name).is_none() && newtype.is_none() {
+            inline_enum_info(field_schema, root_schema, struct_name, field_name, config)
+        } else {
+            None
+        };
+
+    // oneOf/anyOf 生成 untagged 枚举，按顺序尝试每个候选类型
+    let union = if config.type_override(struct_name, field_name).is_none() {
+        union_info(
+            field_schema,
+            imports,
+            root_schema,
+            struct_name,
+            field_name,
+            config,
+        )?
+    } else {
+        None
+    };
+
+    // 带 properties 的内联 object 提升为独立的嵌套结构体
+    let inline_struct =
+        if config.type_override(struct_name, field_name).is_none() && union.is_none() {
+            inline_struct_info(
+                field_schema,
+                imports,
+                root_schema,
+                struct_name,
+                field_name,
+                config,
+            )?
+        } else {
+            None
+        };
+
+    let (mut rust_type, needs_validation) =
+        if let Some(override_type) = config.type_override(struct_name, field_name) {
+            // 覆盖为浮点数的 number 字段仍按 schema 边界验证，其他覆盖类型的约束未必适用
+            let float = matches!(override_type, "f32" | "f64")
+                && field_schema.get("type").and_then(|t| t.as_str()) == Some("number");
+            (override_type.to_string(), float)
+        } else if let Some(inline) = &inline_struct {
+            if field_schema.get("items").is_some() {
+                (format!("Vec<{}>", inline.name), true)
+            } else {
+                (inline.name.clone(), true)
+            }
+        } else if let Some(union) = &union {
+            if field_schema.get("items").is_some() {
+                (format!("Vec<{}>", union.name), true)
+            } else {
+                (union.name.clone(), true)
+            }
+        } else if let Some(newtype) = &newtype {
+            if field_schema.get("items").is_some() {
+                (format!("Vec<{}>", newtype.name), true)
+            } else {
+                (newtype.name.clone(), true)
+            }
+        } else if let Some(inline_enum) = &inline_enum {
+            if field_schema.get("items").is_some() {
+                (format!("Vec<{}>", inline_enum.name), true)
+            } else {
+                (inline_enum.name.clone(), false)
+            }
+        } else if datetime_as_string {
+            ("String".to_string(), true)
+        } else {
+            determine_rust_type(field_schema, imports, root_schema, struct_name, config)?
+        };
+
+    // 一致性构建中不允许无类型的 Value 字段
+    if config.no_value_fallback && is_value_type(&rust_type) {
+        return Err(GenError::unsupported(format!(
+            "{}.{} would fall back to serde_json::Value ({}); supply --type-override {}.{}=Type",
+            struct_name,
+            field_name,
+            value_fallback_reason(field_schema),
+            struct_name,
+            field_name
+        )));
+    }
+
+    let unique_items = field_schema
+        .get("uniqueItems")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    // 枚举集合：uniqueItems 的枚举数组使用 HashSet 表示
+    if config.enum_sets && unique_items {
+        if let Some(item_type) = rust_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            if item_type.ends_with("EnumType") {
+                imports.insert("use std::collections::HashSet;".to_string());
+                rust_type = format!("HashSet<{}>", item_type);
+            }
+        }
+    }
+
+    let description = doc_text(field_schema, "description");
+    let title = doc_text(field_schema, "title");
+    let examples = schema_examples(field_schema);
 
     // 提取长度限制
     let max_length = field_schema
         .get("maxLength")
         .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
+        .map(|v| v as u32)
+        .or(datetime_as_string.then_some(DATETIME_STRING_MAX_LENGTH));
 
     let min_length = field_schema
         .get("minLength")
         .and_then(|v| v.as_u64())
         .map(|v| v as u32);
 
-    // 提取数值范围（支持浮点数）
-    let min_value = field_schema.get("minimum").and_then(|v| v.as_f64());
+    // 提取数值范围（支持浮点数及排他边界），同时保留 schema 中的原始写法
+    let (min_bound, exclusive_min) =
+        numeric_bound(field_schema, "minimum", "exclusiveMinimum", |x, i| x >= i);
+    let (min_value, min_literal) = bound_value(min_bound);
+
+    let (max_bound, exclusive_max) =
+        numeric_bound(field_schema, "maximum", "exclusiveMaximum", |x, i| x <= i);
+    let (max_value, max_literal) = bound_value(max_bound);
 
-    let max_value = field_schema.get("maximum").and_then(|v| v.as_f64());
+    // 数值的 multipleOf 约束
+    let multiple_of = field_schema
+        .get("multipleOf")
+        .and_then(|v| v.as_f64())
+        .filter(|m| *m > 0.0);
 
     // 提取数组项目数量限制
     let min_items = field_schema
@@ -146,45 +571,602 @@ fn extract_field_info(
         .and_then(|v| v.as_u64())
         .map(|v| v as u32);
 
+    // 字符串的正则约束，生成代码需要 Regex 与惰性初始化
+    let pattern = field_schema
+        .get("pattern")
+        .and_then(|p| p.as_str())
+        .filter(|_| rust_type == "String" && needs_validation && config.has_derive("Validate"))
+        .filter(|p| match Regex::new(p) {
+            Ok(_) => true,
+            Err(e) => {
+                log::warn!(
+                    "{}.{} pattern is not a valid regex, skipping: {}",
+                    struct_name,
+                    field_name,
+                    e
+                );
+                tracing::warn!(struct_name, field_name, "invalid pattern skipped");
+                false
+            }
+        })
+        .map(|p| p.to_string());
+    if pattern.is_some() {
+        imports.insert("use once_cell::sync::Lazy;".to_string());
+        imports.insert("use regex::Regex;".to_string());
+    }
+
+    // email 与 uri（需 --format-types）格式生成对应的 validator 检查
+    let string_format = field_schema
+        .get("format")
+        .and_then(|f| f.as_str())
+        .filter(|_| rust_type == "String" && needs_validation)
+        .filter(|f| *f == "email" || (*f == "uri" && config.format_types))
+        .map(|f| f.to_string());
+
+    // 字段级别的弃用标记，说明取自 x-deprecation-note
+    let deprecated =
+        (field_schema.get("deprecated").and_then(|d| d.as_bool()) == Some(true)).then(|| {
+            field_schema
+                .get("x-deprecation-note")
+                .and_then(|n| n.as_str())
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| format!("{} is deprecated", field_name))
+        });
+
+    // 原始字节字段通过 serde_bytes 编解码
+    let serde_with = (rust_type == "Vec<u8>").then(|| "serde_bytes".to_string());
+
+    // 可选字段的 schema default，缺失时反序列化为该值
+    let schema_default = field_schema.get("default").filter(|_| is_optional).cloned();
+
+    // const 固定值由生成的验证函数检查，必填时 new 直接填入该值；内联枚举已限定取值
+    let const_value = field_schema
+        .get("const")
+        .filter(|_| config.type_override(struct_name, field_name).is_none())
+        .filter(|_| inline_enum.is_none())
+        .cloned();
+    let needs_validation = needs_validation || const_value.is_some();
+
     Ok(FieldInfo {
         name: rust_field_name,
         original_name: field_name.to_string(),
         rust_type,
         is_optional,
+        required_nullable,
         needs_validation,
         description,
+        title,
+        examples,
         max_length,
         min_length,
         min_value,
         max_value,
+        min_literal,
+        max_literal,
+        exclusive_min,
+        exclusive_max,
         min_items,
         max_items,
+        serde_with,
+        newtype,
+        inline_enum,
+        union,
+        inline_struct,
+        deprecated,
+        pattern,
+        multiple_of,
+        string_format,
+        schema_default,
+        const_value,
+    })
+}
+
+/// 读取数值边界及其排他性
+///
+/// 同时支持 draft-4 的布尔伴随形式（`"minimum": 0, "exclusiveMinimum": true`）
+/// 与 draft-6 起的数值形式（`"exclusiveMinimum": 0`）；两者并存时取更严格的一方。
+fn numeric_bound<'a>(
+    field_schema: &'a Value,
+    inclusive_key: &str,
+    exclusive_key: &str,
+    at_least_as_strict: fn(f64, f64) -> bool,
+) -> (Option<&'a Number>, bool) {
+    let as_number = |v: &'a Value| v.as_number().filter(|n| n.as_f64().is_some());
+    let inclusive = field_schema.get(inclusive_key).and_then(as_number);
+
+    match field_schema.get(exclusive_key) {
+        Some(Value::Bool(exclusive)) => (inclusive, *exclusive && inclusive.is_some()),
+        Some(value) => match (as_number(value), inclusive) {
+            (Some(exclusive), Some(inclusive))
+                if !at_least_as_strict(
+                    exclusive.as_f64().unwrap(),
+                    inclusive.as_f64().unwrap(),
+                ) =>
+            {
+                (Some(inclusive), false)
+            }
+            (Some(exclusive), _) => (Some(exclusive), true),
+            (None, inclusive) => (inclusive, false),
+        },
+        None => (inclusive, false),
+    }
+}
+
+/// 拆分边界的数值及其在 schema 中的十进制原文（arbitrary_precision 保留完整精度）
+///
+/// 指数形式（如 `1e3`）不保留原文，由生成器按数值格式化。
+fn bound_value(bound: Option<&Number>) -> (Option<f64>, Option<String>) {
+    let Some(number) = bound else {
+        return (None, None);
+    };
+    let literal = number.to_string();
+    let literal = (!literal.contains(['e', 'E'])).then_some(literal);
+    (number.as_f64(), literal)
+}
+
+/// 解析字段（或数组元素）内联的字符串 enum，生成以结构体和字段命名的枚举类型信息
+///
+/// 引用本地 enum 定义时（--local-definitions）以定义名命名。
+fn inline_enum_info(
+    field_schema: &Value,
+    root_schema: &Value,
+    struct_name: &str,
+    field_name: &str,
+    config: &Config,
+) -> Option<InlineEnumInfo> {
+    let target = field_schema.get("items").unwrap_or(field_schema);
+    let (target, name) = match local_definition(target, root_schema, config) {
+        Some((name, definition)) => (definition, name.to_string()),
+        None if target.get("$ref").is_some() => return None,
+        None => (
+            target,
+            format!(
+                "{}{}EnumType",
+                struct_name,
+                field_name.to_case(Case::Pascal)
+            ),
+        ),
+    };
+    string_enum_info(target, name)
+}
+
+/// 将字符串 enum schema 转换为以 `name` 命名的枚举信息，非字符串或空 enum 返回 None
+fn string_enum_info(target: &Value, name: String) -> Option<InlineEnumInfo> {
+    if let Some(field_type) = target.get("type").and_then(|t| t.as_str()) {
+        if field_type != "string" {
+            return None;
+        }
+    }
+
+    let values = target
+        .get("enum")?
+        .as_array()?
+        .iter()
+        .map(|v| v.as_str().map(|s| s.to_string()))
+        .collect::<Option<Vec<String>>>()?;
+    if values.is_empty() {
+        return None;
+    }
+
+    let default = target
+        .get("default")
+        .and_then(|d| d.as_str())
+        .filter(|d| values.iter().any(|v| v == d))
+        .map(|d| d.to_string());
+
+    Some(InlineEnumInfo {
+        name,
+        description: doc_text(target, "description"),
+        values,
+        default,
+        emitted_elsewhere: false,
     })
 }
 
+/// 将字段（或数组元素）的内联 object 提取为嵌套结构体，命名为 `{父结构体}{字段}Type`
+///
+/// 只展开内联的 `properties` 而不跟随 `$ref`，递归深度以 schema 的嵌套深度为限。
+/// 例外是 --local-definitions 下引用的本地对象定义：以定义名命名，
+/// 定义之间的递归引用由 `determine_rust_type` 以 Box 打断。
+fn inline_struct_info(
+    field_schema: &Value,
+    imports: &mut HashSet<String>,
+    root_schema: &Value,
+    struct_name: &str,
+    field_name: &str,
+    config: &Config,
+) -> Result<Option<Box<StructInfo>>, GenError> {
+    let target = field_schema.get("items").unwrap_or(field_schema);
+    let (target, name) = match local_definition(target, root_schema, config) {
+        Some((name, _)) if is_expanding(name) => return Ok(None),
+        Some((name, definition)) => (definition, name.to_string()),
+        None => (
+            target,
+            format!(
+                "{}{}Type",
+                struct_name.strip_suffix("Type").unwrap_or(struct_name),
+                field_name.to_case(Case::Pascal)
+            ),
+        ),
+    };
+    if target.get("$ref").is_some()
+        || target
+            .get("type")
+            .is_some_and(|t| t.as_str() != Some("object"))
+        || !target.get("properties").is_some_and(Value::is_object)
+    {
+        return Ok(None);
+    }
+
+    EXPANDING_DEFINITIONS.with(|stack| stack.borrow_mut().push(name.clone()));
+    let inline = extract_object_info(target, root_schema, &name, config);
+    EXPANDING_DEFINITIONS.with(|stack| stack.borrow_mut().pop());
+    let inline = inline?;
+    imports.extend(inline.imports.iter().cloned());
+    Ok(Some(Box::new(inline)))
+}
+
+/// 字段（或数组元素）通过 `$ref` 引用的本文件定义（--local-definitions），返回定义名与定义
+///
+/// 只处理能在消息文件中生成的对象与 enum 定义，受约束的标量仍由新类型包装或导入处理。
+fn local_definition<'a>(
+    target: &Value,
+    root_schema: &'a Value,
+    config: &Config,
+) -> Option<(&'a str, &'a Value)> {
+    if !config.local_definitions {
+        return None;
+    }
+    let name = target
+        .get("$ref")
+        .and_then(|r| r.as_str())?
+        .strip_prefix("#/definitions/")?;
+    let (name, definition) = root_schema
+        .get("definitions")?
+        .as_object()?
+        .get_key_value(name)?;
+    let generated = definition.get("properties").is_some_and(Value::is_object)
+        || definition.get("enum").is_some();
+    generated.then_some((name.as_str(), definition))
+}
+
+/// 将引用本地标量定义的字段（或数组元素）展开为定义本身（--local-definitions），约束随之保留
+///
+/// 启用 --newtype-scalars 时标量定义已生成为新类型包装，保持引用不变。
+fn inline_local_scalar<'a>(
+    field_schema: &'a Value,
+    root_schema: &Value,
+    config: &Config,
+) -> Cow<'a, Value> {
+    if !config.local_definitions || config.newtype_scalars {
+        return Cow::Borrowed(field_schema);
+    }
+    let expand = |schema: &Value| {
+        let name = schema
+            .get("$ref")
+            .and_then(|r| r.as_str())?
+            .strip_prefix("#/definitions/")?;
+        let definition = root_schema.get("definitions")?.get(name)?;
+        let scalar = definition
+            .get("type")
+            .and_then(|t| t.as_str())
+            .is_some_and(|t| matches!(t, "string" | "integer" | "number" | "boolean"))
+            && definition.get("enum").is_none();
+        if !scalar {
+            return None;
+        }
+        // 字段自身的说明优先于定义的说明
+        let mut expanded = definition.clone();
+        for key in ["description", "title", "examples"] {
+            if let Some(text) = schema.get(key) {
+                expanded[key] = text.clone();
+            }
+        }
+        Some(expanded)
+    };
+
+    if let Some(expanded) = expand(field_schema) {
+        return Cow::Owned(expanded);
+    }
+    match field_schema.get("items").and_then(expand) {
+        Some(items) => {
+            let mut expanded = field_schema.clone();
+            expanded["items"] = items;
+            Cow::Owned(expanded)
+        }
+        None => Cow::Borrowed(field_schema),
+    }
+}
+
+/// 只声明 const 而没有 type 的字段按常量值的 JSON 类型推断 type
+fn const_typed(field_schema: &Value) -> Cow<'_, Value> {
+    if field_schema.get("type").is_some() || field_schema.get("$ref").is_some() {
+        return Cow::Borrowed(field_schema);
+    }
+    let field_type = match field_schema.get("const") {
+        Some(Value::String(_)) => "string",
+        Some(Value::Bool(_)) => "boolean",
+        Some(Value::Number(n)) if n.is_i64() || n.is_u64() => "integer",
+        Some(Value::Number(_)) => "number",
+        _ => return Cow::Borrowed(field_schema),
+    };
+    let mut typed = field_schema.clone();
+    typed["type"] = field_type.into();
+    Cow::Owned(typed)
+}
+
+/// 本地定义是否正在展开（即被自身或其成员递归引用）
+fn is_expanding(name: &str) -> bool {
+    EXPANDING_DEFINITIONS.with(|stack| stack.borrow().iter().any(|n| n == name))
+}
+
+/// 解析字段（或数组元素）的 oneOf/anyOf，生成 untagged 枚举信息
+///
+/// 引用的变体以定义名（去掉 `Type` 后缀）命名，其余变体以解析出的 Rust 类型命名。
+fn union_info(
+    field_schema: &Value,
+    imports: &mut HashSet<String>,
+    root_schema: &Value,
+    struct_name: &str,
+    field_name: &str,
+    config: &Config,
+) -> Result<Option<UnionInfo>, GenError> {
+    let target = field_schema.get("items").unwrap_or(field_schema);
+    let Some(alternatives) = target
+        .get("oneOf")
+        .or_else(|| target.get("anyOf"))
+        .and_then(|a| a.as_array())
+    else {
+        return Ok(None);
+    };
+    if alternatives.is_empty() {
+        return Ok(None);
+    }
+
+    let mut variants: Vec<(String, String)> = Vec::new();
+    let mut constraints: Vec<Option<NewtypeInfo>> = Vec::new();
+    for alternative in alternatives {
+        let (rust_type, _) =
+            determine_rust_type(alternative, imports, root_schema, struct_name, config)?;
+        let base = match alternative.get("$ref").and_then(|r| r.as_str()) {
+            Some(ref_path) => {
+                let name = ref_path.rsplit('/').next().unwrap_or(ref_path);
+                name.strip_suffix("Type").unwrap_or(name).to_string()
+            }
+            None => rust_type.clone(),
+        };
+        let base: String = base
+            .to_case(Case::Pascal)
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect();
+
+        let mut variant = base.clone();
+        let mut suffix = 2;
+        while variants.iter().any(|(name, _)| *name == variant) {
+            variant = format!("{}{}", base, suffix);
+            suffix += 1;
+        }
+
+        // 标量变体按自身（或引用的标量定义）的约束验证，与同样约束的字段一致
+        let scalar = matches!(
+            rust_type.as_str(),
+            "String" | "Decimal" | "f64" | "i32" | "i64" | "u32" | "u64"
+        );
+        let definition = alternative
+            .get("$ref")
+            .and_then(|r| r.as_str())
+            .and_then(|r| r.strip_prefix("#/definitions/"))
+            .and_then(|name| root_schema.get("definitions")?.get(name))
+            .unwrap_or(alternative);
+        constraints.push(scalar.then(|| scalar_constraints(definition, &variant, &rust_type)));
+        variants.push((variant, rust_type));
+    }
+
+    let (tag, tag_values) = match union_discriminator(alternatives, root_schema) {
+        Some((tag, tag_values)) => (Some(tag), tag_values),
+        None => (None, Vec::new()),
+    };
+
+    Ok(Some(UnionInfo {
+        name: format!(
+            "{}{}UnionType",
+            struct_name,
+            field_name.to_case(Case::Pascal)
+        ),
+        description: doc_text(target, "description"),
+        variants,
+        constraints,
+        tag,
+        tag_values,
+    }))
+}
+
+/// 查找 oneOf/anyOf 各分支共有的判别属性：每个分支都是必填该属性且 const 为互不相同字符串的 object
+///
+/// 返回属性名与各分支按 schema 顺序的 const 值；找不到时返回 None，联合类型保持 untagged。
+fn union_discriminator(
+    alternatives: &[Value],
+    root_schema: &Value,
+) -> Option<(String, Vec<String>)> {
+    let branches = alternatives
+        .iter()
+        .map(
+            |alternative| match alternative.get("$ref").and_then(|r| r.as_str()) {
+                Some(ref_path) => root_schema
+                    .get("definitions")?
+                    .get(ref_path.strip_prefix("#/definitions/")?),
+                None => Some(alternative),
+            },
+        )
+        .collect::<Option<Vec<&Value>>>()?;
+    let first = branches.first()?.get("properties")?.as_object()?;
+
+    first.keys().find_map(|property| {
+        let values = branches
+            .iter()
+            .map(|branch| {
+                let required = branch
+                    .get("required")?
+                    .as_array()?
+                    .iter()
+                    .any(|r| r.as_str() == Some(property.as_str()));
+                let value = branch
+                    .get("properties")?
+                    .get(property)?
+                    .get("const")?
+                    .as_str()?;
+                required.then(|| value.to_string())
+            })
+            .collect::<Option<Vec<String>>>()?;
+        let distinct: HashSet<&String> = values.iter().collect();
+        (distinct.len() == values.len()).then(|| (property.clone(), values))
+    })
+}
+
+/// 解析字段（或数组元素）引用的标量定义，生成新类型包装信息
+fn scalar_newtype(
+    field_schema: &Value,
+    root_schema: &Value,
+    imports: &mut HashSet<String>,
+) -> Option<NewtypeInfo> {
+    let target = field_schema.get("items").unwrap_or(field_schema);
+    let name = target
+        .get("$ref")
+        .and_then(|r| r.as_str())?
+        .strip_prefix("#/definitions/")?;
+    let definition = root_schema.get("definitions")?.get(name)?;
+
+    // 枚举与带格式的字符串（如 date-time）不是简单的受约束标量
+    if definition.get("enum").is_some() || definition.get("format").is_some() {
+        return None;
+    }
+    let inner_type = match definition.get("type").and_then(|t| t.as_str())? {
+        "string" => "String",
+        "integer" => integer_type(definition),
+        "number" => {
+            imports.insert("use rust_decimal::Decimal;".to_string());
+            "Decimal"
+        }
+        _ => return None,
+    };
+
+    Some(NewtypeInfo {
+        description: doc_text(definition, "description"),
+        ..scalar_constraints(definition, name, inner_type)
+    })
+}
+
+/// 提取标量 schema 的长度与数值边界（含排他边界），用于新类型包装与 oneOf 的标量变体
+fn scalar_constraints(schema: &Value, name: &str, inner_type: &str) -> NewtypeInfo {
+    let as_u32 = |key: &str| schema.get(key).and_then(|v| v.as_u64()).map(|v| v as u32);
+    let (min_bound, exclusive_min) =
+        numeric_bound(schema, "minimum", "exclusiveMinimum", |x, i| x >= i);
+    let (min_value, min_literal) = bound_value(min_bound);
+    let (max_bound, exclusive_max) =
+        numeric_bound(schema, "maximum", "exclusiveMaximum", |x, i| x <= i);
+    let (max_value, max_literal) = bound_value(max_bound);
+    NewtypeInfo {
+        name: name.to_string(),
+        inner_type: inner_type.to_string(),
+        description: None,
+        max_length: as_u32("maxLength"),
+        min_length: as_u32("minLength"),
+        min_value,
+        max_value,
+        min_literal,
+        max_literal,
+        exclusive_min,
+        exclusive_max,
+    }
+}
+
 /// 确定 Rust 类型
 fn determine_rust_type(
     field_schema: &Value,
     imports: &mut HashSet<String>,
-    _root_schema: &Value,
-) -> Result<(String, bool), Box<dyn std::error::Error>> {
+    root_schema: &Value,
+    struct_name: &str,
+    config: &Config,
+) -> Result<(String, bool), GenError> {
+    // 布尔 schema：true 允许任意值，false 不允许任何值
+    match field_schema.as_bool() {
+        Some(true) => {
+            imports.insert("use serde_json::Value;".to_string());
+            return Ok(("Value".to_string(), false));
+        }
+        Some(false) => {
+            return Err(GenError::unsupported(
+                "boolean schema `false` never validates",
+            ))
+        }
+        None => {}
+    }
+
+    // 根引用（"$ref": "#"）指向当前结构体自身，需要 Box 打断无限大小
+    if is_root_ref(field_schema) {
+        return Ok((format!("Box<{}>", struct_name), true));
+    }
+
+    // 递归引用正在展开的本地定义，与根引用一样需要 Box
+    if let Some((name, _)) = local_definition(field_schema, root_schema, config) {
+        if is_expanding(name) {
+            return Ok((format!("Box<{}>", name), true));
+        }
+    }
+
     // Handle $ref references
     if let Some(ref_path) = field_schema.get("$ref").and_then(|r| r.as_str()) {
-        return handle_ref_type(ref_path, imports);
+        if let Some(mapped) = mapped_ref_type(ref_path, imports, config)? {
+            return Ok(mapped);
+        }
+        return handle_ref_type(ref_path, imports, &config.ocpp_version);
+    }
+
+    // 数组元素等嵌套位置的可空类型映射为 Option<T>
+    let (field_schema, nullable) = split_nullable(field_schema);
+    if nullable {
+        let (rust_type, needs_validation) =
+            determine_rust_type(&field_schema, imports, root_schema, struct_name, config)?;
+        return Ok((format!("Option<{}>", rust_type), needs_validation));
+    }
+    let field_schema = field_schema.as_ref();
+
+    // 剩余多个非 null 类型的联合无法映射为单一类型
+    if field_schema.get("type").is_some_and(Value::is_array) {
+        imports.insert("use serde_json::Value;".to_string());
+        return Ok(("Value".to_string(), false));
     }
 
     // Handle arrays
     if let Some(field_type) = field_schema.get("type").and_then(|t| t.as_str()) {
         match field_type {
             "string" => {
-                if field_schema.get("format").and_then(|f| f.as_str()) == Some("date-time") {
-                    imports.insert("use chrono::{DateTime, Utc};".to_string());
-                    Ok(("DateTime<Utc>".to_string(), false))
-                } else {
-                    Ok(("String".to_string(), true))
+                match field_schema.get("format").and_then(|f| f.as_str()) {
+                    Some("date-time") => {
+                        imports.insert("use chrono::{DateTime, Utc};".to_string());
+                        Ok(("DateTime<Utc>".to_string(), false))
+                    }
+                    // 原始字节（非 base64），长度约束按字节数计算
+                    Some("binary") => Ok(("Vec<u8>".to_string(), true)),
+                    // uuid 依赖 uuid crate（需启用 serde feature），仅在 --format-types 时映射
+                    Some("uuid") if config.format_types => {
+                        imports.insert("use uuid::Uuid;".to_string());
+                        Ok(("Uuid".to_string(), false))
+                    }
+                    Some("ipv4") => {
+                        imports.insert("use std::net::Ipv4Addr;".to_string());
+                        Ok(("Ipv4Addr".to_string(), false))
+                    }
+                    Some("ipv6") => {
+                        imports.insert("use std::net::Ipv6Addr;".to_string());
+                        Ok(("Ipv6Addr".to_string(), false))
+                    }
+                    // 其他格式（含 email、uri）保持为 String，由 string_format 生成验证
+                    _ => Ok(("String".to_string(), true)),
                 }
             }
-            "integer" => Ok(("i32".to_string(), true)),
+            "integer" => Ok((integer_type(field_schema).to_string(), true)),
             "number" => {
                 imports.insert("use rust_decimal::Decimal;".to_string());
                 Ok(("Decimal".to_string(), true))
@@ -192,7 +1174,17 @@ fn determine_rust_type(
             "boolean" => Ok(("bool".to_string(), false)),
             "array" => {
                 if let Some(items) = field_schema.get("items") {
-                    let (item_type, _) = determine_rust_type(items, imports, _root_schema)?;
+                    // Vec 本身已提供间接层，根引用与递归定义的元素无需 Box
+                    let recursive = local_definition(items, root_schema, config)
+                        .map(|(name, _)| name)
+                        .filter(|name| is_expanding(name));
+                    let item_type = if is_root_ref(items) {
+                        struct_name.to_string()
+                    } else if let Some(name) = recursive {
+                        name.to_string()
+                    } else {
+                        determine_rust_type(items, imports, root_schema, struct_name, config)?.0
+                    };
                     Ok((format!("Vec<{}>", item_type), true))
                 } else {
                     imports.insert("use serde_json::Value;".to_string());
@@ -200,8 +1192,23 @@ fn determine_rust_type(
                 }
             }
             "object" => {
-                imports.insert("use serde_json::Value;".to_string());
-                Ok(("Value".to_string(), false))
+                // 仅由 additionalProperties 描述取值类型的对象映射为 HashMap
+                let values = field_schema
+                    .get("additionalProperties")
+                    .filter(|v| v.is_object() && field_schema.get("properties").is_none());
+                let Some(values) = values else {
+                    imports.insert("use serde_json::Value;".to_string());
+                    return Ok(("Value".to_string(), false));
+                };
+
+                let value_type = if values.get("type").is_none() && values.get("$ref").is_none() {
+                    imports.insert("use serde_json::Value;".to_string());
+                    "Value".to_string()
+                } else {
+                    determine_rust_type(values, imports, root_schema, struct_name, config)?.0
+                };
+                imports.insert("use std::collections::HashMap;".to_string());
+                Ok((format!("HashMap<String, {}>", value_type), true))
             }
             _ => Ok(("String".to_string(), true)),
         }
@@ -210,130 +1217,1212 @@ fn determine_rust_type(
     }
 }
 
+/// 按 schema 边界选择最窄的整数类型
+///
+/// 下限不小于 0 时使用无符号类型；边界超出 32 位范围或带有 `format: int64` 时使用 64 位类型。
+fn integer_type(field_schema: &Value) -> &'static str {
+    // 排他边界换算为整数的包含边界
+    let min = match numeric_bound(field_schema, "minimum", "exclusiveMinimum", |x, i| x >= i) {
+        (Some(min), true) => min.as_f64().map(|min| min.floor() + 1.0),
+        (min, _) => min.and_then(Number::as_f64),
+    };
+    let max = match numeric_bound(field_schema, "maximum", "exclusiveMaximum", |x, i| x <= i) {
+        (Some(max), true) => max.as_f64().map(|max| max.ceil() - 1.0),
+        (max, _) => max.and_then(Number::as_f64),
+    };
+    let int64 = field_schema.get("format").and_then(|f| f.as_str()) == Some("int64");
+
+    if min.is_some_and(|min| min >= 0.0) {
+        if int64 || max.is_some_and(|max| max > u32::MAX as f64) {
+            "u64"
+        } else {
+            "u32"
+        }
+    } else if int64
+        || min.is_some_and(|min| min < i32::MIN as f64)
+        || max.is_some_and(|max| max > i32::MAX as f64)
+    {
+        "i64"
+    } else {
+        "i32"
+    }
+}
+
+/// 拆分数组形式的 type（如 `["string", "null"]`），返回去除 null 后的 schema 与是否可为 null
+///
+/// 仅剩一个类型时改写为普通的字符串 type；剩余多个类型时保留数组，由调用方回退为 Value。
+fn split_nullable(field_schema: &Value) -> (Cow<'_, Value>, bool) {
+    let Some(types) = field_schema.get("type").and_then(|t| t.as_array()) else {
+        return (Cow::Borrowed(field_schema), false);
+    };
+    let nullable = types.iter().any(|t| t.as_str() == Some("null"));
+    let mut remaining: Vec<Value> = types
+        .iter()
+        .filter(|t| t.as_str() != Some("null"))
+        .cloned()
+        .collect();
+
+    let mut schema = field_schema.clone();
+    schema["type"] = match remaining.len() {
+        1 => remaining.remove(0),
+        _ => Value::Array(remaining),
+    };
+    (Cow::Owned(schema), nullable)
+}
+
+/// 判断类型是否为（或包含）serde_json::Value
+fn is_value_type(rust_type: &str) -> bool {
+    rust_type == "Value" || rust_type.contains("<Value>")
+}
+
+/// 描述字段回退到 Value 的原因
+fn value_fallback_reason(field_schema: &Value) -> String {
+    if field_schema.as_bool() == Some(true) {
+        return "boolean schema `true` allows any value".to_string();
+    }
+    if let Some(ref_path) = field_schema.get("$ref").and_then(|r| r.as_str()) {
+        return format!("$ref {} has no concrete type", ref_path);
+    }
+    if let Some(types) = field_schema.get("type").and_then(|t| t.as_array()) {
+        return format!("union of types {}", Value::Array(types.clone()));
+    }
+    match field_schema.get("type").and_then(|t| t.as_str()) {
+        Some("array") => match field_schema.get("items") {
+            Some(items) => value_fallback_reason(items),
+            None => "array without items".to_string(),
+        },
+        _ => "object without a concrete type".to_string(),
+    }
+}
+
+/// 判断是否为指向根 schema 的自引用
+fn is_root_ref(field_schema: &Value) -> bool {
+    field_schema.get("$ref").and_then(|r| r.as_str()) == Some("#")
+}
+
+/// 已解析的 $ref：Rust 类型、是否需要验证、对应的导入语句
+type ResolvedRef = (String, bool, Option<String>);
+
+thread_local! {
+    /// 正在展开的本地定义名（由外到内），用于识别定义之间的递归引用
+    static EXPANDING_DEFINITIONS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+
+    /// 已读取的 --type-map 文件，按路径缓存
+    static TYPE_MAPS: RefCell<HashMap<String, Rc<HashMap<String, TypeMapping>>>> =
+        RefCell::new(HashMap::new());
+
+    /// $ref 解析缓存，按版本模块与引用路径记忆映射结果，避免对每个字段重复匹配和格式化导入
+    static REF_CACHE: RefCell<HashMap<(String, String), ResolvedRef>> =
+        RefCell::new(HashMap::new());
+}
+
 /// 处理 $ref 类型引用
 fn handle_ref_type(
     ref_path: &str,
     imports: &mut HashSet<String>,
-) -> Result<(String, bool), Box<dyn std::error::Error>> {
-    if ref_path.starts_with("#/definitions/") {
-        let type_name = ref_path.replace("#/definitions/", "");
-
-        // Map OCPP types to their Rust equivalents with special handling for known types
-        let (rust_type, needs_validation) = match type_name.as_str() {
-            // Special cases that need specific handling
-            "DERControlStatusEnumType" => {
-                imports.insert(
-                    "use crate::v2_1::enumerations::der_control::DERControlStatusEnumType;"
-                        .to_string(),
+    version_module: &str,
+) -> Result<(String, bool), GenError> {
+    let (rust_type, needs_validation, import) = REF_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .entry((version_module.to_string(), ref_path.to_string()))
+            .or_insert_with(|| resolve_ref(ref_path, version_module))
+            .clone()
+    });
+
+    if let Some(import) = import {
+        imports.insert(import);
+    }
+
+    Ok((rust_type, needs_validation))
+}
+
+/// 按 --type-map 解析 $ref，未映射的类型返回 None，交由内置规则处理
+fn mapped_ref_type(
+    ref_path: &str,
+    imports: &mut HashSet<String>,
+    config: &Config,
+) -> Result<Option<(String, bool)>, GenError> {
+    let Some(path) = &config.type_map else {
+        return Ok(None);
+    };
+    let Some(type_name) = ref_type_name(ref_path) else {
+        return Ok(None);
+    };
+    let mappings = match TYPE_MAPS.with(|maps| maps.borrow().get(path).cloned()) {
+        Some(mappings) => mappings,
+        None => {
+            let mappings = Rc::new(config.type_mappings()?);
+            TYPE_MAPS.with(|maps| maps.borrow_mut().insert(path.clone(), mappings.clone()));
+            mappings
+        }
+    };
+    let Some(mapping) = mappings.get(type_name) else {
+        return Ok(None);
+    };
+
+    // 省略的键沿用内置规则
+    let (rust_type, needs_validation, import) = resolve_ref(ref_path, &config.ocpp_version);
+    let import = match mapping.import.as_deref().map(str::trim) {
+        Some("") => None,
+        Some(import) if import.starts_with("use ") => Some(import.to_string()),
+        Some(path) => Some(format!("use {};", path)),
+        None => import,
+    };
+    if let Some(import) = import {
+        imports.insert(import);
+    }
+    Ok(Some((
+        mapping.rust_type.clone().unwrap_or(rust_type),
+        mapping.needs_validation.unwrap_or(needs_validation),
+    )))
+}
+
+/// $ref 路径指向的类型名；JSON Schema 的 definitions 与 OpenAPI 的 components.schemas 采用相同的映射规则
+fn ref_type_name(ref_path: &str) -> Option<&str> {
+    ref_path
+        .strip_prefix("#/definitions/")
+        .or_else(|| ref_path.strip_prefix("#/components/schemas/"))
+}
+
+/// 将 $ref 路径映射为 Rust 类型（未缓存的解析逻辑），导入路径位于 `crate::{version_module}` 下
+fn resolve_ref(ref_path: &str, version_module: &str) -> ResolvedRef {
+    let Some(type_name) = ref_type_name(ref_path) else {
+        return ("String".to_string(), true, None);
+    };
+    let type_name = type_name.to_string();
+
+    // Map OCPP types to their Rust equivalents with special handling for known types
+    match type_name.as_str() {
+        // Special cases that need specific handling
+        "DERControlStatusEnumType" => (
+            type_name.clone(),
+            false,
+            Some(format!(
+                "use crate::{}::enumerations::der_control::DERControlStatusEnumType;",
+                version_module
+            )),
+        ),
+        "EventDataType" => {
+            // EventDataType 可能不存在，使用 Value 作为替代
+            (
+                "Value".to_string(),
+                false,
+                Some("use serde_json::Value;".to_string()),
+            )
+        }
+        "AuthorizationData" => (
+            type_name.clone(),
+            true,
+            Some(format!(
+                "use crate::{}::datatypes::AuthorizationData;",
+                version_module
+            )),
+        ),
+        // 常见的数据类型
+        "CustomDataType"
+        | "StatusInfoType"
+        | "IdTokenType"
+        | "IdTokenInfoType"
+        | "EVSEType"
+        | "TariffType"
+        | "OCSPRequestDataType" => (
+            type_name.clone(),
+            true,
+            Some(format!(
+                "use crate::{}::datatypes::{};",
+                version_module, type_name
+            )),
+        ),
+        // 常见的枚举类型
+        "GenericStatusEnumType"
+        | "AuthorizeCertificateStatusEnumType"
+        | "EnergyTransferModeEnumType"
+        | "ResetEnumType"
+        | "ResetStatusEnumType"
+        | "MessageTriggerEnumType"
+        | "TriggerMessageStatusEnumType" => (
+            type_name.clone(),
+            false,
+            Some(format!(
+                "use crate::{}::enumerations::{};",
+                version_module, type_name
+            )),
+        ),
+        _ => {
+            // For other types, try to determine if it's an enum or datatype
+            if type_name.ends_with("EnumType") {
+                // 枚举类型不需要 nested 验证
+                let import = format!(
+                    "use crate::{}::enumerations::{};",
+                    version_module, type_name
                 );
-                (type_name.clone(), false)
+                (type_name, false, Some(import))
+            } else if type_name.ends_with("Type") {
+                // 数据类型需要 nested 验证
+                let import = format!("use crate::{}::datatypes::{};", version_module, type_name);
+                (type_name, true, Some(import))
+            } else {
+                (type_name, true, None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versioned_filename_normalizes_to_base_name() {
+        let pattern = Regex::new(r"_v\d+p\d+$").unwrap();
+
+        let name = normalize_schema_name("AuthorizeRequest_v1p2", Some(&pattern));
+        assert_eq!(name, "AuthorizeRequest");
+        assert_eq!(parse_message_type(&name), ("Authorize".to_string(), true));
+
+        let name = normalize_schema_name("AuthorizeResponse_v1p2", Some(&pattern));
+        assert_eq!(parse_message_type(&name), ("Authorize".to_string(), false));
+
+        assert_eq!(
+            normalize_schema_name("AuthorizeRequest_v1p2", None),
+            "AuthorizeRequest_v1p2"
+        );
+    }
+
+    #[test]
+    fn test_required_property_missing_from_properties() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "idTag": { "type": "string" } },
+            "required": ["idTag", "evseId"]
+        });
+
+        assert_eq!(
+            missing_required_properties(&schema),
+            vec!["evseId".to_string()]
+        );
+
+        // Lenient mode warns and keeps generating
+        let info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        assert_eq!(info.fields.len(), 1);
+
+        // Strict mode turns the warning into an error naming the struct
+        let strict = Config {
+            strict: true,
+            ..Config::default()
+        };
+        let err = extract_struct_info(&schema, "FooRequest", &strict)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("FooRequest"));
+        assert!(err.contains("evseId"));
+    }
+
+    #[test]
+    fn test_unique_enum_array_becomes_hash_set_with_enum_sets() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "modes": {
+                    "type": "array",
+                    "items": { "$ref": "#/definitions/EnergyTransferModeEnumType" },
+                    "uniqueItems": true,
+                    "minItems": 1
+                },
+                "names": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "uniqueItems": true
+                }
+            }
+        });
+        let config = Config {
+            enum_sets: true,
+            ..Config::default()
+        };
+
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        assert_eq!(
+            info.fields[0].rust_type,
+            "HashSet<EnergyTransferModeEnumType>"
+        );
+        assert_eq!(info.fields[1].rust_type, "Vec<String>");
+        assert!(info.imports.contains("use std::collections::HashSet;"));
+
+        let info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        assert_eq!(info.fields[0].rust_type, "Vec<EnergyTransferModeEnumType>");
+    }
+
+    #[test]
+    fn test_openapi_component_refs_resolve_like_definitions() {
+        let mut imports = HashSet::new();
+        let from_openapi =
+            handle_ref_type("#/components/schemas/IdTokenType", &mut imports, "v2_1").unwrap();
+        let from_definitions =
+            handle_ref_type("#/definitions/IdTokenType", &mut imports, "v2_1").unwrap();
+
+        assert_eq!(from_openapi, from_definitions);
+        assert_eq!(from_openapi, ("IdTokenType".to_string(), true));
+        assert!(imports.contains("use crate::v2_1::datatypes::IdTokenType;"));
+    }
+
+    #[test]
+    fn test_yaml_schema_matches_json_schema() {
+        let json = r#"{
+            "type": "object",
+            "properties": {
+                "zeta": { "type": "string", "maxLength": 20 },
+                "alpha": { "type": "integer", "minimum": 1 }
+            },
+            "required": ["zeta"]
+        }"#;
+        let yaml = "
+type: object
+properties:
+  zeta:
+    type: string
+    maxLength: 20
+  alpha:
+    type: integer
+    minimum: 1
+required:
+  - zeta
+";
+
+        let json_schema = parse_schema_content(json, Path::new("Schema.json")).unwrap();
+        let yaml_schema = parse_schema_content(yaml, Path::new("Schema.yaml")).unwrap();
+        let config = Config::default();
+        let from_json = extract_struct_info(&json_schema, "FooRequest", &config).unwrap();
+        let from_yaml = extract_struct_info(&yaml_schema, "FooRequest", &config).unwrap();
+
+        let summary = |info: &StructInfo| {
+            info.fields
+                .iter()
+                .map(|f| {
+                    (
+                        f.name.clone(),
+                        f.rust_type.clone(),
+                        f.is_optional,
+                        f.max_length,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summary(&from_json), summary(&from_yaml));
+        assert_eq!(from_yaml.fields[0].name, "zeta");
+        assert_eq!(from_yaml.fields[1].name, "alpha");
+    }
+
+    #[test]
+    fn test_root_ref_maps_to_boxed_self_type() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": "string" },
+                "parent": { "$ref": "#" },
+                "children": { "type": "array", "items": { "$ref": "#" } }
+            },
+            "required": ["label"]
+        });
+
+        let struct_info = extract_struct_info(&schema, "NodeRequest", &Config::default()).unwrap();
+
+        assert_eq!(struct_info.fields[1].rust_type, "Box<NodeRequest>");
+        assert!(struct_info.fields[1].is_optional);
+        assert_eq!(struct_info.fields[2].rust_type, "Vec<NodeRequest>");
+    }
+
+    #[test]
+    fn test_datetime_as_string_override() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "vendorTimestamp": { "type": "string", "format": "date-time" },
+                "timestamp": { "type": "string", "format": "date-time" }
+            },
+            "required": ["vendorTimestamp", "timestamp"]
+        });
+        let config = Config {
+            datetime_as_string: vec!["MeterValuesRequest.vendorTimestamp".to_string()],
+            ..Config::default()
+        };
+
+        let struct_info = extract_struct_info(&schema, "MeterValuesRequest", &config).unwrap();
+
+        assert_eq!(struct_info.fields[0].rust_type, "String");
+        assert_eq!(struct_info.fields[0].max_length, Some(64));
+        assert_eq!(struct_info.fields[1].rust_type, "DateTime<Utc>");
+
+        // 其他结构体的同名字段不受影响
+        let other = extract_struct_info(&schema, "OtherRequest", &config).unwrap();
+        assert_eq!(other.fields[0].rust_type, "DateTime<Utc>");
+    }
+
+    #[test]
+    fn test_no_value_fallback_requires_type_override() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "payload": { "type": "object" }
+            },
+            "required": ["payload"]
+        });
+        let mut config = Config {
+            no_value_fallback: true,
+            ..Config::default()
+        };
+
+        let error = extract_struct_info(&schema, "DataTransferRequest", &config).unwrap_err();
+        assert!(error.to_string().contains("DataTransferRequest.payload"));
+        assert!(error.to_string().contains("object without a concrete type"));
+
+        config.type_overrides =
+            vec!["DataTransferRequest.payload=DataTransferPayloadType".to_string()];
+        let struct_info = extract_struct_info(&schema, "DataTransferRequest", &config).unwrap();
+        assert_eq!(struct_info.fields[0].rust_type, "DataTransferPayloadType");
+        assert!(!struct_info.imports.contains("use serde_json::Value;"));
+    }
+
+    #[test]
+    fn test_ref_cache_matches_uncached_resolution() {
+        let refs = [
+            "#/definitions/CustomDataType",
+            "#/definitions/EventDataType",
+            "#/definitions/DERControlStatusEnumType",
+            "#/definitions/ChargingProfileType",
+            "#/definitions/ChargingRateUnitEnumType",
+            "#/components/schemas/IdTokenType",
+            "#/definitions/AuthorizationData",
+            "#/definitions/Plain",
+            "#/other/Unknown",
+        ];
+
+        for ref_path in refs {
+            let (rust_type, needs_validation, import) = resolve_ref(ref_path, "v2_1");
+            // 第一次填充缓存，第二次命中缓存，两者都应与未缓存路径一致
+            for _ in 0..2 {
+                let mut imports = HashSet::new();
+                let cached = handle_ref_type(ref_path, &mut imports, "v2_1").unwrap();
+                assert_eq!(cached, (rust_type.clone(), needs_validation));
+                assert_eq!(imports, import.iter().cloned().collect());
             }
-            "EventDataType" => {
-                // EventDataType 可能不存在，使用 Value 作为替代
-                imports.insert("use serde_json::Value;".to_string());
-                ("Value".to_string(), false)
+        }
+    }
+
+    #[test]
+    fn test_definitions_library_yields_one_struct_per_object() {
+        let schema = serde_json::json!({
+            "$schema": "http://json-schema.org/draft-06/schema#",
+            "definitions": {
+                "ChargingLimitType": {
+                    "type": "object",
+                    "properties": {
+                        "chargingLimitSource": { "type": "string", "maxLength": 20 },
+                        "isGridCritical": { "type": "boolean" }
+                    },
+                    "required": ["chargingLimitSource"]
+                },
+                "ChargingScheduleType": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "limit": { "$ref": "#/definitions/ChargingLimitType" },
+                        "customData": { "$ref": "#/definitions/CustomDataType" },
+                        "chargingRateUnit": { "$ref": "#/definitions/ChargingRateUnitEnumType" }
+                    },
+                    "required": ["id"]
+                },
+                "ChargingRateUnitEnumType": {
+                    "type": "string",
+                    "enum": ["W", "A"]
+                }
             }
-            "AuthorizationData" => {
-                imports.insert("use crate::v2_1::datatypes::AuthorizationData;".to_string());
-                (type_name.clone(), true)
+        });
+
+        assert!(is_definitions_library(&schema));
+        let struct_infos =
+            extract_struct_infos_from_definitions(&schema, &Config::default()).unwrap();
+
+        let names: Vec<&str> = struct_infos.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["ChargingLimitType", "ChargingScheduleType"]);
+        let schedule = &struct_infos[1];
+        assert_eq!(schedule.fields[1].rust_type, "ChargingLimitType");
+        assert!(!schedule
+            .imports
+            .contains("use crate::v2_1::datatypes::ChargingLimitType;"));
+        assert!(schedule
+            .imports
+            .contains("use crate::v2_1::datatypes::CustomDataType;"));
+
+        // 字符串 enum 定义生成到同一模块，引用它的字段不再导入
+        let enums = extract_enum_infos_from_definitions(&schema);
+        assert_eq!(
+            enums,
+            vec![InlineEnumInfo {
+                name: "ChargingRateUnitEnumType".to_string(),
+                description: None,
+                values: vec!["W".to_string(), "A".to_string()],
+                default: None,
+                emitted_elsewhere: false,
+            }]
+        );
+        assert_eq!(schedule.fields[3].rust_type, "ChargingRateUnitEnumType");
+        assert!(!schedule
+            .imports
+            .iter()
+            .any(|import| import.ends_with("::ChargingRateUnitEnumType;")));
+    }
+
+    #[test]
+    fn test_newtype_scalars_resolve_constrained_definitions() {
+        let schema = serde_json::json!({
+            "definitions": {
+                "CiString20Type": { "type": "string", "maxLength": 20 },
+                "CustomDataType": { "type": "object", "properties": {} }
+            },
+            "type": "object",
+            "properties": {
+                "idTag": { "$ref": "#/definitions/CiString20Type" },
+                "aliases": { "type": "array", "items": { "$ref": "#/definitions/CiString20Type" } },
+                "customData": { "$ref": "#/definitions/CustomDataType" }
+            },
+            "required": ["idTag"]
+        });
+        let config = Config {
+            newtype_scalars: true,
+            ..Config::default()
+        };
+
+        let struct_info = extract_struct_info(&schema, "AuthorizeRequest", &config).unwrap();
+
+        assert_eq!(struct_info.fields[0].rust_type, "CiString20Type");
+        let newtype = struct_info.fields[0].newtype.as_ref().unwrap();
+        assert_eq!(newtype.inner_type, "String");
+        assert_eq!(newtype.max_length, Some(20));
+        assert_eq!(struct_info.fields[1].rust_type, "Vec<CiString20Type>");
+        assert!(struct_info.fields[2].newtype.is_none());
+        assert!(!struct_info
+            .imports
+            .contains("use crate::v2_1::datatypes::CiString20Type;"));
+    }
+
+    #[test]
+    fn test_boolean_property_schemas() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "anything": true,
+                "list": { "type": "array", "items": true }
+            },
+            "required": ["anything"]
+        });
+
+        let struct_info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        assert_eq!(struct_info.fields[0].rust_type, "Value");
+        assert_eq!(struct_info.fields[1].rust_type, "Vec<Value>");
+        assert!(struct_info.imports.contains("use serde_json::Value;"));
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "never": false }
+        });
+        let error = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap_err();
+        assert!(error.to_string().contains("FooRequest.never"));
+    }
+
+    #[test]
+    fn test_inline_string_enum_becomes_enum_type() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "status": {
+                    "type": "string",
+                    "enum": ["Accepted", "Rejected", "Pending"]
+                },
+                "phases": {
+                    "type": "array",
+                    "items": { "type": "string", "enum": ["L1", "L2"] },
+                    "minItems": 1
+                },
+                "mode": { "type": "integer", "enum": [1, 2] }
+            },
+            "required": ["status"]
+        });
+
+        let info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        assert_eq!(info.fields[0].rust_type, "FooRequestStatusEnumType");
+        assert_eq!(
+            info.fields[0].inline_enum.as_ref().unwrap().values,
+            vec!["Accepted", "Rejected", "Pending"]
+        );
+        assert_eq!(info.fields[1].rust_type, "Vec<FooRequestPhasesEnumType>");
+        assert_eq!(info.fields[1].min_items, Some(1));
+        assert_eq!(info.fields[2].rust_type, "i32");
+        assert!(info.fields[2].inline_enum.is_none());
+    }
+
+    #[test]
+    fn test_exclusive_bounds_in_both_draft_forms() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "draft4": { "type": "integer", "minimum": 0, "exclusiveMinimum": true, "maximum": 10 },
+                "draft6": { "type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 1.5 },
+                "looser": { "type": "integer", "minimum": 5, "exclusiveMinimum": 2 }
             }
-            // 常见的数据类型
-            "CustomDataType"
-            | "StatusInfoType"
-            | "IdTokenType"
-            | "IdTokenInfoType"
-            | "EVSEType"
-            | "TariffType"
-            | "OCSPRequestDataType" => {
-                imports.insert(format!("use crate::v2_1::datatypes::{};", type_name));
-                (type_name.clone(), true)
+        });
+
+        let info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        let draft4 = &info.fields[0];
+        assert_eq!((draft4.min_value, draft4.exclusive_min), (Some(0.0), true));
+        assert_eq!(
+            (draft4.max_value, draft4.exclusive_max),
+            (Some(10.0), false)
+        );
+        let draft6 = &info.fields[1];
+        assert_eq!((draft6.min_value, draft6.exclusive_min), (Some(0.0), true));
+        assert_eq!((draft6.max_value, draft6.exclusive_max), (Some(1.5), true));
+        let looser = &info.fields[2];
+        assert_eq!((looser.min_value, looser.exclusive_min), (Some(5.0), false));
+    }
+
+    #[test]
+    fn test_version_module_sets_import_prefix() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "idToken": { "$ref": "#/definitions/IdTokenType" },
+                "status": { "$ref": "#/definitions/AuthorizationStatusEnumType" }
             }
-            // 常见的枚举类型
-            "GenericStatusEnumType"
-            | "AuthorizeCertificateStatusEnumType"
-            | "EnergyTransferModeEnumType"
-            | "ResetEnumType"
-            | "ResetStatusEnumType"
-            | "MessageTriggerEnumType"
-            | "TriggerMessageStatusEnumType" => {
-                imports.insert(format!("use crate::v2_1::enumerations::{};", type_name));
-                (type_name.clone(), false)
+        });
+        let config = Config {
+            ocpp_version: "v1_6".to_string(),
+            ..Config::default()
+        };
+
+        let info = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        assert!(info
+            .imports
+            .contains("use crate::v1_6::datatypes::IdTokenType;"));
+        assert!(info
+            .imports
+            .contains("use crate::v1_6::enumerations::AuthorizationStatusEnumType;"));
+
+        // 缓存按版本区分，默认版本仍使用 v2_1
+        let info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        assert!(info
+            .imports
+            .contains("use crate::v2_1::datatypes::IdTokenType;"));
+    }
+
+    #[test]
+    fn test_field_order_ignores_braces_in_descriptions() {
+        let content = r#"{
+            "type": "object",
+            "properties": {
+                "zeta": { "type": "string", "description": "Payload like { \"nested\": true }" },
+                "alpha": { "type": "integer", "description": "Not a field: \"beta\": {" },
+                "mid": { "type": "boolean" }
+            },
+            "definitions": {
+                "OtherType": { "type": "object", "properties": { "other": { "type": "string" } } }
             }
-            _ => {
-                // For other types, try to determine if it's an enum or datatype
-                if type_name.ends_with("EnumType") {
-                    imports.insert(format!("use crate::v2_1::enumerations::{};", type_name));
-                    (type_name.clone(), false) // 枚举类型不需要 nested 验证
-                } else if type_name.ends_with("Type") {
-                    imports.insert(format!("use crate::v2_1::datatypes::{};", type_name));
-                    (type_name.clone(), true) // 数据类型需要 nested 验证
-                } else {
-                    (type_name.clone(), true)
-                }
+        }"#;
+        let schema = parse_schema_content(content, Path::new("Schema.json")).unwrap();
+
+        let info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        let names: Vec<&str> = info
+            .fields
+            .iter()
+            .map(|f| f.original_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["zeta", "alpha", "mid"]);
+    }
+
+    #[test]
+    fn test_integer_type_follows_schema_bounds() {
+        let cases = [
+            (serde_json::json!({ "type": "integer" }), "i32"),
+            (
+                serde_json::json!({ "type": "integer", "minimum": -5, "maximum": 5 }),
+                "i32",
+            ),
+            (
+                serde_json::json!({ "type": "integer", "minimum": 0 }),
+                "u32",
+            ),
+            (
+                serde_json::json!({ "type": "integer", "exclusiveMinimum": -1 }),
+                "u32",
+            ),
+            (
+                serde_json::json!({ "type": "integer", "minimum": 0, "maximum": 4294967296u64 }),
+                "u64",
+            ),
+            (
+                serde_json::json!({ "type": "integer", "minimum": 0, "format": "int64" }),
+                "u64",
+            ),
+            (
+                serde_json::json!({ "type": "integer", "maximum": 3000000000u64 }),
+                "i64",
+            ),
+            (
+                serde_json::json!({ "type": "integer", "minimum": -3000000000i64 }),
+                "i64",
+            ),
+            (
+                serde_json::json!({ "type": "integer", "format": "int64" }),
+                "i64",
+            ),
+        ];
+
+        for (schema, expected) in cases {
+            assert_eq!(integer_type(&schema), expected, "{}", schema);
+        }
+    }
+
+    #[test]
+    fn test_string_formats_map_to_types_and_validators() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "contact": { "type": "string", "format": "email" },
+                "endpoint": { "type": "string", "format": "uri" },
+                "session": { "type": "string", "format": "uuid" },
+                "v4": { "type": "string", "format": "ipv4" },
+                "v6": { "type": "string", "format": "ipv6" },
+                "other": { "type": "string", "format": "hostname", "maxLength": 50 }
             }
+        });
+
+        let plain = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        let types: Vec<_> = plain.fields.iter().map(|f| f.rust_type.as_str()).collect();
+        assert_eq!(
+            types,
+            ["String", "String", "String", "Ipv4Addr", "Ipv6Addr", "String"]
+        );
+        assert_eq!(plain.fields[0].string_format.as_deref(), Some("email"));
+        assert_eq!(plain.fields[1].string_format, None);
+        assert_eq!(plain.fields[5].string_format, None);
+        assert_eq!(plain.fields[5].max_length, Some(50));
+        assert!(plain.imports.contains("use std::net::Ipv4Addr;"));
+        assert!(!plain.imports.contains("use uuid::Uuid;"));
+
+        // uuid 与 url 依赖额外 crate，需要显式启用
+        let config = Config {
+            format_types: true,
+            ..Config::default()
         };
+        let typed = extract_struct_info(&schema, "FooRequest", &config).unwrap();
+        assert_eq!(typed.fields[1].string_format.as_deref(), Some("uri"));
+        assert_eq!(typed.fields[2].rust_type, "Uuid");
+        assert!(typed.imports.contains("use uuid::Uuid;"));
+    }
 
-        Ok((rust_type.to_string(), needs_validation))
-    } else {
-        Ok(("String".to_string(), true))
+    #[test]
+    fn test_nullable_type_arrays() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": ["integer", "null"] },
+                "labels": { "type": "array", "items": { "type": ["string", "null"] } },
+                "mixed": { "type": ["string", "integer", "null"] }
+            },
+            "required": ["count", "labels", "mixed"]
+        });
+
+        let struct_info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+
+        assert_eq!(struct_info.fields[0].rust_type, "i32");
+        assert!(struct_info.fields[0].is_optional);
+        assert!(struct_info.fields[0].required_nullable);
+        assert!(struct_info.fields[0].needs_validation);
+        assert_eq!(struct_info.fields[1].rust_type, "Vec<Option<String>>");
+        assert!(!struct_info.fields[1].is_optional);
+        assert_eq!(struct_info.fields[2].rust_type, "Value");
+        assert!(struct_info.fields[2].is_optional);
+
+        // 多类型联合同样受 --no-value-fallback 约束
+        let config = Config {
+            no_value_fallback: true,
+            ..Config::default()
+        };
+        let err = extract_struct_info(&schema, "FooRequest", &config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("union of types [\"string\",\"integer\"]"));
     }
-}
 
-/// 从原始JSON内容中提取properties字段的顺序
-fn extract_field_order_from_content(
-    content: &str,
-) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let mut field_order = Vec::new();
-
-    // 使用正则表达式方法来查找最后一个properties对象中的字段
-    // 首先找到所有"properties"的位置
-    let properties_positions: Vec<_> = content.match_indices("\"properties\"").collect();
-
-    if let Some((last_pos, _)) = properties_positions.last() {
-        // 从最后一个"properties"开始查找
-        let content_from_properties = &content[*last_pos..];
-
-        // 查找 "properties": { 的开始
-        if let Some(brace_start) = content_from_properties.find('{') {
-            let mut current_pos = brace_start + 1; // 跳过开始的 {
-            let chars: Vec<char> = content_from_properties.chars().collect();
-            let mut brace_count = 1; // 已经进入了一个大括号
-
-            while current_pos < chars.len() && brace_count > 0 {
-                match chars[current_pos] {
-                    '{' => brace_count += 1,
-                    '}' => brace_count -= 1,
-                    '"' if brace_count == 1 => {
-                        // 在properties对象的第一层找到字符串
-                        current_pos += 1; // 跳过开始引号
-                        let mut field_name = String::new();
-
-                        // 收集字段名
-                        while current_pos < chars.len() && chars[current_pos] != '"' {
-                            field_name.push(chars[current_pos]);
-                            current_pos += 1;
-                        }
+    #[test]
+    fn test_one_of_builds_union_info() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "token": {
+                    "oneOf": [
+                        { "$ref": "#/definitions/IdTokenType" },
+                        { "type": "string", "maxLength": 36 }
+                    ]
+                },
+                "limits": {
+                    "type": "array",
+                    "items": { "anyOf": [{ "type": "integer" }, { "type": "number" }] }
+                }
+            },
+            "required": ["token"]
+        });
 
-                        if current_pos < chars.len() && chars[current_pos] == '"' {
-                            current_pos += 1; // 跳过结束引号
+        let struct_info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
 
-                            // 跳过空白字符
-                            while current_pos < chars.len() && chars[current_pos].is_whitespace() {
-                                current_pos += 1;
-                            }
+        let token = &struct_info.fields[0];
+        assert_eq!(token.rust_type, "FooRequestTokenUnionType");
+        assert!(token.needs_validation);
+        assert_eq!(
+            token.union.as_ref().unwrap().variants,
+            vec![
+                ("IdToken".to_string(), "IdTokenType".to_string()),
+                ("String".to_string(), "String".to_string()),
+            ]
+        );
+        // 标量变体保留自身的约束，数据类型变体由其 validate() 负责
+        let constraints = &token.union.as_ref().unwrap().constraints;
+        assert_eq!(constraints[0], None);
+        assert_eq!(constraints[1].as_ref().unwrap().max_length, Some(36));
+        assert!(struct_info
+            .imports
+            .contains("use crate::v2_1::datatypes::IdTokenType;"));
+        // 字符串分支没有判别属性，保持 untagged
+        assert_eq!(token.union.as_ref().unwrap().tag, None);
+
+        let limits = &struct_info.fields[1];
+        assert_eq!(limits.rust_type, "Vec<FooRequestLimitsUnionType>");
+        assert_eq!(
+            limits.union.as_ref().unwrap().variants,
+            vec![
+                ("I32".to_string(), "i32".to_string()),
+                ("Decimal".to_string(), "Decimal".to_string()),
+            ]
+        );
+    }
 
-                            // 检查是否是字段定义（有冒号）
-                            if current_pos < chars.len() && chars[current_pos] == ':' {
-                                field_order.push(field_name);
-                            }
+    #[test]
+    fn test_one_of_detects_shared_const_discriminator() {
+        let branch = |kind: &str, required: serde_json::Value| {
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "kind": { "type": "string", "const": kind },
+                    "value": { "type": "integer" }
+                },
+                "required": required
+            })
+        };
+        let union_of = |a: serde_json::Value, b: serde_json::Value| {
+            let schema = serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "event": {
+                        "oneOf": [
+                            { "$ref": "#/definitions/StartedType" },
+                            { "$ref": "#/definitions/EndedType" }
+                        ]
+                    }
+                },
+                "required": ["event"],
+                "definitions": { "StartedType": a, "EndedType": b }
+            });
+            let struct_info =
+                extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+            struct_info.fields[0].union.clone().unwrap()
+        };
+
+        let union = union_of(
+            branch("Started", serde_json::json!(["kind"])),
+            branch("Ended", serde_json::json!(["kind", "value"])),
+        );
+        assert_eq!(union.tag.as_deref(), Some("kind"));
+        assert_eq!(union.tag_values, vec!["Started", "Ended"]);
+
+        // 相同的 const 值或可选的判别属性都无法区分分支
+        let union = union_of(
+            branch("Started", serde_json::json!(["kind"])),
+            branch("Started", serde_json::json!(["kind"])),
+        );
+        assert_eq!((union.tag, union.tag_values), (None, Vec::new()));
+        let union = union_of(
+            branch("Started", serde_json::json!(["kind"])),
+            branch("Ended", serde_json::json!(["value"])),
+        );
+        assert_eq!(union.tag, None);
+    }
+
+    #[test]
+    fn test_inline_objects_become_nested_structs() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "chargingSchedule": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "period": {
+                            "type": "object",
+                            "properties": { "limit": { "type": "number" } },
+                            "required": ["limit"]
                         }
+                    },
+                    "required": ["id"]
+                },
+                "chargingSchedulePeriod": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "startPeriod": { "type": "integer" } }
                     }
-                    _ => {}
-                }
-                current_pos += 1;
+                },
+                "payload": { "type": "object" }
+            },
+            "required": ["chargingSchedule"]
+        });
+
+        let struct_info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+
+        let schedule = &struct_info.fields[0];
+        assert_eq!(schedule.rust_type, "FooRequestChargingScheduleType");
+        assert!(schedule.needs_validation);
+        let nested = schedule.inline_struct.as_ref().unwrap();
+        assert_eq!(nested.name, "FooRequestChargingScheduleType");
+        assert_eq!(nested.fields[0].rust_type, "i32");
+        assert!(!nested.fields[0].is_optional);
+        assert_eq!(
+            nested.fields[1].rust_type,
+            "FooRequestChargingSchedulePeriodType"
+        );
+        assert!(nested.fields[1].inline_struct.is_some());
+        assert!(struct_info.imports.contains("use rust_decimal::Decimal;"));
+
+        // 与上面嵌套生成的名称冲突时追加序号
+        let periods = &struct_info.fields[1];
+        assert_eq!(
+            periods.rust_type,
+            "Vec<FooRequestChargingSchedulePeriod2Type>"
+        );
+        assert_eq!(
+            periods.inline_struct.as_ref().unwrap().name,
+            "FooRequestChargingSchedulePeriod2Type"
+        );
+
+        // 没有 properties 的 object 仍回退为 Value
+        assert_eq!(struct_info.fields[2].rust_type, "Value");
+        assert!(struct_info.fields[2].inline_struct.is_none());
+    }
+
+    #[test]
+    fn test_additional_properties_map_to_hash_map() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "tokens": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/IdTokenType" }
+                },
+                "counters": { "type": "object", "additionalProperties": { "type": "integer" } },
+                "extra": { "type": "object", "additionalProperties": {} },
+                "open": { "type": "object", "additionalProperties": true }
             }
-        }
+        });
+
+        let struct_info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        let types: Vec<_> = struct_info
+            .fields
+            .iter()
+            .map(|f| f.rust_type.as_str())
+            .collect();
+
+        assert_eq!(
+            types,
+            [
+                "HashMap<String, IdTokenType>",
+                "HashMap<String, i32>",
+                "HashMap<String, Value>",
+                "Value"
+            ]
+        );
+        assert!(struct_info
+            .imports
+            .contains("use std::collections::HashMap;"));
+        assert!(struct_info
+            .imports
+            .contains("use crate::v2_1::datatypes::IdTokenType;"));
+    }
+
+    #[test]
+    fn test_schema_default_is_kept_for_optional_fields_only() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "mode": { "type": "string", "default": "auto" },
+                "count": { "type": "integer", "default": 1 }
+            },
+            "required": ["count"]
+        });
+        let info = extract_struct_info(&schema, "FooRequest", &Config::default()).unwrap();
+        assert_eq!(
+            info.fields[0].schema_default,
+            Some(serde_json::json!("auto"))
+        );
+        assert_eq!(info.fields[1].schema_default, None);
+    }
+
+    #[test]
+    fn test_local_definitions_generate_in_message_file() {
+        let schema = serde_json::json!({
+            "definitions": {
+                "PhaseEnumType": { "type": "string", "enum": ["L1", "L2"] },
+                "IdTokenType": {
+                    "type": "object",
+                    "properties": { "idToken": { "type": "string", "maxLength": 36 } },
+                    "required": ["idToken"]
+                },
+                "NodeType": {
+                    "type": "object",
+                    "properties": {
+                        "parent": { "$ref": "#/definitions/NodeType" },
+                        "children": { "type": "array", "items": { "$ref": "#/definitions/NodeType" } }
+                    }
+                },
+                "CiString20Type": { "type": "string", "maxLength": 20 }
+            },
+            "type": "object",
+            "properties": {
+                "idToken": { "$ref": "#/definitions/IdTokenType" },
+                "groupIdToken": { "$ref": "#/definitions/IdTokenType" },
+                "phase": { "$ref": "#/definitions/PhaseEnumType" },
+                "tree": { "$ref": "#/definitions/NodeType" },
+                "label": { "$ref": "#/definitions/CiString20Type" },
+                "customData": { "$ref": "#/definitions/CustomDataType" }
+            },
+            "required": ["idToken"]
+        });
+        let config = Config {
+            local_definitions: true,
+            ..Config::default()
+        };
+
+        let struct_info = extract_struct_info(&schema, "AuthorizeRequest", &config).unwrap();
+        let fields = &struct_info.fields;
+
+        // 同一定义只在首次引用处生成，不改名
+        let id_token = fields[0].inline_struct.as_ref().unwrap();
+        assert_eq!(id_token.name, "IdTokenType");
+        assert!(!id_token.emitted_elsewhere);
+        assert_eq!(fields[1].rust_type, "IdTokenType");
+        assert!(fields[1].inline_struct.as_ref().unwrap().emitted_elsewhere);
+        assert_eq!(
+            fields[2].inline_enum.as_ref().unwrap().name,
+            "PhaseEnumType"
+        );
+        // 递归引用以 Box 打断
+        let node = fields[3].inline_struct.as_ref().unwrap();
+        assert_eq!(node.fields[0].rust_type, "Box<NodeType>");
+        assert!(node.fields[0].needs_validation);
+        // 数组本身已提供间接层
+        assert_eq!(node.fields[1].rust_type, "Vec<NodeType>");
+        // 标量定义展开为其类型与约束
+        assert_eq!(fields[4].rust_type, "String");
+        assert_eq!(fields[4].max_length, Some(20));
+        // 本文件中不存在的定义仍然导入
+        assert!(struct_info
+            .imports
+            .contains("use crate::v2_1::datatypes::CustomDataType;"));
+        assert!(!struct_info
+            .imports
+            .iter()
+            .any(|import| import.contains("IdTokenType") || import.contains("CiString20Type")));
+
+        let imported =
+            extract_struct_info(&schema, "AuthorizeRequest", &Config::default()).unwrap();
+        assert!(imported.fields[0].inline_struct.is_none());
+        assert!(imported
+            .imports
+            .contains("use crate::v2_1::datatypes::IdTokenType;"));
     }
 
-    Ok(field_order)
+    #[test]
+    fn test_type_map_overrides_builtin_ref_resolution() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("types.json");
+        fs::write(
+            &path,
+            r#"{
+                "IdTokenType": { "import": "crate::tokens::IdTokenType" },
+                "EventDataType": { "rust_type": "EventData", "import": "use crate::events::EventData;", "needs_validation": true },
+                "CustomDataType": { "rust_type": "Value", "import": "serde_json::Value", "needs_validation": false }
+            }"#,
+        )
+        .unwrap();
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "idToken": { "$ref": "#/definitions/IdTokenType" },
+                "event": { "$ref": "#/definitions/EventDataType" },
+                "customData": { "$ref": "#/definitions/CustomDataType" },
+                "status": { "$ref": "#/definitions/GenericStatusEnumType" }
+            }
+        });
+        let config = Config {
+            type_map: Some(path.to_string_lossy().into_owned()),
+            ..Config::default()
+        };
+
+        let struct_info = extract_struct_info(&schema, "AuthorizeRequest", &config).unwrap();
+        let fields = &struct_info.fields;
+
+        // 只映射 import 时沿用内置的类型与验证规则
+        assert_eq!(fields[0].rust_type, "IdTokenType");
+        assert!(fields[0].needs_validation);
+        assert_eq!(fields[1].rust_type, "EventData");
+        assert!(fields[1].needs_validation);
+        assert_eq!(fields[2].rust_type, "Value");
+        assert!(!fields[2].needs_validation);
+        assert!(struct_info
+            .imports
+            .contains("use crate::tokens::IdTokenType;"));
+        assert!(struct_info
+            .imports
+            .contains("use crate::events::EventData;"));
+        assert!(struct_info.imports.contains("use serde_json::Value;"));
+        assert!(!struct_info
+            .imports
+            .iter()
+            .any(|import| import.contains("datatypes")));
+        // 未映射的类型仍按内置规则解析
+        assert!(struct_info
+            .imports
+            .contains("use crate::v2_1::enumerations::GenericStatusEnumType;"));
+    }
+
+    #[test]
+    fn test_const_without_type_is_typed_from_its_value() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "kind": { "const": "Heartbeat" },
+                "enabled": { "const": true },
+                "version": { "const": 2 }
+            },
+            "required": ["kind", "enabled", "version"]
+        });
+        let info = extract_struct_info(&schema, "PingRequest", &Config::default()).unwrap();
+
+        let types: Vec<&str> = info.fields.iter().map(|f| f.rust_type.as_str()).collect();
+        assert_eq!(types, ["String", "bool", "i32"]);
+        assert_eq!(
+            info.fields[0].const_value,
+            Some(serde_json::json!("Heartbeat"))
+        );
+        assert!(info.fields.iter().all(|f| f.needs_validation));
+    }
 }
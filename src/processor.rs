@@ -1,11 +1,26 @@
-use std::collections::HashMap;
+use convert_case::{Case, Casing};
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 use crate::config::Config;
-use crate::generator::{generate_mod_file, generate_paired_file};
-use crate::parser::{extract_struct_info_from_file, parse_message_type};
-use crate::types::MessagePair;
+use crate::error::GenError;
+use crate::generator::{
+    generate_action_registry, generate_constants_module, generate_errors_module, generate_mod_file,
+    generate_paired_file, generate_shared_file, generate_single_file, generate_validate_dispatch,
+};
+use crate::manifest::Manifest;
+use crate::parser::{
+    extract_enum_infos_from_definitions, extract_struct_info,
+    extract_struct_infos_from_definitions, extract_struct_infos_from_openapi,
+    is_definitions_library, is_schema_extension, load_schema_file, normalize_schema_name,
+    parse_message_type,
+};
+use crate::shared::collect_shared_types;
+use crate::types::{InlineEnumInfo, MessagePair, SharedTypes, StructInfo};
 
 /// 主要的处理器结构
 pub struct SchemaProcessor {
@@ -19,85 +34,524 @@ impl SchemaProcessor {
     }
 
     /// 处理所有 schema 文件
-    pub fn process_all(&self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn process_all(&self) -> Result<(), GenError> {
         // Validate configuration
-        self.config.validate()?;
+        self.config.validate().map_err(GenError::InvalidConfig)?;
 
         // Create output directory
-        fs::create_dir_all(&self.config.output_dir)?;
+        if !self.config.dry_run {
+            fs::create_dir_all(&self.config.output_dir)
+                .map_err(GenError::io(&self.config.output_dir))?;
+        }
+
+        // --force 时忽略已有清单，重写全部文件；--dry-run 时只与磁盘上的文件比较
+        let manifest = if self.config.dry_run {
+            Manifest::dry_run()
+        } else if self.config.force {
+            Manifest::default()
+        } else {
+            Manifest::load(Path::new(&self.config.output_dir))
+        };
+        let (mut written_files, mut skipped_files) = (0, 0);
+        let mut tally = |written: bool| {
+            if written {
+                written_files += 1;
+            } else {
+                skipped_files += 1;
+            }
+            written
+        };
 
-        // Collect all JSON files and group them by base name
-        let message_pairs = self.collect_message_pairs()?;
+        // Collect all schema files (JSON/YAML) and group them by base name
+        let (mut message_pairs, mut failures) = self.collect_message_pairs()?;
 
-        // Generate paired files and standalone messages
+        // Inline helper types repeated across messages move to shared.rs
+        let shared = if self.config.share_helpers {
+            collect_shared_types(&mut message_pairs)
+        } else {
+            SharedTypes::default()
+        };
+        let mut message_pairs: Vec<&MessagePair> = message_pairs.values().collect();
+        message_pairs.sort_by(|a, b| a.base_name.cmp(&b.base_name));
+
+        // 预演模式下的输出只描述将要发生的变化
+        let generated = if self.config.dry_run {
+            "Would generate"
+        } else {
+            "Generated"
+        };
+
+        // Classify paired files and standalone messages
         let mut generated_pairs = Vec::new();
         let mut standalone_messages = Vec::new();
-        for (base_name, pair) in &message_pairs {
-            if pair.is_complete() {
-                generate_paired_file(pair, &self.config.output_dir)?;
-                generated_pairs.push(base_name.clone());
-                println!("Generated: {}", base_name);
+        let mut to_generate = Vec::new();
+        for pair in message_pairs {
+            let label = if pair.is_complete() {
+                generated_pairs.push(pair);
+                generated.to_string()
+            } else if pair.has_definitions() {
+                // Generate data types from a definitions-only schema
+                standalone_messages.push(pair);
+                format!("{} definitions", generated)
+            } else if self.config.strict && Self::is_half_pair(pair) {
+                return Err(GenError::MissingPair {
+                    base_name: pair.base_name.clone(),
+                });
             } else if pair.has_standalone_message() {
                 // Generate standalone message
-                generate_paired_file(pair, &self.config.output_dir)?;
-                standalone_messages.push(base_name.clone());
-                println!("Generated standalone: {}", base_name);
+                standalone_messages.push(pair);
+                format!("{} standalone", generated)
+            } else {
+                log::warn!("Incomplete pair for {}", pair.base_name);
+                tracing::warn!(base_name = %pair.base_name, "incomplete message pair");
+                continue;
+            };
+            to_generate.push((pair, label));
+        }
+
+        // Each pair writes its own file, so generation runs in parallel;
+        // results are reported in base-name order. In single-file mode all
+        // pairs are written into messages.rs below instead
+        let results = if self.config.single_file {
+            Vec::new()
+        } else {
+            self.map_jobs(&to_generate, |(pair, _)| {
+                generate_paired_file(pair, &self.config, &manifest)
+            })?
+        };
+        let mut failed_pairs = HashSet::new();
+        for ((pair, label), written) in to_generate.iter().zip(results) {
+            match written.map(&mut tally) {
+                Ok(true) => log::info!("{}: {}", label, pair.base_name),
+                Ok(false) if self.config.append_only => {
+                    log::info!("Skipped existing: {}", pair.base_name)
+                }
+                Ok(false) => log::info!("Unchanged: {}", pair.base_name),
+                Err(e) if self.config.fail_fast => return Err(e),
+                Err(e) => {
+                    let path = Path::new(&self.config.output_dir)
+                        .join(format!("{}.rs", pair.base_name.to_case(Case::Snake)));
+                    log::warn!("skipping {}", describe_failure(&path, &e));
+                    tracing::warn!(base_name = %pair.base_name, error = %e, "generation failed");
+                    failed_pairs.insert(pair.base_name.as_str());
+                    failures.push((path, e));
+                }
+            }
+        }
+
+        // 生成失败的消息不写入 mod.rs 等汇总文件
+        generated_pairs.retain(|pair| !failed_pairs.contains(pair.base_name.as_str()));
+        standalone_messages.retain(|pair| !failed_pairs.contains(pair.base_name.as_str()));
+
+        let all_messages: Vec<&MessagePair> = generated_pairs
+            .iter()
+            .chain(standalone_messages.iter())
+            .copied()
+            .collect();
+
+        // Generate the combined messages.rs in single-file mode
+        if self.config.single_file {
+            if tally(generate_single_file(
+                &all_messages,
+                &shared,
+                &self.config,
+                &manifest,
+            )?) {
+                log::info!("{} messages.rs file", generated);
+            } else {
+                log::info!("Unchanged messages.rs file");
+            }
+        }
+
+        // Generate shared.rs with the deduplicated helper types (merged into messages.rs in single-file mode)
+        if self.config.share_helpers && !self.config.single_file {
+            if tally(generate_shared_file(&shared, &self.config, &manifest)?) {
+                log::info!("{} shared.rs file", generated);
+            } else {
+                log::info!("Unchanged shared.rs file");
+            }
+        }
+
+        // Generate the shared constants module if enabled
+        if self.config.emit_constants_module {
+            if tally(generate_constants_module(
+                &all_messages,
+                &self.config,
+                &manifest,
+            )?) {
+                log::info!("{} constants.rs file", generated);
+            } else {
+                log::info!("Unchanged constants.rs file");
+            }
+        }
+
+        // Generate the action-based validation dispatcher if enabled
+        if self.config.emit_validate_dispatch {
+            if tally(generate_validate_dispatch(
+                &all_messages,
+                &self.config,
+                &manifest,
+            )?) {
+                log::info!("{} dispatch.rs file", generated);
+            } else {
+                log::info!("Unchanged dispatch.rs file");
+            }
+        }
+
+        // Generate the error type shared by the TryFrom<serde_json::Value> impls (inlined in messages.rs in single-file mode)
+        if self.config.try_from_value && !self.config.single_file {
+            if tally(generate_errors_module(&self.config, &manifest)?) {
+                log::info!("{} errors.rs file", generated);
+            } else {
+                log::info!("Unchanged errors.rs file");
+            }
+        }
+
+        // Generate the action registry if enabled
+        if self.config.emit_action_registry {
+            if tally(generate_action_registry(
+                &all_messages,
+                &self.config,
+                &manifest,
+            )?) {
+                log::info!("{} actions.rs file", generated);
+            } else {
+                log::info!("Unchanged actions.rs file");
+            }
+        }
+
+        // Generate mod.rs file if enabled (messages.rs is included directly in single-file mode)
+        if self.config.generate_mod_file && !self.config.single_file {
+            if tally(generate_mod_file(&all_messages, &self.config, &manifest)?) {
+                log::info!("{} mod.rs file", generated);
             } else {
-                println!("Warning: Incomplete pair for {}", base_name);
+                log::info!("Unchanged mod.rs file");
             }
         }
 
-        // Generate mod.rs file if enabled
-        if self.config.generate_mod_file {
-            generate_mod_file(
-                &generated_pairs,
-                &standalone_messages,
-                &self.config.output_dir,
-            )?;
-            println!("Generated mod.rs file");
+        manifest.save(Path::new(&self.config.output_dir))?;
+
+        log::info!("Paired schema processing completed!");
+        log::info!("{} {} message pairs", generated, generated_pairs.len());
+        if self.config.dry_run {
+            log::info!(
+                "{} files would change, {} up to date",
+                written_files,
+                skipped_files
+            );
+        } else {
+            log::info!(
+                "Wrote {} files, skipped {} unchanged",
+                written_files,
+                skipped_files
+            );
+        }
+
+        if !failures.is_empty() {
+            let lines: Vec<String> = failures
+                .iter()
+                .map(|(path, error)| format!("  {}", describe_failure(path, error)))
+                .collect();
+            log::error!("{} file(s) failed:\n{}", failures.len(), lines.join("\n"));
+            return Err(GenError::Failures { failures });
         }
 
-        println!("Paired schema processing completed!");
-        println!("Generated {} message pairs", generated_pairs.len());
+        // 预演模式下有文件需要更新时返回错误，便于在 CI 中检查生成代码是否最新
+        let changes = manifest.changes();
+        if !changes.is_empty() {
+            for (path, diff) in &changes {
+                println!();
+                println!("Would update {}", path.display());
+                print!("{}", diff);
+            }
+            return Err(GenError::OutOfDate {
+                files: changes.into_iter().map(|(path, _)| path).collect(),
+            });
+        }
         Ok(())
     }
 
-    /// 收集所有消息对
-    fn collect_message_pairs(
-        &self,
-    ) -> Result<HashMap<String, MessagePair>, Box<dyn std::error::Error>> {
+    /// 收集所有消息对，同时返回被跳过的 schema 文件及其错误
+    ///
+    /// 启用 --fail-fast 时遇到第一个失败的文件即返回错误。
+    fn collect_message_pairs(&self) -> Result<(HashMap<String, MessagePair>, Failures), GenError> {
         let mut message_pairs: HashMap<String, MessagePair> = HashMap::new();
+        let renames = self.config.struct_renames()?;
 
+        // OpenAPI 模式：从 components.schemas 收集
+        if let Some(openapi) = &self.config.openapi {
+            for struct_info in extract_struct_infos_from_openapi(Path::new(openapi), &self.config)?
+            {
+                let (base_name, is_request) = parse_message_type(&struct_info.name);
+                let struct_info = Self::apply_rename(struct_info, &renames);
+                Self::add_to_pair(&mut message_pairs, base_name, is_request, struct_info, None);
+            }
+            for pair in message_pairs.values_mut() {
+                pair.sources = vec![openapi.clone()];
+            }
+            return Ok((message_pairs, Vec::new()));
+        }
+
+        let strip_suffix = self.config.strip_suffix_regex().map_err(|e| {
+            GenError::InvalidConfig(format!("Invalid --strip-suffix pattern: {}", e))
+        })?;
+
+        let (include, exclude) = self.config.schema_filters().map_err(|e| {
+            GenError::InvalidConfig(format!("Invalid --include/--exclude pattern: {}", e))
+        })?;
+
+        let (mut paths, mut candidates) = (Vec::new(), 0);
         for entry in WalkDir::new(&self.config.schema_dir) {
-            let entry = entry?;
+            let entry = entry.map_err(|e| {
+                let path = e
+                    .path()
+                    .unwrap_or(Path::new(&self.config.schema_dir))
+                    .to_path_buf();
+                GenError::io(path)(e.into())
+            })?;
             if entry.file_type().is_file() {
-                if let Some(extension) = entry.path().extension() {
-                    if extension == "json" {
-                        let filename = entry.path().file_stem().unwrap().to_str().unwrap();
-                        let (base_name, is_request) = parse_message_type(filename);
-
-                        let struct_info = extract_struct_info_from_file(entry.path(), filename)?;
+                if let Some(extension) = entry.path().extension().and_then(|e| e.to_str()) {
+                    if is_schema_extension(extension) {
+                        candidates += 1;
+                        // 按相对于 schema 目录的路径匹配，exclude 优先
+                        let relative = entry
+                            .path()
+                            .strip_prefix(&self.config.schema_dir)
+                            .unwrap_or(entry.path());
+                        if include.as_ref().is_none_or(|set| set.is_match(relative))
+                            && !exclude.is_match(relative)
+                        {
+                            paths.push(entry.into_path());
+                        }
+                    }
+                }
+            }
+        }
+        // 按路径排序，使合并结果不依赖目录遍历与线程调度顺序
+        paths.sort();
+        if include.is_some() || !exclude.is_empty() {
+            log::info!("Matched {} of {} schema files", paths.len(), candidates);
+        }
 
-                        let pair = message_pairs
-                            .entry(base_name.clone())
-                            .or_insert_with(|| MessagePair::new(base_name.clone()));
+        // 各文件独立解析，可并行执行；合并按路径顺序进行
+        let parsed = self.map_jobs(&paths, |path| {
+            let file_stem = path.file_stem().unwrap().to_str().unwrap();
+            let filename = normalize_schema_name(file_stem, strip_suffix.as_ref());
+            let _span = tracing::info_span!("parse_schema", path = %path.display()).entered();
+            log::debug!("Parsing {}", path.display());
+            self.parse_schema_file(path, filename)
+                .map_err(|e| e.in_file(path))
+        })?;
 
-                        if is_request {
-                            pair.add_request(struct_info);
+        let mut failures = Vec::new();
+        // 已占用的请求/响应位置及其来源文件，用于发现不同目录中的同名 schema
+        let mut sources: HashMap<(String, bool), PathBuf> = HashMap::new();
+        for (path, schema_file) in paths.into_iter().zip(parsed) {
+            let schema_file = match schema_file {
+                Ok(schema_file) => schema_file,
+                Err(e) if self.config.fail_fast => return Err(e),
+                Err(e) => {
+                    log::warn!("skipping {}", describe_failure(&path, &e));
+                    tracing::warn!(path = %path.display(), error = %e, "skipping schema file");
+                    failures.push((path, e));
+                    continue;
+                }
+            };
+            match schema_file {
+                // 仅含 definitions 的文件：所有类型输出到以文件命名的模块
+                ParsedSchema::Definitions {
+                    filename,
+                    feature,
+                    definitions,
+                    enums,
+                } => {
+                    let pair = message_pairs
+                        .entry(filename.clone())
+                        .or_insert_with(|| MessagePair::new(filename));
+                    pair.feature = pair.feature.take().or(feature);
+                    pair.sources
+                        .push(Self::source_name(&path, &self.config.schema_dir));
+                    for struct_info in definitions {
+                        pair.add_definition(Self::apply_rename(struct_info, &renames));
+                    }
+                    for enum_info in enums {
+                        pair.add_definition_enum(enum_info);
+                    }
+                }
+                ParsedSchema::Message {
+                    base_name,
+                    is_request,
+                    struct_info,
+                    feature,
+                } => {
+                    let slot = (base_name.clone(), is_request);
+                    if let Some(first) = sources.get(&slot) {
+                        let e = GenError::DuplicateMessage {
+                            name: struct_info.name.clone(),
+                            first: first.clone(),
+                            second: path.clone(),
+                        };
+                        if self.config.allow_duplicates {
+                            // 保留原有的后者覆盖前者行为
+                            log::warn!("{}; using the latter", e);
+                            tracing::warn!(error = %e, "duplicate message schema");
+                        } else if self.config.fail_fast {
+                            return Err(e);
                         } else {
-                            pair.add_response(struct_info);
+                            log::warn!("skipping {}", describe_failure(&path, &e));
+                            tracing::warn!(path = %path.display(), error = %e, "skipping schema file");
+                            failures.push((path, e));
+                            continue;
                         }
                     }
+                    sources.insert(slot, path);
+
+                    let struct_info = Self::apply_rename(struct_info, &renames);
+                    Self::add_to_pair(
+                        &mut message_pairs,
+                        base_name,
+                        is_request,
+                        struct_info,
+                        feature,
+                    );
                 }
             }
         }
 
-        Ok(message_pairs)
+        // 同名 schema 以后者为准时，只记录实际使用的文件
+        for ((base_name, _), path) in sources {
+            if let Some(pair) = message_pairs.get_mut(&base_name) {
+                pair.sources
+                    .push(Self::source_name(&path, &self.config.schema_dir));
+            }
+        }
+        for pair in message_pairs.values_mut() {
+            pair.sources.sort();
+        }
+
+        Ok((message_pairs, failures))
+    }
+
+    /// 文件头中的来源名称：相对 schema 目录的路径，统一使用 `/` 分隔
+    fn source_name(path: &Path, schema_dir: &str) -> String {
+        let relative = path.strip_prefix(schema_dir).unwrap_or(path);
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// 解析单个 schema 文件（不修改共享状态，可在线程池中并行调用）
+    fn parse_schema_file(&self, path: &Path, filename: String) -> Result<ParsedSchema, GenError> {
+        let schema = load_schema_file(path)?;
+        let feature = Self::feature_for_path(path, &self.config.schema_dir);
+
+        if is_definitions_library(&schema) {
+            return Ok(ParsedSchema::Definitions {
+                definitions: extract_struct_infos_from_definitions(&schema, &self.config)?,
+                enums: extract_enum_infos_from_definitions(&schema),
+                filename,
+                feature,
+            });
+        }
+
+        let (base_name, is_request) = parse_message_type(&filename);
+        let struct_info = extract_struct_info(&schema, &filename, &self.config)?;
+        Ok(ParsedSchema::Message {
+            base_name,
+            is_request,
+            struct_info,
+            feature,
+        })
+    }
+
+    /// 按 --jobs 将 `f` 应用于每一项，结果保持输入顺序
+    ///
+    /// jobs 为 1 时在当前线程顺序执行；0 表示使用全部 CPU 核心。
+    fn map_jobs<T, R, F>(&self, items: &[T], f: F) -> Result<Vec<R>, GenError>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync + Send,
+    {
+        if self.config.jobs == 1 {
+            return Ok(items.iter().map(f).collect());
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.config.jobs)
+            .build()
+            .map_err(|e| {
+                GenError::InvalidConfig(format!(
+                    "cannot start {} worker threads: {}",
+                    self.config.jobs, e
+                ))
+            })?;
+        Ok(pool.install(|| items.par_iter().map(f).collect()))
+    }
+
+    /// 按 --rename-struct 映射替换结构体名（优先于文件名推导的名称）
+    fn apply_rename(mut struct_info: StructInfo, renames: &HashMap<String, String>) -> StructInfo {
+        if let Some(rust_name) = renames.get(&struct_info.name) {
+            // 自引用字段（如 Box<Self>）中的类型名一并替换
+            let old_name = Regex::new(&format!(r"\b{}\b", regex::escape(&struct_info.name)))
+                .expect("escaped struct name is a valid pattern");
+            for field in &mut struct_info.fields {
+                field.rust_type = old_name
+                    .replace_all(&field.rust_type, rust_name.as_str())
+                    .into_owned();
+            }
+            struct_info.name = rust_name.clone();
+        }
+        struct_info
+    }
+
+    /// 由 schema 相对路径的顶层子目录推导 feature 名（如 SmartCharging => smart_charging）
+    fn feature_for_path(path: &Path, schema_dir: &str) -> Option<String> {
+        let relative = path.strip_prefix(schema_dir).ok()?;
+        let mut components = relative.components();
+        let top = components.next()?;
+        // 直接位于 schema 根目录的文件不属于任何功能块
+        components.next()?;
+        Some(top.as_os_str().to_str()?.to_case(Case::Snake))
+    }
+
+    /// 判断消息对是否只有请求或只有带 `Response` 后缀的响应（无后缀的独立消息除外）
+    fn is_half_pair(pair: &MessagePair) -> bool {
+        match (&pair.request, &pair.response) {
+            (Some(_), None) => true,
+            (None, Some(response)) => response.name.ends_with("Response"),
+            _ => false,
+        }
+    }
+
+    /// 将结构体加入对应的消息对
+    fn add_to_pair(
+        message_pairs: &mut HashMap<String, MessagePair>,
+        base_name: String,
+        is_request: bool,
+        struct_info: StructInfo,
+        feature: Option<String>,
+    ) {
+        let pair = message_pairs
+            .entry(base_name.clone())
+            .or_insert_with(|| MessagePair::new(base_name));
+
+        if pair.feature.is_none() {
+            pair.feature = feature;
+        }
+
+        if is_request {
+            pair.add_request(struct_info);
+        } else {
+            pair.add_response(struct_info);
+        }
     }
 
     /// 获取统计信息
-    pub fn get_stats(&self) -> Result<ProcessorStats, Box<dyn std::error::Error>> {
-        let message_pairs = self.collect_message_pairs()?;
+    pub fn get_stats(&self) -> Result<ProcessorStats, GenError> {
+        let (message_pairs, failures) = self.collect_message_pairs()?;
         let complete_pairs = message_pairs.values().filter(|p| p.is_complete()).count();
         let incomplete_pairs = message_pairs.len() - complete_pairs;
 
@@ -105,23 +559,849 @@ impl SchemaProcessor {
             total_pairs: message_pairs.len(),
             complete_pairs,
             incomplete_pairs,
+            failed_files: failures.len(),
         })
     }
 }
 
+/// 处理失败的文件及其错误
+type Failures = Vec<(PathBuf, GenError)>;
+
+/// 格式化失败文件及原因，错误信息已包含该路径时不再重复
+fn describe_failure(path: &Path, error: &GenError) -> String {
+    if error.file() == Some(path) {
+        error.to_string()
+    } else {
+        format!("{}: {}", path.display(), error)
+    }
+}
+
+/// 单个 schema 文件的解析结果
+enum ParsedSchema {
+    Definitions {
+        filename: String,
+        feature: Option<String>,
+        definitions: Vec<StructInfo>,
+        enums: Vec<InlineEnumInfo>,
+    },
+    Message {
+        base_name: String,
+        is_request: bool,
+        struct_info: StructInfo,
+        feature: Option<String>,
+    },
+}
+
 /// 处理器统计信息
 #[derive(Debug)]
 pub struct ProcessorStats {
     pub total_pairs: usize,
     pub complete_pairs: usize,
     pub incomplete_pairs: usize,
+    pub failed_files: usize,
 }
 
 impl ProcessorStats {
+    /// 以 info 级别输出统计信息
     pub fn print(&self) {
-        println!("Schema Processing Statistics:");
-        println!("  Total message pairs: {}", self.total_pairs);
-        println!("  Complete pairs: {}", self.complete_pairs);
-        println!("  Incomplete pairs: {}", self.incomplete_pairs);
+        log::info!("Schema Processing Statistics:");
+        log::info!("  Total message pairs: {}", self.total_pairs);
+        log::info!("  Complete pairs: {}", self.complete_pairs);
+        log::info!("  Incomplete pairs: {}", self.incomplete_pairs);
+        log::info!("  Failed files: {}", self.failed_files);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_schema(dir: &std::path::Path, filename: &str, content: &str) {
+        fs::write(dir.join(filename), content).unwrap();
+    }
+
+    const SIMPLE_SCHEMA: &str = r#"{
+        "type": "object",
+        "properties": { "idTag": { "type": "string", "maxLength": 20 } },
+        "required": ["idTag"]
+    }"#;
+
+    #[test]
+    fn test_openapi_components_are_grouped_into_pairs() {
+        let dir = tempfile::tempdir().unwrap();
+        write_schema(
+            dir.path(),
+            "openapi.json",
+            r##"{
+                "openapi": "3.0.0",
+                "info": { "title": "OCPP", "version": "1.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "HeartbeatRequest": { "type": "object", "properties": {} },
+                        "HeartbeatResponse": {
+                            "type": "object",
+                            "properties": {
+                                "currentTime": { "type": "string", "format": "date-time" },
+                                "status": { "$ref": "#/components/schemas/GenericStatusEnumType" }
+                            },
+                            "required": ["currentTime"]
+                        }
+                    }
+                }
+            }"##,
+        );
+
+        let config = Config {
+            openapi: Some(
+                dir.path()
+                    .join("openapi.json")
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+            ..Config::default()
+        };
+        let (pairs, _) = SchemaProcessor::new(config)
+            .collect_message_pairs()
+            .unwrap();
+
+        assert_eq!(pairs.len(), 1);
+        let pair = &pairs["Heartbeat"];
+        assert!(pair.is_complete());
+        let response = pair.response.as_ref().unwrap();
+        assert_eq!(response.fields[0].rust_type, "DateTime<Utc>");
+        assert_eq!(response.fields[1].rust_type, "GenericStatusEnumType");
+    }
+
+    #[test]
+    fn test_strip_suffix_produces_clean_struct_names() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        write_schema(
+            schema_dir.path(),
+            "AuthorizeRequest_v1p2.json",
+            SIMPLE_SCHEMA,
+        );
+        write_schema(
+            schema_dir.path(),
+            "AuthorizeResponse_v1p2.json",
+            SIMPLE_SCHEMA,
+        );
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            strip_suffix: Some(r"_v\d+p\d+$".to_string()),
+            ..Config::default()
+        };
+        let (pairs, _) = SchemaProcessor::new(config)
+            .collect_message_pairs()
+            .unwrap();
+
+        let pair = pairs
+            .get("Authorize")
+            .expect("pair keyed by clean base name");
+        assert!(pair.is_complete());
+        assert_eq!(pair.request.as_ref().unwrap().name, "AuthorizeRequest");
+        assert_eq!(pair.response.as_ref().unwrap().name, "AuthorizeResponse");
+    }
+
+    #[test]
+    fn test_rename_struct_applies_to_code_and_exports() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        let mapping_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        write_schema(
+            mapping_dir.path(),
+            "renames.json",
+            r#"{ "AuthorizeRequest": "OcppAuthorizeRequest" }"#,
+        );
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            rename_struct: Some(
+                mapping_dir
+                    .path()
+                    .join("renames.json")
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+            ..Config::default()
+        };
+        SchemaProcessor::new(config).process_all().unwrap();
+
+        let code = fs::read_to_string(output_dir.path().join("authorize.rs")).unwrap();
+        assert!(code.contains("pub struct OcppAuthorizeRequest {"));
+        assert!(code.contains("impl OcppAuthorizeRequest {"));
+        assert!(!code.contains("pub struct AuthorizeRequest {"));
+        assert!(code.contains("pub struct AuthorizeResponse {"));
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub use authorize::{OcppAuthorizeRequest, AuthorizeResponse};"));
+    }
+
+    #[test]
+    fn test_feature_gates_follow_schema_subfolders() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        let smart_charging = schema_dir.path().join("SmartCharging");
+        fs::create_dir(&smart_charging).unwrap();
+        write_schema(
+            &smart_charging,
+            "ClearChargingProfileRequest.json",
+            SIMPLE_SCHEMA,
+        );
+        write_schema(
+            &smart_charging,
+            "ClearChargingProfileResponse.json",
+            SIMPLE_SCHEMA,
+        );
+        write_schema(schema_dir.path(), "HeartbeatRequest.json", SIMPLE_SCHEMA);
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            feature_gates: true,
+            ..Config::default()
+        };
+        SchemaProcessor::new(config).process_all().unwrap();
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs
+            .contains("#[cfg(feature = \"smart_charging\")]\npub mod clear_charging_profile;\n"));
+        assert!(mod_rs
+            .contains("#[cfg(feature = \"smart_charging\")]\npub use clear_charging_profile::{"));
+        assert!(mod_rs.contains("\npub mod heartbeat;\n"));
+        assert!(!mod_rs.contains("\"heartbeat\""));
+    }
+
+    #[test]
+    fn test_definitions_library_generates_one_module() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(
+            schema_dir.path(),
+            "CommonTypes.json",
+            r##"{
+                "definitions": {
+                    "ChargingLimitType": {
+                        "type": "object",
+                        "properties": { "isGridCritical": { "type": "boolean" } }
+                    },
+                    "ChargingScheduleType": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "limit": { "$ref": "#/definitions/ChargingLimitType" },
+                            "unit": { "$ref": "#/definitions/ChargingRateUnitEnumType" }
+                        },
+                        "required": ["id"]
+                    },
+                    "ChargingRateUnitEnumType": {
+                        "description": "Unit of the charging rate",
+                        "type": "string",
+                        "enum": ["W", "A"]
+                    }
+                }
+            }"##,
+        );
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        SchemaProcessor::new(config).process_all().unwrap();
+
+        let code = fs::read_to_string(output_dir.path().join("common_types.rs")).unwrap();
+        assert!(code.contains("/// ChargingLimitType data type.\n"));
+        assert!(code.contains("pub struct ChargingLimitType {"));
+        assert!(code.contains("pub struct ChargingScheduleType {"));
+        assert!(code.contains("pub limit: Option<ChargingLimitType>,"));
+        assert!(!code.contains("use crate::v2_1::datatypes::ChargingLimitType;"));
+        assert!(!code.contains("fn action("));
+        // 字符串 enum 定义与对象定义生成在同一模块
+        assert!(code.contains(
+            "/// Unit of the charging rate\n#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]\npub enum ChargingRateUnitEnumType {\n    #[serde(rename = \"W\")]\n    W,\n"
+        ));
+        assert!(code.contains("pub unit: Option<ChargingRateUnitEnumType>,"));
+        assert!(!code.contains("::ChargingRateUnitEnumType;"));
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod common_types;\n"));
+        assert!(mod_rs.contains(
+            "pub use common_types::{ChargingRateUnitEnumType, ChargingLimitType, ChargingScheduleType};"
+        ));
+    }
+
+    /// 记录 generate_file span 字段的测试订阅层
+    struct GenerateFileSpans(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for GenerateFileSpans {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            struct Fields(String);
+            impl tracing::field::Visit for Fields {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    self.0.push_str(&format!("{}={:?} ", field.name(), value));
+                }
+            }
+
+            if attrs.metadata().name() == "generate_file" {
+                let mut fields = Fields(String::new());
+                attrs.record(&mut fields);
+                self.0.lock().unwrap().push(fields.0.trim_end().to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_file_span_per_generated_file() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "HeartbeatRequest.json", SIMPLE_SCHEMA);
+
+        // with_default 只作用于当前线程，因此顺序生成
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            jobs: 1,
+            ..Config::default()
+        };
+        let spans = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(GenerateFileSpans(spans.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            SchemaProcessor::new(config).process_all().unwrap();
+        });
+
+        let mut spans = spans.lock().unwrap().clone();
+        spans.sort();
+        assert_eq!(
+            spans,
+            vec![
+                "base_name=Authorize field_count=2",
+                "base_name=Heartbeat field_count=1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_append_only_keeps_existing_files() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            append_only: true,
+            ..Config::default()
+        };
+        SchemaProcessor::new(config.clone()).process_all().unwrap();
+
+        // 手工修改已生成的文件，追加模式下不应被覆盖
+        let authorize_path = output_dir.path().join("authorize.rs");
+        fs::write(&authorize_path, "// reviewed\n").unwrap();
+        write_schema(schema_dir.path(), "HeartbeatRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "HeartbeatResponse.json", SIMPLE_SCHEMA);
+        SchemaProcessor::new(config).process_all().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&authorize_path).unwrap(),
+            "// reviewed\n"
+        );
+        assert!(fs::read_to_string(output_dir.path().join("heartbeat.rs"))
+            .unwrap()
+            .contains("pub struct HeartbeatRequest {"));
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod authorize;\npub mod heartbeat;\n"));
+        assert!(mod_rs.contains("pub use heartbeat::{HeartbeatRequest, HeartbeatResponse};"));
+    }
+
+    #[test]
+    fn test_parallel_jobs_match_sequential_output() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        for name in ["Authorize", "Heartbeat", "Reset", "StatusNotification"] {
+            write_schema(
+                schema_dir.path(),
+                &format!("{}Request.json", name),
+                SIMPLE_SCHEMA,
+            );
+            write_schema(
+                schema_dir.path(),
+                &format!("{}Response.json", name),
+                SIMPLE_SCHEMA,
+            );
+        }
+
+        let generate = |jobs| {
+            let output_dir = tempfile::tempdir().unwrap();
+            let config = Config {
+                schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+                output_dir: output_dir.path().to_string_lossy().into_owned(),
+                jobs,
+                ..Config::default()
+            };
+            SchemaProcessor::new(config).process_all().unwrap();
+
+            let mut files: Vec<(String, String)> = fs::read_dir(output_dir.path())
+                .unwrap()
+                .map(|entry| {
+                    let path = entry.unwrap().path();
+                    let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                    (name, fs::read_to_string(&path).unwrap())
+                })
+                .collect();
+            files.sort();
+            files
+        };
+
+        let sequential = generate(1);
+        // 4 个消息文件、mod.rs 与生成清单
+        assert_eq!(sequential.len(), 6);
+        assert_eq!(generate(4), sequential);
+    }
+
+    #[test]
+    fn test_mod_file_reexports_paired_then_standalone_messages() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AlertRequest.json", SIMPLE_SCHEMA);
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        SchemaProcessor::new(config).process_all().unwrap();
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod alert;\npub mod authorize;\n"));
+        assert!(mod_rs.contains(
+            "pub use authorize::{AuthorizeRequest, AuthorizeResponse};\npub use alert::AlertRequest;\n"
+        ));
+    }
+
+    #[test]
+    fn test_unsuffixed_schema_generates_standalone_message() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(
+            schema_dir.path(),
+            "NotifyPeriodicEventStream.json",
+            SIMPLE_SCHEMA,
+        );
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        SchemaProcessor::new(config).process_all().unwrap();
+
+        let path = output_dir.path().join("notify_periodic_event_stream.rs");
+        assert!(path.exists());
+        let code = fs::read_to_string(path).unwrap();
+        assert!(code.contains("/// NotifyPeriodicEventStream message structure.\n"));
+        assert!(code.contains("pub struct NotifyPeriodicEventStream {"));
+
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod notify_periodic_event_stream;\n"));
+        assert!(
+            mod_rs.contains("pub use notify_periodic_event_stream::NotifyPeriodicEventStream;\n")
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_half_pairs() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(
+            schema_dir.path(),
+            "NotifyPeriodicEventStream.json",
+            SIMPLE_SCHEMA,
+        );
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        SchemaProcessor::new(config.clone()).process_all().unwrap();
+
+        let strict = Config {
+            strict: true,
+            ..config
+        };
+        let error = SchemaProcessor::new(strict).process_all().unwrap_err();
+        assert!(matches!(&error, GenError::MissingPair { base_name } if base_name == "Authorize"));
+    }
+
+    #[test]
+    fn test_schema_errors_name_the_offending_file() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(
+            schema_dir.path(),
+            "BrokenRequest.json",
+            r#"{ "type": "object", "properties": { "x": false } }"#,
+        );
+        write_schema(schema_dir.path(), "TruncatedResponse.json", "{ \"type\": ");
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            fail_fast: true,
+            ..Config::default()
+        };
+        let broken = schema_dir.path().join("BrokenRequest.json");
+        let error = SchemaProcessor::new(config.clone())
+            .process_all()
+            .unwrap_err();
+        assert!(
+            matches!(&error, GenError::UnsupportedSchema { file: Some(file), .. } if *file == broken)
+        );
+        assert!(error
+            .to_string()
+            .starts_with(&format!("{}: BrokenRequest.x", broken.display())));
+
+        fs::remove_file(&broken).unwrap();
+        let truncated = schema_dir.path().join("TruncatedResponse.json");
+        let error = SchemaProcessor::new(config).process_all().unwrap_err();
+        assert!(matches!(&error, GenError::JsonParse { file, .. } if *file == truncated));
+        assert!(error.to_string().contains("TruncatedResponse.json"));
+    }
+
+    #[test]
+    fn test_failed_files_are_skipped_and_summarized() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "HeartbeatRequest.json", "{ \"type\": ");
+        write_schema(schema_dir.path(), "HeartbeatResponse.json", SIMPLE_SCHEMA);
+        write_schema(
+            schema_dir.path(),
+            "ResetRequest.json",
+            r#"{ "type": "object", "properties": { "x": false } }"#,
+        );
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            show_statistics: false,
+            ..Config::default()
+        };
+        let processor = SchemaProcessor::new(config);
+        assert_eq!(processor.get_stats().unwrap().failed_files, 2);
+
+        let error = processor.process_all().unwrap_err();
+        let GenError::Failures { failures } = error else {
+            panic!("expected a failure summary, got {:?}", error);
+        };
+        let failed: Vec<&Path> = failures.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(
+            failed,
+            [
+                schema_dir.path().join("HeartbeatRequest.json"),
+                schema_dir.path().join("ResetRequest.json"),
+            ]
+        );
+        assert!(matches!(failures[0].1, GenError::JsonParse { .. }));
+        assert!(matches!(failures[1].1, GenError::UnsupportedSchema { .. }));
+
+        // 其余文件照常生成
+        assert!(output_dir.path().join("authorize.rs").exists());
+        assert!(output_dir.path().join("heartbeat.rs").exists());
+        assert!(!output_dir.path().join("reset.rs").exists());
+        let mod_rs = fs::read_to_string(output_dir.path().join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod authorize;\n"));
+        assert!(!mod_rs.contains("pub mod reset;\n"));
+    }
+
+    #[test]
+    fn test_unchanged_files_are_not_rewritten() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "HeartbeatRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "HeartbeatResponse.json", SIMPLE_SCHEMA);
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        SchemaProcessor::new(config.clone()).process_all().unwrap();
+        assert!(output_dir.path().join(".gen_manifest.json").exists());
+
+        // 把修改时间调回过去，被重写的文件会得到新的修改时间
+        let authorize = output_dir.path().join("authorize.rs");
+        let heartbeat = output_dir.path().join("heartbeat.rs");
+        let past = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        let backdate = |path: &Path| {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(past)
+                .unwrap();
+        };
+        let modified = |path: &Path| fs::metadata(path).unwrap().modified().unwrap();
+        backdate(&authorize);
+        write_schema(
+            schema_dir.path(),
+            "HeartbeatResponse.json",
+            r#"{ "type": "object", "properties": { "currentTime": { "type": "string" } } }"#,
+        );
+
+        SchemaProcessor::new(config.clone()).process_all().unwrap();
+        assert_eq!(modified(&authorize), past);
+        assert!(fs::read_to_string(&heartbeat)
+            .unwrap()
+            .contains("pub current_time: Option<String>,"));
+
+        // 手工修改的输出即使 schema 未变也会被重新生成
+        let generated = fs::read_to_string(&authorize).unwrap();
+        fs::write(&authorize, "// edited\n").unwrap();
+        SchemaProcessor::new(config.clone()).process_all().unwrap();
+        assert_eq!(fs::read_to_string(&authorize).unwrap(), generated);
+
+        // 删除的输出文件即使内容未变也会重新生成
+        backdate(&authorize);
+        fs::remove_file(output_dir.path().join("mod.rs")).unwrap();
+        SchemaProcessor::new(config.clone()).process_all().unwrap();
+        assert!(output_dir.path().join("mod.rs").exists());
+        assert_eq!(modified(&authorize), past);
+
+        let force = Config {
+            force: true,
+            ..config
+        };
+        SchemaProcessor::new(force).process_all().unwrap();
+        assert_ne!(modified(&authorize), past);
+        assert_eq!(fs::read_to_string(&authorize).unwrap(), generated);
+    }
+
+    #[test]
+    fn test_duplicate_message_schemas_are_reported() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        for folder in ["Core", "Vendor"] {
+            fs::create_dir(schema_dir.path().join(folder)).unwrap();
+        }
+        let core = schema_dir.path().join("Core").join("AuthorizeRequest.json");
+        let vendor = schema_dir
+            .path()
+            .join("Vendor")
+            .join("AuthorizeRequest.json");
+        fs::write(&core, SIMPLE_SCHEMA).unwrap();
+        fs::write(
+            &vendor,
+            r#"{ "type": "object", "properties": { "vendorId": { "type": "string" } } }"#,
+        )
+        .unwrap();
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        let (pairs, failures) = SchemaProcessor::new(config.clone())
+            .collect_message_pairs()
+            .unwrap();
+        assert_eq!(
+            pairs["Authorize"].request.as_ref().unwrap().fields[0].name,
+            "id_tag"
+        );
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].1.to_string(),
+            format!(
+                "AuthorizeRequest is defined by both {} and {}",
+                core.display(),
+                vendor.display()
+            )
+        );
+
+        let fail_fast = Config {
+            fail_fast: true,
+            ..config.clone()
+        };
+        let error = SchemaProcessor::new(fail_fast)
+            .collect_message_pairs()
+            .unwrap_err();
+        assert!(matches!(error, GenError::DuplicateMessage { .. }));
+
+        // --allow-duplicates 保留后者覆盖前者的行为
+        let allow = Config {
+            allow_duplicates: true,
+            ..config
+        };
+        let (pairs, failures) = SchemaProcessor::new(allow).collect_message_pairs().unwrap();
+        assert!(failures.is_empty());
+        assert_eq!(
+            pairs["Authorize"].request.as_ref().unwrap().fields[0].name,
+            "vendor_id"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_reports_changes_without_writing() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "HeartbeatRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "HeartbeatResponse.json", SIMPLE_SCHEMA);
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        let dry_run = Config {
+            dry_run: true,
+            ..config.clone()
+        };
+
+        // 输出目录为空时所有文件都会变化，但不写入任何文件
+        let error = SchemaProcessor::new(dry_run.clone())
+            .process_all()
+            .unwrap_err();
+        let GenError::OutOfDate { files } = error else {
+            panic!("expected out-of-date files, got {:?}", error);
+        };
+        assert_eq!(
+            files,
+            ["authorize.rs", "heartbeat.rs", "mod.rs"].map(|name| output_dir.path().join(name))
+        );
+        assert_eq!(fs::read_dir(output_dir.path()).unwrap().count(), 0);
+
+        // 生成后再预演没有差异
+        SchemaProcessor::new(config).process_all().unwrap();
+        SchemaProcessor::new(dry_run.clone()).process_all().unwrap();
+
+        // 只报告内容不同的文件，并保留磁盘上的内容
+        let heartbeat = output_dir.path().join("heartbeat.rs");
+        fs::write(&heartbeat, "// edited\n").unwrap();
+        let manifest = fs::read_to_string(output_dir.path().join(".gen_manifest.json")).unwrap();
+        let error = SchemaProcessor::new(dry_run).process_all().unwrap_err();
+        assert!(matches!(error, GenError::OutOfDate { files } if files == [heartbeat.clone()]));
+        assert_eq!(fs::read_to_string(&heartbeat).unwrap(), "// edited\n");
+        assert_eq!(
+            fs::read_to_string(output_dir.path().join(".gen_manifest.json")).unwrap(),
+            manifest
+        );
+    }
+
+    #[test]
+    fn test_include_and_exclude_globs_select_schemas() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        let vendor = schema_dir.path().join("vendor");
+        fs::create_dir(&vendor).unwrap();
+        write_schema(schema_dir.path(), "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(schema_dir.path(), "AuthorizeResponse.json", SIMPLE_SCHEMA);
+        write_schema(
+            schema_dir.path(),
+            "TransactionEventRequest.json",
+            SIMPLE_SCHEMA,
+        );
+        write_schema(
+            schema_dir.path(),
+            "TransactionEventResponse.json",
+            SIMPLE_SCHEMA,
+        );
+        write_schema(&vendor, "TransactionExtraRequest.json", SIMPLE_SCHEMA);
+        write_schema(&vendor, "TransactionExtraResponse.json", SIMPLE_SCHEMA);
+
+        let config = Config {
+            schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+            output_dir: output_dir.path().to_string_lossy().into_owned(),
+            include: vec!["**/Transaction*".to_string()],
+            exclude: vec!["vendor/**".to_string()],
+            ..Config::default()
+        };
+        let processor = SchemaProcessor::new(config.clone());
+        assert_eq!(processor.get_stats().unwrap().total_pairs, 1);
+        processor.process_all().unwrap();
+
+        assert!(output_dir.path().join("transaction_event.rs").exists());
+        assert!(!output_dir.path().join("transaction_extra.rs").exists());
+        assert!(!output_dir.path().join("authorize.rs").exists());
+
+        let invalid = Config {
+            include: vec!["[".to_string()],
+            ..config
+        };
+        let error = SchemaProcessor::new(invalid).process_all().unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Invalid --include/--exclude pattern"));
+    }
+
+    #[test]
+    fn test_generated_files_name_their_source_schemas() {
+        let schema_dir = tempfile::tempdir().unwrap();
+        let core = schema_dir.path().join("Core");
+        fs::create_dir(&core).unwrap();
+        write_schema(&core, "AuthorizeRequest.json", SIMPLE_SCHEMA);
+        write_schema(&core, "AuthorizeResponse.json", SIMPLE_SCHEMA);
+
+        let output_dir = tempfile::tempdir().unwrap();
+        let generate = |header: bool| {
+            let config = Config {
+                schema_dir: schema_dir.path().to_string_lossy().into_owned(),
+                output_dir: output_dir.path().to_string_lossy().into_owned(),
+                header,
+                ..Config::default()
+            };
+            SchemaProcessor::new(config).process_all().unwrap();
+            let read = |name: &str| fs::read_to_string(output_dir.path().join(name)).unwrap();
+            (read("authorize.rs"), read("mod.rs"))
+        };
+
+        let (authorize, mod_rs) = generate(true);
+        assert!(authorize.starts_with(&format!(
+            "// Code generated by ocpp_messages_gen_tool {}. DO NOT EDIT.\n// Source: Core/AuthorizeRequest.json, Core/AuthorizeResponse.json\n// Manual changes are overwritten on every run, even if the schemas are unchanged.\n\nuse ",
+            env!("CARGO_PKG_VERSION")
+        )));
+        // mod.rs 的文件头位于生成区域内，随区域一起重写
+        assert!(mod_rs.starts_with("// GEN-BEGIN\n// Code generated by ocpp_messages_gen_tool "));
+        assert!(mod_rs.contains(
+            "// Edits outside the GEN-BEGIN/GEN-END markers are kept across runs.\n\npub mod authorize;\n"
+        ));
+
+        // 文件头的提示与实际行为一致：手工修改在下次运行时被覆盖
+        fs::write(
+            output_dir.path().join("authorize.rs"),
+            authorize.replace("DO NOT EDIT", ""),
+        )
+        .unwrap();
+        assert_eq!(generate(true).0, authorize);
+
+        let (authorize, mod_rs) = generate(false);
+        assert!(authorize.starts_with("use "));
+        assert!(mod_rs.starts_with("// GEN-BEGIN\npub mod authorize;\n"));
     }
 }
@@ -0,0 +1,171 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::types::{FieldInfo, InlineEnumInfo, MessagePair, SharedTypes, StructInfo};
+
+/// 共享辅助类型所在的模块名
+pub const SHARED_MODULE: &str = "shared";
+
+/// 收集在多个消息中结构相同的内联结构体与枚举，标记为共享并改写引用
+///
+/// 类型的身份由解析后的字段（名称、类型、约束与文档）决定，与所属消息无关；
+/// 共享类型沿用按消息名排序后首次出现时的名称，引用它的消息从 shared 模块重新导出，
+/// 原有的模块路径保持可用。
+pub fn collect_shared_types(message_pairs: &mut HashMap<String, MessagePair>) -> SharedTypes {
+    let mut base_names: Vec<String> = message_pairs.keys().cloned().collect();
+    base_names.sort();
+
+    // 统计每种结构出现在哪些消息中，并记录首次出现时的名称
+    let mut seen: BTreeMap<String, (String, BTreeSet<&str>)> = BTreeMap::new();
+    for base_name in &base_names {
+        for info in message_pairs[base_name].structs() {
+            count_helpers(info, base_name, &mut seen);
+        }
+    }
+    let canonical: HashMap<String, String> = seen
+        .into_iter()
+        .filter(|(_, (_, messages))| messages.len() > 1)
+        .map(|(key, (name, _))| (key, name))
+        .collect();
+
+    let mut shared = SharedTypes::default();
+    let mut captured = HashSet::new();
+    for base_name in &base_names {
+        let pair = message_pairs.get_mut(base_name).unwrap();
+        let mut imports = BTreeSet::new();
+        for info in pair.structs_mut() {
+            share_helpers(
+                info,
+                false,
+                &canonical,
+                &mut shared,
+                &mut captured,
+                &mut imports,
+            );
+        }
+        pair.combined_imports.extend(
+            imports
+                .into_iter()
+                .map(|name| format!("pub use super::{}::{};", SHARED_MODULE, name)),
+        );
+    }
+
+    shared
+}
+
+/// 记录结构体中（含嵌套的）内联辅助类型出现在哪个消息
+fn count_helpers<'a>(
+    info: &StructInfo,
+    base_name: &'a str,
+    seen: &mut BTreeMap<String, (String, BTreeSet<&'a str>)>,
+) {
+    for field in &info.fields {
+        if let Some(inline) = field.inline_struct.as_deref() {
+            seen.entry(struct_key(inline))
+                .or_insert_with(|| (inline.name.clone(), BTreeSet::new()))
+                .1
+                .insert(base_name);
+            count_helpers(inline, base_name, seen);
+        }
+        if let Some(inline_enum) = &field.inline_enum {
+            seen.entry(enum_key(inline_enum))
+                .or_insert_with(|| (inline_enum.name.clone(), BTreeSet::new()))
+                .1
+                .insert(base_name);
+        }
+    }
+}
+
+/// 将共享的辅助类型改名为统一名称并标记为共享，首次遇到时复制到 `shared`
+///
+/// `in_shared` 表示 `info` 本身位于共享类型中：其引用在 shared 模块内解析，无需导入。
+fn share_helpers(
+    info: &mut StructInfo,
+    in_shared: bool,
+    canonical: &HashMap<String, String>,
+    shared: &mut SharedTypes,
+    captured: &mut HashSet<String>,
+    imports: &mut BTreeSet<String>,
+) {
+    for field in &mut info.fields {
+        if let Some(inline) = field.inline_struct.as_deref_mut() {
+            // 身份在改写嵌套类型之前计算，与统计时一致
+            let key = struct_key(inline);
+            let name = canonical.get(&key);
+            share_helpers(
+                inline,
+                in_shared || name.is_some(),
+                canonical,
+                shared,
+                captured,
+                imports,
+            );
+            if let Some(name) = name {
+                field.rust_type = field.rust_type.replace(&inline.name, name);
+                inline.name = name.clone();
+                inline.emitted_elsewhere = true;
+                if !in_shared {
+                    imports.insert(name.clone());
+                }
+                if captured.insert(key) {
+                    shared.structs.push(inline.clone());
+                }
+            }
+        }
+        if let Some(inline_enum) = field.inline_enum.as_mut() {
+            let key = enum_key(inline_enum);
+            if let Some(name) = canonical.get(&key) {
+                field.rust_type = field.rust_type.replace(&inline_enum.name, name);
+                inline_enum.name = name.clone();
+                inline_enum.emitted_elsewhere = true;
+                if !in_shared {
+                    imports.insert(name.clone());
+                }
+                if captured.insert(key) {
+                    shared.enums.push(inline_enum.clone());
+                }
+            }
+        }
+    }
+}
+
+/// 内联结构体的结构身份：字段的完整信息，嵌套辅助类型以其结构代替名称
+fn struct_key(info: &StructInfo) -> String {
+    let fields: Vec<String> = info.fields.iter().map(field_key).collect();
+    format!(
+        "struct {:?} {:?} {:?} {:?} {} [{}]",
+        info.title,
+        info.examples,
+        info.deprecated,
+        info.rename_all,
+        info.allows_additional_properties,
+        fields.join(", ")
+    )
+}
+
+/// 内联枚举的结构身份：线上字符串值、默认值与文档
+fn enum_key(inline_enum: &InlineEnumInfo) -> String {
+    format!(
+        "enum {:?} {:?} {:?}",
+        inline_enum.description, inline_enum.values, inline_enum.default
+    )
+}
+
+fn field_key(field: &FieldInfo) -> String {
+    let mut field = field.clone();
+    if let Some(inline) = field.inline_struct.take() {
+        field.rust_type = field.rust_type.replace(&inline.name, &struct_key(&inline));
+    }
+    if let Some(inline_enum) = field.inline_enum.take() {
+        field.rust_type = field
+            .rust_type
+            .replace(&inline_enum.name, &enum_key(&inline_enum));
+    }
+    if let Some(union) = field.union.take() {
+        let union_key = format!(
+            "union {:?} {:?} {:?} {:?} {:?}",
+            union.description, union.variants, union.constraints, union.tag, union.tag_values
+        );
+        field.rust_type = field.rust_type.replace(&union.name, &union_key);
+    }
+    format!("{:?}", field)
+}
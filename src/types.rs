@@ -1,28 +1,124 @@
+use serde_json::Value;
 use std::collections::HashSet;
 
 /// 表示结构体字段的信息
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FieldInfo {
     pub name: String,
     pub original_name: String, // 原始 JSON 字段名
     pub rust_type: String,
     pub is_optional: bool,
+    pub required_nullable: bool, // 必填但可为 null：键必须出现，None 序列化为 null
     pub needs_validation: bool,
     pub description: Option<String>,
+    pub title: Option<String>, // schema title，文档注释中位于 description 之前
+    pub examples: Vec<Value>,  // schema examples，--doc-examples 时写入文档注释
     pub max_length: Option<u32>,
     pub min_length: Option<u32>,
     pub min_value: Option<f64>,
     pub max_value: Option<f64>,
+    pub min_literal: Option<String>, // min_value 在 schema 中的十进制原文，保留完整精度
+    pub max_literal: Option<String>, // max_value 在 schema 中的十进制原文
+    pub exclusive_min: bool,         // min_value 为排他边界（exclusiveMinimum）
+    pub exclusive_max: bool,         // max_value 为排他边界（exclusiveMaximum）
     pub min_items: Option<u32>,
     pub max_items: Option<u32>,
+    pub serde_with: Option<String>, // 自定义 serde 编解码模块（如 serde_bytes）
+    pub newtype: Option<NewtypeInfo>, // 引用受约束标量定义时生成的新类型包装
+    pub inline_enum: Option<InlineEnumInfo>, // 内联字符串 enum 生成的枚举类型
+    pub union: Option<UnionInfo>,   // oneOf/anyOf 生成的 untagged 枚举类型
+    pub inline_struct: Option<Box<StructInfo>>, // 内联 object 提升生成的嵌套结构体
+    pub deprecated: Option<String>, // 字段被弃用时的说明
+    pub pattern: Option<String>,    // schema 中字符串的 pattern 正则约束
+    pub multiple_of: Option<f64>,   // schema 中数值的 multipleOf 约束
+    pub string_format: Option<String>, // 需要生成验证的字符串 format（email、uri）
+    pub schema_default: Option<Value>, // 可选字段的 schema default 值
+    pub const_value: Option<Value>, // schema const 规定的固定值
+}
+
+/// 表示受约束标量定义（如 CiString20Type）的新类型包装
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NewtypeInfo {
+    pub name: String,
+    pub inner_type: String,
+    pub description: Option<String>,
+    pub max_length: Option<u32>,
+    pub min_length: Option<u32>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub min_literal: Option<String>, // min_value 在 schema 中的十进制原文
+    pub max_literal: Option<String>, // max_value 在 schema 中的十进制原文
+    pub exclusive_min: bool,         // min_value 为排他边界（exclusiveMinimum）
+    pub exclusive_max: bool,         // max_value 为排他边界（exclusiveMaximum）
+}
+
+impl NewtypeInfo {
+    /// 以包装值为字段描述其约束，供生成验证、示例与 proptest 策略时复用字段的处理
+    pub fn inner_field(&self) -> FieldInfo {
+        FieldInfo {
+            name: "0".to_string(),
+            original_name: "0".to_string(),
+            rust_type: self.inner_type.clone(),
+            needs_validation: true,
+            min_length: self.min_length,
+            max_length: self.max_length,
+            min_value: self.min_value,
+            max_value: self.max_value,
+            min_literal: self.min_literal.clone(),
+            max_literal: self.max_literal.clone(),
+            exclusive_min: self.exclusive_min,
+            exclusive_max: self.exclusive_max,
+            ..FieldInfo::default()
+        }
+    }
+}
+
+/// 表示字段内联字符串 enum 生成的 Rust 枚举
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InlineEnumInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub values: Vec<String>,     // 原始的线上字符串值
+    pub default: Option<String>, // schema default（须为 values 之一），生成 impl Default
+    pub emitted_elsewhere: bool, // 已在 shared.rs 或同一文件的其他位置生成，此处不再生成
+}
+
+/// 表示字段 oneOf/anyOf 生成的 untagged Rust 枚举
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UnionInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub variants: Vec<(String, String)>, // （变体名, 包装的 Rust 类型），按 schema 顺序尝试
+    pub constraints: Vec<Option<NewtypeInfo>>, // 与 variants 对齐：标量变体的长度与数值约束
+    pub tag: Option<String>,             // 各分支共有的判别属性，存在时生成 internally tagged 枚举
+    pub tag_values: Vec<String>, // 与 variants 对齐：各分支判别属性的 const 值（untagged 时为空）
 }
 
 /// 表示一个结构体的信息
 #[derive(Debug, Clone)]
 pub struct StructInfo {
     pub name: String,
+    pub title: Option<String>, // schema title，作为文档注释的首行
+    pub examples: Vec<Value>,  // 整个对象的 schema examples，--doc-examples 时生成文档测试
     pub fields: Vec<FieldInfo>,
     pub imports: HashSet<String>,
+    pub deprecated: Option<String>,         // 整个消息被弃用时的说明
+    pub rename_all: Option<String>,         // schema 中 x-rename-all 指定的命名规则
+    pub allows_additional_properties: bool, // 允许未声明的属性（additionalProperties 非 false，或 CustomDataType）
+    pub emitted_elsewhere: bool,            // 内联辅助类型已在 shared.rs 或同一文件的其他位置生成
+}
+
+/// 多个消息中结构相同的内联辅助类型，统一生成到 shared.rs
+#[derive(Debug, Clone, Default)]
+pub struct SharedTypes {
+    pub structs: Vec<StructInfo>, // 嵌套的辅助类型在引用它的结构体之前
+    pub enums: Vec<InlineEnumInfo>,
+}
+
+impl SharedTypes {
+    pub fn is_empty(&self) -> bool {
+        self.structs.is_empty() && self.enums.is_empty()
+    }
 }
 
 /// 表示一对 Request/Response 消息
@@ -32,6 +128,10 @@ pub struct MessagePair {
     pub request: Option<StructInfo>,
     pub response: Option<StructInfo>,
     pub combined_imports: HashSet<String>,
+    pub feature: Option<String>, // 来源子目录对应的 cargo feature（功能块）
+    pub definitions: Vec<StructInfo>, // 仅含 definitions 的 schema 文件生成的数据类型
+    pub definition_enums: Vec<InlineEnumInfo>, // 同一文件中字符串 enum 定义生成的枚举
+    pub sources: Vec<String>,    // 生成该模块的 schema 文件（相对 schema 目录），写入文件头
 }
 
 impl MessagePair {
@@ -41,17 +141,63 @@ impl MessagePair {
             request: None,
             response: None,
             combined_imports: HashSet::new(),
+            feature: None,
+            definitions: Vec::new(),
+            definition_enums: Vec::new(),
+            sources: Vec::new(),
         }
     }
 
     pub fn add_request(&mut self, struct_info: StructInfo) {
         self.combined_imports.extend(struct_info.imports.clone());
         self.request = Some(struct_info);
+        self.mark_repeated_helpers();
     }
 
     pub fn add_response(&mut self, struct_info: StructInfo) {
         self.combined_imports.extend(struct_info.imports.clone());
         self.response = Some(struct_info);
+        self.mark_repeated_helpers();
+    }
+
+    pub fn add_definition(&mut self, struct_info: StructInfo) {
+        self.combined_imports.extend(struct_info.imports.clone());
+        self.definitions.push(struct_info);
+    }
+
+    pub fn add_definition_enum(&mut self, enum_info: InlineEnumInfo) {
+        self.combined_imports
+            .insert("use serde::{Deserialize, Serialize};".to_string());
+        self.definition_enums.push(enum_info);
+    }
+
+    /// 是否为 definitions 库文件生成的模块
+    pub fn has_definitions(&self) -> bool {
+        !self.definitions.is_empty() || !self.definition_enums.is_empty()
+    }
+
+    /// 模块中的所有结构体：请求、响应及 definitions 数据类型
+    pub fn structs(&self) -> impl Iterator<Item = &StructInfo> {
+        [&self.request, &self.response]
+            .into_iter()
+            .flatten()
+            .chain(self.definitions.iter())
+    }
+
+    /// 可变地遍历模块中的所有结构体
+    pub fn structs_mut(&mut self) -> impl Iterator<Item = &mut StructInfo> {
+        [&mut self.request, &mut self.response]
+            .into_iter()
+            .flatten()
+            .chain(self.definitions.iter_mut())
+    }
+
+    /// 请求与响应引用同一本地定义时，辅助类型只在首次出现处生成
+    fn mark_repeated_helpers(&mut self) {
+        let mut seen = HashSet::new();
+        for info in self.structs_mut() {
+            mark_repeated_helpers(info, &mut seen);
+        }
     }
 
     pub fn is_complete(&self) -> bool {
@@ -63,3 +209,21 @@ impl MessagePair {
             || (self.request.is_none() && self.response.is_some())
     }
 }
+
+/// 标记已在前面出现过的同名内联辅助类型，重复的结构体不再递归
+fn mark_repeated_helpers(info: &mut StructInfo, seen: &mut HashSet<String>) {
+    for field in &mut info.fields {
+        if let Some(inline_enum) = field.inline_enum.as_mut() {
+            if !seen.insert(inline_enum.name.clone()) {
+                inline_enum.emitted_elsewhere = true;
+            }
+        }
+        if let Some(inline) = field.inline_struct.as_deref_mut() {
+            if seen.insert(inline.name.clone()) {
+                mark_repeated_helpers(inline, seen);
+            } else {
+                inline.emitted_elsewhere = true;
+            }
+        }
+    }
+}
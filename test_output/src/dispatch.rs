@@ -0,0 +1,40 @@
+//! Validation dispatch for request payloads by OCPP action name.
+
+use validator::Validate;
+
+/// Error returned by [`validate_message`].
+#[derive(Debug)]
+pub enum ValidateMessageError {
+    /// The action name does not match any generated request.
+    UnknownAction(String),
+    /// The payload is not valid JSON for the request type.
+    Json(serde_json::Error),
+    /// The payload violates the request's constraints.
+    Validation(validator::ValidationErrors),
+}
+
+impl std::fmt::Display for ValidateMessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownAction(action) => write!(f, "unknown action: {}", action),
+            Self::Json(e) => write!(f, "invalid JSON payload: {}", e),
+            Self::Validation(e) => write!(f, "invalid payload: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ValidateMessageError {}
+
+/// Deserializes `json` into the request type for `action` and validates it.
+pub fn validate_message(json: &str, action: &str) -> Result<(), ValidateMessageError> {
+    match action {
+        "TestDispatch" => {
+            let message: super::test_dispatch::TestDispatchRequest =
+                serde_json::from_str(json).map_err(ValidateMessageError::Json)?;
+            message
+                .validate()
+                .map_err(ValidateMessageError::Validation)
+        }
+        _ => Err(ValidateMessageError::UnknownAction(action.to_string())),
+    }
+}
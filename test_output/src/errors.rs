@@ -0,0 +1,21 @@
+//! Errors returned when converting JSON values into messages.
+
+/// Error returned when converting a `serde_json::Value` into a message.
+#[derive(Debug)]
+pub enum FromValueError {
+    /// The value does not have the shape of the message.
+    Json(serde_json::Error),
+    /// The message violates its constraints.
+    Validation(validator::ValidationErrors),
+}
+
+impl std::fmt::Display for FromValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid message JSON: {}", e),
+            Self::Validation(e) => write!(f, "invalid message: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FromValueError {}
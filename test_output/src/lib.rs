@@ -3,10 +3,50 @@
 // Include the generated modules
 pub mod test_constraints;
 pub mod notify_periodic_event_stream;
+pub mod test_optional_nested;
+pub mod errors;
+pub mod test_newtypes;
+pub mod test_recursive;
+pub mod test_local_tree;
+pub mod test_nullable;
+pub mod v2_1;
+pub mod test_union;
+pub mod test_messages;
+pub mod test_exclusive;
+pub mod test_try_new;
+pub mod test_strict;
+pub mod test_permissive;
+pub mod test_dispatch;
+pub mod dispatch;
+pub mod test_const;
+pub mod test_batch;
+pub mod test_enums;
+pub mod test_tagged_union;
 
 // Re-export for easier testing
 pub use test_constraints::TestConstraints;
 pub use notify_periodic_event_stream::NotifyPeriodicEventStream;
+pub use test_optional_nested::{TestOptionalNested, TestOptionalNestedEntriesType};
+pub use errors::FromValueError;
+pub use test_newtypes::{CiString5Type, MeterValueType, PriceType, TestNewtypes};
+pub use test_recursive::TestRecursive;
+pub use test_local_tree::{NodeType, TestLocalTree};
+pub use test_nullable::TestNullable;
+pub use test_union::{TestUnion, TestUnionTokenUnionType};
+pub use test_tagged_union::{TestTaggedUnion, TestTaggedUnionEventUnionType};
+pub use v2_1::datatypes::{
+    AuthorizationStatusEnumType, EndedEventType, IdTokenInfoType, IdTokenType, StartedEventType,
+};
+pub use test_messages::TestMessages;
+pub use test_exclusive::TestExclusive;
+pub use test_try_new::TestTryNew;
+pub use test_strict::TestStrict;
+pub use test_permissive::TestPermissive;
+pub use test_dispatch::{TestDispatchRequest, TestDispatchResponse};
+pub use dispatch::{validate_message, ValidateMessageError};
+pub use test_const::TestConst;
+pub use test_batch::{TestBatch, TestBatchDataType};
+pub use test_enums::{TestEnums, TestEnumsModeEnumType, TestEnumsStatusEnumType};
 
 #[cfg(test)]
 mod tests {
@@ -135,4 +175,384 @@ mod tests {
         instance.set_optional_field(Some("Hello".to_string())); // 5 chars, between 3-20 ✓
         assert!(instance.validate().is_ok());
     }
+
+    #[test]
+    fn test_optional_nested_array_validates_elements() {
+        let mut instance = TestOptionalNested::new("Label".to_string());
+
+        // Optional array is None, should be valid
+        assert!(instance.validate().is_ok());
+
+        // Set the array with a valid element
+        instance.set_entries(Some(vec![TestOptionalNestedEntriesType::new("a".to_string(), 50)]));
+        assert!(instance.validate().is_ok());
+
+        // Empty array violates min items (min = 1)
+        instance.set_entries(Some(vec![]));
+        assert!(instance.validate().is_err());
+
+        // Invalid element inside the Option<Vec<_>> (name max = 10, value max = 100)
+        instance.set_entries(Some(vec![
+            TestOptionalNestedEntriesType::new("a".to_string(), 50),
+            TestOptionalNestedEntriesType::new("Far too long name".to_string(), 50),
+        ]));
+        assert!(instance.validate().is_err());
+
+        instance.set_entries(Some(vec![TestOptionalNestedEntriesType::new("a".to_string(), 101)]));
+        let errors = instance.validate().unwrap_err();
+        assert!(errors.errors().contains_key("entries"));
+    }
+
+    #[test]
+    fn test_try_from_value_reports_validation_failures() {
+        // A well-formed value converts into a validated message
+        let value = serde_json::json!({ "label": "Label", "entries": [{ "name": "a", "value": 50 }] });
+        assert!(TestOptionalNested::try_from(value).is_ok());
+
+        // The value deserializes, but the nested name exceeds max length (10)
+        let value = serde_json::json!({ "label": "Label", "entries": [{ "name": "Far too long name", "value": 50 }] });
+        match TestOptionalNested::try_from(value) {
+            Err(FromValueError::Validation(errors)) => assert!(errors.errors().contains_key("entries")),
+            other => panic!("expected a validation failure, got {:?}", other),
+        }
+
+        // A value of the wrong shape fails to deserialize
+        let value = serde_json::json!({ "label": 5 });
+        assert!(matches!(TestOptionalNested::try_from(value), Err(FromValueError::Json(_))));
+    }
+
+    #[test]
+    fn test_newtype_scalars_enforce_definition_constraints() {
+        let valid = TestNewtypes::new(
+            CiString5Type::from("ABC"),
+            MeterValueType(4294967295), // beyond i32, at the maximum ✓
+            PriceType(Decimal::from_str("99.5").unwrap()), // at the inclusive maximum ✓
+        );
+        assert!(valid.validate().is_ok());
+
+        // String longer than maxLength (5)
+        let mut instance = valid.clone();
+        instance.set_id_tag(CiString5Type::from("ABCDEF"));
+        assert!(CiString5Type::from("ABCDEF").validate().is_err());
+        assert!(instance.validate().is_err());
+
+        // Integer above the maximum
+        assert!(MeterValueType(4294967296).validate().is_err());
+
+        // Decimal bounds: exclusiveMinimum 0 and maximum 99.5
+        assert!(PriceType(Decimal::ZERO).validate().is_err());
+        assert!(PriceType(Decimal::from_str("0.01").unwrap()).validate().is_ok());
+        assert!(PriceType(Decimal::from_str("99.6").unwrap()).validate().is_err());
+    }
+
+    #[test]
+    fn test_self_references_validate_nested_messages() {
+        let mut root = TestRecursive::new("root".to_string());
+        assert!(root.validate().is_ok());
+
+        // Boxed parent whose name exceeds maxLength (10)
+        root.set_parent(Some(Box::new(TestRecursive::new("Far too long name".to_string()))));
+        let errors = root.validate().unwrap_err();
+        assert!(errors.errors().contains_key("parent"));
+
+        root.set_parent(Some(Box::new(TestRecursive::new("parent".to_string()))));
+        assert!(root.validate().is_ok());
+
+        // Invalid grandchild inside the array of self-references
+        let child = TestRecursive::new("child".to_string())
+            .with_children(vec![TestRecursive::new("Far too long name".to_string())]);
+        root.set_children(Some(vec![child]));
+        let errors = root.validate().unwrap_err();
+        assert!(errors.errors().contains_key("children"));
+    }
+
+    #[test]
+    fn test_recursive_local_definitions_validate_nested_nodes() {
+        let leaf = NodeType::new("leaf".to_string());
+        let tree = NodeType::new("root".to_string()).with_children(vec![leaf]);
+        let mut instance = TestLocalTree::new(tree);
+        assert!(instance.validate().is_ok());
+
+        // Boxed child whose name exceeds maxLength (10)
+        let bad_leaf = NodeType::new("Far too long name".to_string());
+        instance.set_tree(NodeType::new("root".to_string()).with_children(vec![bad_leaf]));
+        let errors = instance.validate().unwrap_err();
+        assert!(errors.errors().contains_key("tree"));
+    }
+
+    #[test]
+    fn test_required_nullable_field_is_always_present() {
+        // None is written as null instead of dropping the required key
+        let instance = TestNullable::new();
+        assert_eq!(serde_json::to_string(&instance).unwrap(), r#"{"count":null}"#);
+
+        // An explicit null is accepted, a missing key is not
+        let parsed: TestNullable = serde_json::from_str(r#"{"count":null}"#).unwrap();
+        assert_eq!(parsed.count, None);
+        assert!(serde_json::from_str::<TestNullable>("{}").is_err());
+
+        let parsed: TestNullable = serde_json::from_str(r#"{"count":3}"#).unwrap();
+        assert_eq!(parsed.count, Some(3));
+        assert!(parsed.validate().is_ok());
+
+        let parsed: TestNullable = serde_json::from_str(r#"{"count":11}"#).unwrap();
+        assert!(parsed.validate().is_err());
+    }
+
+    #[test]
+    fn test_one_of_deserializes_both_shapes_and_validates_each() {
+        // Object shape deserializes into the datatype variant
+        let parsed: TestUnion =
+            serde_json::from_str(r#"{"token":{"idToken":"ABC123","type":"ISO14443"}}"#).unwrap();
+        assert_eq!(
+            parsed.token,
+            TestUnionTokenUnionType::IdToken(IdTokenType::new(
+                "ABC123".to_string(),
+                "ISO14443".to_string()
+            ))
+        );
+        assert!(parsed.validate().is_ok());
+
+        // String shape deserializes into the scalar variant
+        let parsed: TestUnion = serde_json::from_str(r#"{"token":"ABC123"}"#).unwrap();
+        assert_eq!(parsed.token, TestUnionTokenUnionType::String("ABC123".to_string()));
+        assert!(parsed.validate().is_ok());
+
+        // Each variant keeps its own constraints: the string's maxLength (36)...
+        let parsed: TestUnion =
+            serde_json::from_value(serde_json::json!({ "token": "X".repeat(37) })).unwrap();
+        assert!(parsed.validate().is_err());
+
+        // ...and the datatype's type maxLength (20)
+        let parsed: TestUnion = serde_json::from_value(
+            serde_json::json!({ "token": { "idToken": "ABC123", "type": "X".repeat(21) } }),
+        )
+        .unwrap();
+        assert!(parsed.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_messages_appear_in_errors() {
+        let instance = TestMessages::new("ABC".to_string(), 4);
+        assert!(instance.validate().is_ok());
+
+        let instance = TestMessages::new("Far too long".to_string(), 4);
+        let message = instance.validate().unwrap_err().to_string();
+        assert!(message.contains("code must be at most 10 chars"), "{}", message);
+
+        let instance = TestMessages::new("ABC".to_string(), 9);
+        let message = instance.validate().unwrap_err().to_string();
+        assert!(message.contains("evse_id must be between 1 and 8"), "{}", message);
+    }
+
+    #[test]
+    fn test_exclusive_bounds_reject_the_bound_itself() {
+        let price = |s: &str| Decimal::from_str(s).unwrap();
+        let valid = TestExclusive::new(5, 0.5, price("50"));
+        assert!(valid.validate().is_ok());
+
+        // Integer: the bounds fail, the nearest integers inside pass
+        for (count, ok) in [(0, false), (1, true), (9, true), (10, false)] {
+            let mut instance = valid.clone();
+            instance.set_count(count);
+            assert_eq!(instance.validate().is_ok(), ok, "count = {}", count);
+        }
+
+        // Float
+        for (ratio, ok) in [(0.0, false), (0.001, true), (0.999, true), (1.0, false)] {
+            let mut instance = valid.clone();
+            instance.set_ratio(ratio);
+            assert_eq!(instance.validate().is_ok(), ok, "ratio = {}", ratio);
+        }
+
+        // Decimal
+        for (value, ok) in [("0.5", false), ("0.51", true), ("99.49", true), ("99.5", false)] {
+            let mut instance = valid.clone();
+            instance.set_price(price(value));
+            assert_eq!(instance.validate().is_ok(), ok, "price = {}", value);
+        }
+    }
+
+    #[test]
+    fn test_try_new_validates_the_constructed_message() {
+        let message = TestTryNew::try_new("TAG-1".to_string()).unwrap();
+        assert_eq!(message, TestTryNew::new("TAG-1".to_string()));
+
+        // idTag longer than maxLength (20)
+        let errors = TestTryNew::try_new("X".repeat(21)).unwrap_err();
+        assert!(errors.field_errors().contains_key("id_tag"));
+    }
+
+    #[test]
+    fn test_deny_unknown_fields_rejects_extra_keys_only_when_enabled() {
+        let extra = r#"{"idTag":"abc","unexpected":1}"#;
+        let error = serde_json::from_str::<TestStrict>(extra).unwrap_err();
+        assert!(error.to_string().contains("unknown field `unexpected`"), "{}", error);
+        assert!(serde_json::from_str::<TestPermissive>(extra).is_ok());
+
+        // Objects whose schema allows additional properties stay lenient
+        let extensible = r#"{"idTag":"abc","vendorData":{"vendorId":"v","extra":true}}"#;
+        assert!(serde_json::from_str::<TestStrict>(extensible).is_ok());
+    }
+
+    #[test]
+    fn test_validate_dispatch_by_action_name() {
+        assert!(validate_message(r#"{"idTag":"TAG-1"}"#, "TestDispatch").is_ok());
+
+        // idTag longer than maxLength (20)
+        let too_long = format!(r#"{{"idTag":"{}"}}"#, "X".repeat(21));
+        assert!(matches!(
+            validate_message(&too_long, "TestDispatch"),
+            Err(ValidateMessageError::Validation(_))
+        ));
+        assert!(matches!(
+            validate_message("{}", "TestDispatch"),
+            Err(ValidateMessageError::Json(_))
+        ));
+        assert!(matches!(
+            validate_message(r#"{"idTag":"TAG-1"}"#, "Unknown"),
+            Err(ValidateMessageError::UnknownAction(action)) if action == "Unknown"
+        ));
+    }
+
+    #[test]
+    fn test_const_fields_default_and_reject_other_values() {
+        // new and the builder fill in the constants
+        let message = TestConst::new("ping".to_string());
+        assert_eq!((message.kind.as_str(), message.version), ("Heartbeat", 2));
+        assert!(message.validate().is_ok());
+        let built = TestConst::builder().name("ping".to_string()).build().unwrap();
+        assert_eq!(built, message);
+
+        // Any other value fails validation
+        let parsed: TestConst =
+            serde_json::from_str(r#"{"kind":"Other","version":2,"name":"ping"}"#).unwrap();
+        assert!(parsed.validate().unwrap_err().field_errors().contains_key("kind"));
+        let parsed: TestConst =
+            serde_json::from_str(r#"{"kind":"Heartbeat","version":3,"name":"ping"}"#).unwrap();
+        assert!(parsed.validate().unwrap_err().field_errors().contains_key("version"));
+        assert!(TestConst::builder().name("ping".to_string()).version(3).build().is_err());
+    }
+
+    #[test]
+    fn test_optional_constrained_array_is_checked_only_when_present() {
+        let entry = |name: &str| TestOptionalNestedEntriesType::new(name.to_string(), 50);
+
+        // Absent key deserializes to None and skips the length check
+        let parsed: TestOptionalNested = serde_json::from_str(r#"{"label":"Label"}"#).unwrap();
+        assert_eq!(parsed.entries, None);
+        assert!(parsed.validate().is_ok());
+
+        // Present: length (min = 1, max = 3) and nested validation both apply
+        let mut instance = parsed.clone();
+        instance.set_entries(Some(vec![entry("a"), entry("b"), entry("c")]));
+        assert!(instance.validate().is_ok());
+        instance.set_entries(Some(vec![entry("a"), entry("b"), entry("c"), entry("d")]));
+        assert!(instance.validate().unwrap_err().errors().contains_key("entries"));
+        let parsed: TestOptionalNested =
+            serde_json::from_str(r#"{"label":"Label","entries":[]}"#).unwrap();
+        assert!(parsed.validate().unwrap_err().errors().contains_key("entries"));
+
+        // Back to None: an invalid value no longer matters
+        instance.set_entries(None);
+        assert!(instance.validate().is_ok());
+    }
+
+    #[test]
+    fn test_batch_message_iterates_its_data() {
+        let sample = |t: u32, v: &str| TestBatchDataType::new(t, v.to_string());
+        let batch = TestBatch::new(vec![sample(0, "1.5"), sample(10, "2.5")], 7);
+
+        // By reference: iter() and `for ... in &batch`
+        let offsets: Vec<u32> = batch.iter().map(|s| s.t).collect();
+        assert_eq!(offsets, vec![0, 10]);
+        let mut values = Vec::new();
+        for s in &batch {
+            values.push(s.v.as_str());
+        }
+        assert_eq!(values, vec!["1.5", "2.5"]);
+
+        // By value: consumes the message and yields the owned elements
+        let owned: Vec<TestBatchDataType> = batch.into_iter().collect();
+        assert_eq!(owned, vec![sample(0, "1.5"), sample(10, "2.5")]);
+    }
+
+    #[test]
+    fn test_definitions_library_generates_enum_definitions() {
+        // The string enum definition is generated next to the object that references it
+        let info: IdTokenInfoType = serde_json::from_str(r#"{"status":"Blocked"}"#).unwrap();
+        assert_eq!(info.status, AuthorizationStatusEnumType::Blocked);
+        assert_eq!(serde_json::to_string(&info).unwrap(), r#"{"status":"Blocked"}"#);
+        assert_eq!(
+            "Accepted".parse::<AuthorizationStatusEnumType>().unwrap().as_str(),
+            "Accepted"
+        );
+
+        // Values outside the enum are rejected
+        assert!(serde_json::from_str::<IdTokenInfoType>(r#"{"status":"Unknown"}"#).is_err());
+    }
+
+    #[test]
+    fn test_enum_all_covers_every_wire_value() {
+        // schema 中的 enum 共 3 个值，每个变体映射到不同的线上字符串
+        assert_eq!(TestEnumsStatusEnumType::ALL.len(), 3);
+        let wire: std::collections::HashSet<&str> =
+            TestEnumsStatusEnumType::ALL.iter().map(|v| v.as_str()).collect();
+        assert_eq!(wire.len(), 3);
+        assert!(wire.contains("ISO15118-20"));
+    }
+
+    #[test]
+    fn test_enum_default_prefers_schema_default() {
+        // mode 的 schema default 为 Manual；status 没有 default，退回第一个变体
+        assert_eq!(TestEnumsModeEnumType::default(), TestEnumsModeEnumType::Manual);
+        assert_eq!(
+            TestEnumsStatusEnumType::default(),
+            TestEnumsStatusEnumType::Accepted
+        );
+    }
+
+    #[test]
+    fn test_enum_compares_with_wire_string() {
+        let accepted = String::from("Accepted");
+        assert!(TestEnumsStatusEnumType::Accepted == "Accepted");
+        assert!(TestEnumsStatusEnumType::Accepted == accepted);
+        assert!(TestEnumsStatusEnumType::Rejected != accepted);
+        // 比较使用 serde 的线上值，而不是变体名
+        assert!(TestEnumsStatusEnumType::Iso1511820 == "ISO15118-20");
+        assert!(TestEnumsStatusEnumType::Iso1511820 != "Iso1511820");
+    }
+
+    #[test]
+    fn test_tagged_union_selects_branch_by_tag() {
+        // 判别属性的值决定分支，分支类型自身的字段保留该值
+        let started: TestTaggedUnion =
+            serde_json::from_str(r#"{"event":{"eventType":"Started","meterStart":10}}"#).unwrap();
+        assert_eq!(
+            started.event,
+            TestTaggedUnionEventUnionType::StartedEvent(StartedEventType::new(10))
+        );
+        assert!(started.validate().is_ok());
+        let ended: TestTaggedUnion =
+            serde_json::from_str(r#"{"event":{"reason":"Local","eventType":"Ended"}}"#).unwrap();
+        assert_eq!(
+            ended.event,
+            TestTaggedUnionEventUnionType::EndedEvent(EndedEventType::new("Local".to_string()))
+        );
+
+        // 序列化只写一次标签，并能原样读回
+        let json = serde_json::to_string(&started).unwrap();
+        assert_eq!(json, r#"{"event":{"eventType":"Started","meterStart":10}}"#);
+        assert_eq!(serde_json::from_str::<TestTaggedUnion>(&json).unwrap(), started);
+
+        // 未知标签或与标签不符的字段被拒绝
+        assert!(serde_json::from_str::<TestTaggedUnion>(
+            r#"{"event":{"eventType":"Paused","meterStart":10}}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<TestTaggedUnion>(
+            r#"{"event":{"eventType":"Ended","meterStart":10}}"#
+        )
+        .is_err());
+    }
 }
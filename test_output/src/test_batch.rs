@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// TestBatchDataType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestBatchDataType {
+    /// Sample offset in seconds
+    pub t: u32,
+
+    /// Sample value
+    #[validate(length(max = 20))]
+    pub v: String,
+}
+
+impl TestBatchDataType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `t` - Sample offset in seconds
+    /// * `v` - Sample value
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(t: u32, v: String) -> Self {
+        Self { t, v }
+    }
+
+    /// Sets the t field.
+    ///
+    /// * `t` - Sample offset in seconds
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_t(&mut self, t: u32) -> &mut Self {
+        self.t = t;
+        self
+    }
+
+    /// Sets the v field.
+    ///
+    /// * `v` - Sample value
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_v(&mut self, v: String) -> &mut Self {
+        self.v = v;
+        self
+    }
+
+    /// Gets the value of the t field.
+    ///
+    /// # Returns
+    ///
+    /// Sample offset in seconds
+    pub fn get_t(&self) -> u32 {
+        self.t
+    }
+
+    /// Gets a reference to the v field.
+    ///
+    /// # Returns
+    ///
+    /// Sample value
+    pub fn get_v(&self) -> &String {
+        &self.v
+    }
+}
+
+/// TestBatch message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestBatch {
+    /// Batched samples
+    #[validate(length(min = 1))]
+    #[validate(nested)]
+    pub data: Vec<TestBatchDataType>,
+
+    /// Stream id
+    pub id: u32,
+}
+
+impl TestBatch {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `data` - Batched samples
+    /// * `id` - Stream id
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(data: Vec<TestBatchDataType>, id: u32) -> Self {
+        Self { data, id }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestBatch` action name.
+    pub fn action(&self) -> &'static str {
+        "TestBatch"
+    }
+
+    /// Sets the data field.
+    ///
+    /// * `data` - Batched samples
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_data(&mut self, data: Vec<TestBatchDataType>) -> &mut Self {
+        self.data = data;
+        self
+    }
+
+    /// Sets the id field.
+    ///
+    /// * `id` - Stream id
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_id(&mut self, id: u32) -> &mut Self {
+        self.id = id;
+        self
+    }
+
+    /// Gets a reference to the data field.
+    ///
+    /// # Returns
+    ///
+    /// Batched samples
+    pub fn get_data(&self) -> &Vec<TestBatchDataType> {
+        &self.data
+    }
+
+    /// Gets the value of the id field.
+    ///
+    /// # Returns
+    ///
+    /// Stream id
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the number of elements in the data list.
+    ///
+    /// # Returns
+    ///
+    /// The list length, or 0 if the list is not set.
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks whether the data list is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the list has no elements or is not set.
+    pub fn data_is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Appends an element to the data list.
+    ///
+    /// * `item` - The element to append
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn push_data(&mut self, item: TestBatchDataType) -> &mut Self {
+        self.data.push(item);
+        self
+    }
+
+    /// Returns an iterator over the data items of this message.
+    pub fn iter(&self) -> std::slice::Iter<'_, TestBatchDataType> {
+        self.data.iter()
+    }
+}
+
+impl IntoIterator for TestBatch {
+    type Item = TestBatchDataType;
+    type IntoIter = std::vec::IntoIter<TestBatchDataType>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TestBatch {
+    type Item = &'a TestBatchDataType;
+    type IntoIter = std::slice::Iter<'a, TestBatchDataType>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
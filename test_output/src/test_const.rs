@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Checks that `kind` of `TestConst` equals its schema constant "Heartbeat".
+fn validate_test_const_kind_const(value: &str) -> Result<(), validator::ValidationError> {
+    if value != "Heartbeat" {
+        return Err(validator::ValidationError::new("const"));
+    }
+    Ok(())
+}
+
+/// Checks that `version` of `TestConst` equals its schema constant 2.
+fn validate_test_const_version_const(value: i32) -> Result<(), validator::ValidationError> {
+    if value != 2 {
+        return Err(validator::ValidationError::new("const"));
+    }
+    Ok(())
+}
+
+/// TestConst message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestConst {
+    /// Message discriminator
+    #[validate(length(max = 512))]
+    #[validate(custom(function = "validate_test_const_kind_const"))]
+    pub kind: String,
+
+    /// Schema version
+    #[validate(custom(function = "validate_test_const_version_const"))]
+    pub version: i32,
+
+    #[validate(length(max = 10))]
+    pub name: String,
+}
+
+impl TestConst {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `name` - The name field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(name: String) -> Self {
+        Self {
+            kind: "Heartbeat".to_string(),
+            version: 2,
+            name,
+        }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestConst` action name.
+    pub fn action(&self) -> &'static str {
+        "TestConst"
+    }
+
+    /// Sets the kind field.
+    ///
+    /// * `kind` - Message discriminator
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_kind(&mut self, kind: String) -> &mut Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the version field.
+    ///
+    /// * `version` - Schema version
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_version(&mut self, version: i32) -> &mut Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the name field.
+    ///
+    /// * `name` - The name field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_name(&mut self, name: String) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Gets a reference to the kind field.
+    ///
+    /// # Returns
+    ///
+    /// Message discriminator
+    pub fn get_kind(&self) -> &String {
+        &self.kind
+    }
+
+    /// Gets the value of the version field.
+    ///
+    /// # Returns
+    ///
+    /// Schema version
+    pub fn get_version(&self) -> i32 {
+        self.version
+    }
+
+    /// Gets a reference to the name field.
+    ///
+    /// # Returns
+    ///
+    /// The name field
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+}
+
+impl TestConst {
+    /// Returns a builder for incrementally constructing a `TestConst`.
+    pub fn builder() -> TestConstBuilder {
+        TestConstBuilder::default()
+    }
+}
+
+/// Builder for [`TestConst`] that reports missing required fields.
+#[derive(Debug, Clone, Default)]
+pub struct TestConstBuilder {
+    kind: Option<String>,
+    version: Option<i32>,
+    name: Option<String>,
+}
+
+impl TestConstBuilder {
+    /// Sets the kind field.
+    ///
+    /// * `kind` - Message discriminator
+    pub fn kind(mut self, kind: String) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Sets the version field.
+    ///
+    /// * `version` - Schema version
+    pub fn version(mut self, version: i32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Sets the name field.
+    ///
+    /// * `name` - The name field
+    pub fn name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Builds the `TestConst`, failing if a required field is missing or validation fails.
+    pub fn build(self) -> Result<TestConst, String> {
+        let message = TestConst {
+            kind: self.kind.unwrap_or_else(|| "Heartbeat".to_string()),
+            version: self.version.unwrap_or(2),
+            name: self.name.ok_or("missing required field `name`")?,
+        };
+        message.validate().map_err(|e| e.to_string())?;
+        Ok(message)
+    }
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Request body for the TestDispatch request.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestDispatchRequest {
+    /// Identifier of the tag
+    #[validate(length(max = 20))]
+    pub id_tag: String,
+}
+
+impl TestDispatchRequest {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `id_tag` - Identifier of the tag
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(id_tag: String) -> Self {
+        Self { id_tag }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestDispatch` action name.
+    pub fn action(&self) -> &'static str {
+        "TestDispatch"
+    }
+
+    /// Sets the id_tag field.
+    ///
+    /// * `id_tag` - Identifier of the tag
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_id_tag(&mut self, id_tag: String) -> &mut Self {
+        self.id_tag = id_tag;
+        self
+    }
+
+    /// Gets a reference to the id_tag field.
+    ///
+    /// # Returns
+    ///
+    /// Identifier of the tag
+    pub fn get_id_tag(&self) -> &String {
+        &self.id_tag
+    }
+}
+
+/// Response body for the TestDispatch response.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestDispatchResponse {
+    pub accepted: bool,
+}
+
+impl TestDispatchResponse {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `accepted` - The accepted field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(accepted: bool) -> Self {
+        Self { accepted }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestDispatch` action name.
+    pub fn action(&self) -> &'static str {
+        "TestDispatch"
+    }
+
+    /// Sets the accepted field.
+    ///
+    /// * `accepted` - The accepted field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_accepted(&mut self, accepted: bool) -> &mut Self {
+        self.accepted = accepted;
+        self
+    }
+
+    /// Gets the value of the accepted field.
+    ///
+    /// # Returns
+    ///
+    /// The accepted field
+    pub fn get_accepted(&self) -> bool {
+        self.accepted
+    }
+}
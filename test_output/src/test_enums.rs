@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Result of the request
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum TestEnumsStatusEnumType {
+    #[serde(rename = "Accepted")]
+    Accepted,
+    #[serde(rename = "Rejected")]
+    Rejected,
+    #[serde(rename = "ISO15118-20")]
+    Iso1511820,
+}
+
+impl TestEnumsStatusEnumType {
+    /// Every variant, in schema order.
+    pub const ALL: &[Self] = &[Self::Accepted, Self::Rejected, Self::Iso1511820];
+
+    /// Returns the wire value of the variant, as serialized by serde.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Rejected => "Rejected",
+            Self::Iso1511820 => "ISO15118-20",
+        }
+    }
+}
+
+impl std::str::FromStr for TestEnumsStatusEnumType {
+    type Err = serde::de::value::Error;
+
+    /// Parses a wire value, rejecting strings that are not a variant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Accepted" => Ok(Self::Accepted),
+            "Rejected" => Ok(Self::Rejected),
+            "ISO15118-20" => Ok(Self::Iso1511820),
+            other => Err(serde::de::Error::unknown_variant(
+                other,
+                &["Accepted", "Rejected", "ISO15118-20"],
+            )),
+        }
+    }
+}
+
+impl Default for TestEnumsStatusEnumType {
+    /// Returns `Accepted`.
+    fn default() -> Self {
+        Self::Accepted
+    }
+}
+
+impl PartialEq<&str> for TestEnumsStatusEnumType {
+    /// Compares the wire value of the variant with the string.
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for TestEnumsStatusEnumType {
+    /// Compares the wire value of the variant with the string.
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+/// Charging mode
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum TestEnumsModeEnumType {
+    #[serde(rename = "Auto")]
+    Auto,
+    #[serde(rename = "Manual")]
+    Manual,
+}
+
+impl TestEnumsModeEnumType {
+    /// Every variant, in schema order.
+    pub const ALL: &[Self] = &[Self::Auto, Self::Manual];
+
+    /// Returns the wire value of the variant, as serialized by serde.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Manual => "Manual",
+        }
+    }
+}
+
+impl std::str::FromStr for TestEnumsModeEnumType {
+    type Err = serde::de::value::Error;
+
+    /// Parses a wire value, rejecting strings that are not a variant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Auto" => Ok(Self::Auto),
+            "Manual" => Ok(Self::Manual),
+            other => Err(serde::de::Error::unknown_variant(
+                other,
+                &["Auto", "Manual"],
+            )),
+        }
+    }
+}
+
+impl Default for TestEnumsModeEnumType {
+    /// Returns `Manual`.
+    fn default() -> Self {
+        Self::Manual
+    }
+}
+
+impl PartialEq<&str> for TestEnumsModeEnumType {
+    /// Compares the wire value of the variant with the string.
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for TestEnumsModeEnumType {
+    /// Compares the wire value of the variant with the string.
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+/// TestEnums message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestEnums {
+    /// Result of the request
+    pub status: TestEnumsStatusEnumType,
+
+    /// Charging mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<TestEnumsModeEnumType>,
+}
+
+impl TestEnums {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `status` - Result of the request
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(status: TestEnumsStatusEnumType) -> Self {
+        Self { status, mode: None }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestEnums` action name.
+    pub fn action(&self) -> &'static str {
+        "TestEnums"
+    }
+
+    /// Sets the status field.
+    ///
+    /// * `status` - Result of the request
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_status(&mut self, status: TestEnumsStatusEnumType) -> &mut Self {
+        self.status = status;
+        self
+    }
+
+    /// Sets the mode field.
+    ///
+    /// * `mode` - Charging mode
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_mode(&mut self, mode: Option<TestEnumsModeEnumType>) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Gets a reference to the status field.
+    ///
+    /// # Returns
+    ///
+    /// Result of the request
+    pub fn get_status(&self) -> &TestEnumsStatusEnumType {
+        &self.status
+    }
+
+    /// Gets a reference to the mode field.
+    ///
+    /// # Returns
+    ///
+    /// Charging mode
+    pub fn get_mode(&self) -> Option<&TestEnumsModeEnumType> {
+        self.mode.as_ref()
+    }
+
+    /// Sets the mode field and returns self for builder pattern.
+    ///
+    /// * `mode` - Charging mode
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_mode(mut self, mode: TestEnumsModeEnumType) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Clears the mode field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_mode(&mut self) -> &mut Self {
+        self.mode = None;
+        self
+    }
+
+    /// Checks whether the mode field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_mode_set(&self) -> bool {
+        self.mode.is_some()
+    }
+}
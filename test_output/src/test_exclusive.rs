@@ -0,0 +1,119 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Checks `price` of `TestExclusive` against its schema bounds.
+fn validate_test_exclusive_price_range(value: &Decimal) -> Result<(), validator::ValidationError> {
+    if *value <= Decimal::new(5, 1) || *value >= Decimal::new(995, 1) {
+        return Err(validator::ValidationError::new("range"));
+    }
+    Ok(())
+}
+
+/// TestExclusive message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestExclusive {
+    /// Integer strictly between 0 and 10
+    #[validate(range(min = 1, max = 9))]
+    pub count: u32,
+
+    /// Float strictly between 0 and 1
+    #[validate(range(exclusive_min = 0.0, exclusive_max = 1.0))]
+    pub ratio: f64,
+
+    /// Decimal strictly between 0.5 and 99.5
+    #[validate(custom(function = "validate_test_exclusive_price_range"))]
+    pub price: Decimal,
+}
+
+impl TestExclusive {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `count` - Integer strictly between 0 and 10
+    /// * `ratio` - Float strictly between 0 and 1
+    /// * `price` - Decimal strictly between 0.5 and 99.5
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(count: u32, ratio: f64, price: Decimal) -> Self {
+        Self {
+            count,
+            ratio,
+            price,
+        }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestExclusive` action name.
+    pub fn action(&self) -> &'static str {
+        "TestExclusive"
+    }
+
+    /// Sets the count field.
+    ///
+    /// * `count` - Integer strictly between 0 and 10
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_count(&mut self, count: u32) -> &mut Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets the ratio field.
+    ///
+    /// * `ratio` - Float strictly between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_ratio(&mut self, ratio: f64) -> &mut Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the price field.
+    ///
+    /// * `price` - Decimal strictly between 0.5 and 99.5
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_price(&mut self, price: Decimal) -> &mut Self {
+        self.price = price;
+        self
+    }
+
+    /// Gets the value of the count field.
+    ///
+    /// # Returns
+    ///
+    /// Integer strictly between 0 and 10
+    pub fn get_count(&self) -> u32 {
+        self.count
+    }
+
+    /// Gets the value of the ratio field.
+    ///
+    /// # Returns
+    ///
+    /// Float strictly between 0 and 1
+    pub fn get_ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Gets a reference to the price field.
+    ///
+    /// # Returns
+    ///
+    /// Decimal strictly between 0.5 and 99.5
+    pub fn get_price(&self) -> &Decimal {
+        &self.price
+    }
+}
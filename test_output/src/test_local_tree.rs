@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// NodeType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeType {
+    /// Node name
+    #[validate(length(max = 10))]
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub children: Option<Vec<NodeType>>,
+}
+
+impl NodeType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `name` - Node name
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            children: None,
+        }
+    }
+
+    /// Sets the name field.
+    ///
+    /// * `name` - Node name
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_name(&mut self, name: String) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the children field.
+    ///
+    /// * `children` - The children field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_children(&mut self, children: Option<Vec<NodeType>>) -> &mut Self {
+        self.children = children;
+        self
+    }
+
+    /// Gets a reference to the name field.
+    ///
+    /// # Returns
+    ///
+    /// Node name
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Gets a reference to the children field.
+    ///
+    /// # Returns
+    ///
+    /// The children field
+    pub fn get_children(&self) -> Option<&Vec<NodeType>> {
+        self.children.as_ref()
+    }
+
+    /// Sets the children field and returns self for builder pattern.
+    ///
+    /// * `children` - The children field
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_children(mut self, children: Vec<NodeType>) -> Self {
+        self.children = Some(children);
+        self
+    }
+
+    /// Clears the children field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_children(&mut self) -> &mut Self {
+        self.children = None;
+        self
+    }
+
+    /// Checks whether the children field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_children_set(&self) -> bool {
+        self.children.is_some()
+    }
+
+    /// Returns the number of elements in the children list.
+    ///
+    /// # Returns
+    ///
+    /// The list length, or 0 if the list is not set.
+    pub fn children_len(&self) -> usize {
+        self.children.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Checks whether the children list is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the list has no elements or is not set.
+    pub fn children_is_empty(&self) -> bool {
+        self.children.as_ref().is_none_or(Vec::is_empty)
+    }
+
+    /// Appends an element to the children list.
+    ///
+    /// * `item` - The element to append
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn push_children(&mut self, item: NodeType) -> &mut Self {
+        self.children.get_or_insert_with(Vec::new).push(item);
+        self
+    }
+}
+
+/// TestLocalTree message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestLocalTree {
+    #[validate(nested)]
+    pub tree: NodeType,
+}
+
+impl TestLocalTree {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `tree` - The tree field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(tree: NodeType) -> Self {
+        Self { tree }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestLocalTree` action name.
+    pub fn action(&self) -> &'static str {
+        "TestLocalTree"
+    }
+
+    /// Sets the tree field.
+    ///
+    /// * `tree` - The tree field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_tree(&mut self, tree: NodeType) -> &mut Self {
+        self.tree = tree;
+        self
+    }
+
+    /// Gets a reference to the tree field.
+    ///
+    /// # Returns
+    ///
+    /// The tree field
+    pub fn get_tree(&self) -> &NodeType {
+        &self.tree
+    }
+}
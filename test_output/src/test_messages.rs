@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// TestMessages message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMessages {
+    /// Short code
+    #[validate(length(max = 10, message = "code must be at most 10 chars"))]
+    pub code: String,
+
+    /// EVSE number
+    #[validate(range(min = 1, max = 8, message = "evse_id must be between 1 and 8"))]
+    pub evse_id: u32,
+}
+
+impl TestMessages {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `code` - Short code
+    /// * `evse_id` - EVSE number
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(code: String, evse_id: u32) -> Self {
+        Self { code, evse_id }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestMessages` action name.
+    pub fn action(&self) -> &'static str {
+        "TestMessages"
+    }
+
+    /// Sets the code field.
+    ///
+    /// * `code` - Short code
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_code(&mut self, code: String) -> &mut Self {
+        self.code = code;
+        self
+    }
+
+    /// Sets the evse_id field.
+    ///
+    /// * `evse_id` - EVSE number
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_evse_id(&mut self, evse_id: u32) -> &mut Self {
+        self.evse_id = evse_id;
+        self
+    }
+
+    /// Gets a reference to the code field.
+    ///
+    /// # Returns
+    ///
+    /// Short code
+    pub fn get_code(&self) -> &String {
+        &self.code
+    }
+
+    /// Gets the value of the evse_id field.
+    ///
+    /// # Returns
+    ///
+    /// EVSE number
+    pub fn get_evse_id(&self) -> u32 {
+        self.evse_id
+    }
+}
@@ -0,0 +1,207 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Case insensitive string of at most 5 characters
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct CiString5Type(pub String);
+
+impl Validate for CiString5Type {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        let value = &self.0;
+        if value.chars().count() > 5 {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add("0", validator::ValidationError::new("length"));
+            return Err(errors);
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for CiString5Type {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.0
+    }
+}
+
+impl From<String> for CiString5Type {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for CiString5Type {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+/// Meter reading that exceeds the i32 range
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct MeterValueType(pub i64);
+
+impl Validate for MeterValueType {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        let value = &self.0;
+        if *value > 4294967295 {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add("0", validator::ValidationError::new("range"));
+            return Err(errors);
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for MeterValueType {
+    type Target = i64;
+
+    fn deref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl From<i64> for MeterValueType {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+/// Price above zero, at most 99.5
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PriceType(pub Decimal);
+
+impl Validate for PriceType {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        let value = &self.0;
+        if *value <= Decimal::new(0, 0) || *value > Decimal::new(995, 1) {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add("0", validator::ValidationError::new("range"));
+            return Err(errors);
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for PriceType {
+    type Target = Decimal;
+
+    fn deref(&self) -> &Decimal {
+        &self.0
+    }
+}
+
+impl From<Decimal> for PriceType {
+    fn from(value: Decimal) -> Self {
+        Self(value)
+    }
+}
+
+/// TestNewtypes message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestNewtypes {
+    #[validate(nested)]
+    pub id_tag: CiString5Type,
+
+    #[validate(nested)]
+    pub meter: MeterValueType,
+
+    #[validate(nested)]
+    pub price: PriceType,
+}
+
+impl TestNewtypes {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `id_tag` - The id_tag field
+    /// * `meter` - The meter field
+    /// * `price` - The price field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(id_tag: CiString5Type, meter: MeterValueType, price: PriceType) -> Self {
+        Self {
+            id_tag,
+            meter,
+            price,
+        }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestNewtypes` action name.
+    pub fn action(&self) -> &'static str {
+        "TestNewtypes"
+    }
+
+    /// Sets the id_tag field.
+    ///
+    /// * `id_tag` - The id_tag field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_id_tag(&mut self, id_tag: CiString5Type) -> &mut Self {
+        self.id_tag = id_tag;
+        self
+    }
+
+    /// Sets the meter field.
+    ///
+    /// * `meter` - The meter field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_meter(&mut self, meter: MeterValueType) -> &mut Self {
+        self.meter = meter;
+        self
+    }
+
+    /// Sets the price field.
+    ///
+    /// * `price` - The price field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_price(&mut self, price: PriceType) -> &mut Self {
+        self.price = price;
+        self
+    }
+
+    /// Gets a reference to the id_tag field.
+    ///
+    /// # Returns
+    ///
+    /// The id_tag field
+    pub fn get_id_tag(&self) -> &CiString5Type {
+        &self.id_tag
+    }
+
+    /// Gets a reference to the meter field.
+    ///
+    /// # Returns
+    ///
+    /// The meter field
+    pub fn get_meter(&self) -> &MeterValueType {
+        &self.meter
+    }
+
+    /// Gets a reference to the price field.
+    ///
+    /// # Returns
+    ///
+    /// The price field
+    pub fn get_price(&self) -> &PriceType {
+        &self.price
+    }
+}
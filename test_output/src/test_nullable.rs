@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// TestNullable message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestNullable {
+    /// Required, but may be null
+    #[serde(deserialize_with = "Option::deserialize")]
+    #[validate(range(max = 10))]
+    pub count: Option<u32>,
+
+    /// Optional and omitted when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 20))]
+    pub note: Option<String>,
+}
+
+impl TestNullable {
+    /// Creates a new instance of the struct.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new() -> Self {
+        Self {
+            count: None,
+            note: None,
+        }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestNullable` action name.
+    pub fn action(&self) -> &'static str {
+        "TestNullable"
+    }
+
+    /// Sets the count field.
+    ///
+    /// * `count` - Required, but may be null
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_count(&mut self, count: Option<u32>) -> &mut Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets the note field.
+    ///
+    /// * `note` - Optional and omitted when unset
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_note(&mut self, note: Option<String>) -> &mut Self {
+        self.note = note;
+        self
+    }
+
+    /// Gets the value of the count field.
+    ///
+    /// # Returns
+    ///
+    /// Required, but may be null
+    pub fn get_count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Gets a reference to the note field.
+    ///
+    /// # Returns
+    ///
+    /// Optional and omitted when unset
+    pub fn get_note(&self) -> Option<&String> {
+        self.note.as_ref()
+    }
+
+    /// Sets the count field and returns self for builder pattern.
+    ///
+    /// * `count` - Required, but may be null
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_count(mut self, count: u32) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Sets the note field and returns self for builder pattern.
+    ///
+    /// * `note` - Optional and omitted when unset
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_note(mut self, note: String) -> Self {
+        self.note = Some(note);
+        self
+    }
+
+    /// Clears the count field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_count(&mut self) -> &mut Self {
+        self.count = None;
+        self
+    }
+
+    /// Checks whether the count field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_count_set(&self) -> bool {
+        self.count.is_some()
+    }
+
+    /// Clears the note field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_note(&mut self) -> &mut Self {
+        self.note = None;
+        self
+    }
+
+    /// Checks whether the note field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_note_set(&self) -> bool {
+        self.note.is_some()
+    }
+}
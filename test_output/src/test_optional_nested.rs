@@ -0,0 +1,229 @@
+use super::errors::FromValueError;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// TestOptionalNestedEntriesType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestOptionalNestedEntriesType {
+    /// Entry name
+    #[validate(length(min = 1, max = 10))]
+    pub name: String,
+
+    /// Entry value
+    #[validate(range(max = 100))]
+    pub value: u32,
+}
+
+impl TestOptionalNestedEntriesType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `name` - Entry name
+    /// * `value` - Entry value
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(name: String, value: u32) -> Self {
+        Self { name, value }
+    }
+
+    /// Sets the name field.
+    ///
+    /// * `name` - Entry name
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_name(&mut self, name: String) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the value field.
+    ///
+    /// * `value` - Entry value
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_value(&mut self, value: u32) -> &mut Self {
+        self.value = value;
+        self
+    }
+
+    /// Gets a reference to the name field.
+    ///
+    /// # Returns
+    ///
+    /// Entry name
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Gets the value of the value field.
+    ///
+    /// # Returns
+    ///
+    /// Entry value
+    pub fn get_value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// TestOptionalNested message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestOptionalNested {
+    /// Required label next to the optional array
+    #[validate(length(max = 20))]
+    pub label: String,
+
+    /// Optional array whose elements are validated as well
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1, max = 3))]
+    #[validate(nested)]
+    pub entries: Option<Vec<TestOptionalNestedEntriesType>>,
+}
+
+impl TestOptionalNested {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `label` - Required label next to the optional array
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            entries: None,
+        }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestOptionalNested` action name.
+    pub fn action(&self) -> &'static str {
+        "TestOptionalNested"
+    }
+
+    /// Sets the label field.
+    ///
+    /// * `label` - Required label next to the optional array
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_label(&mut self, label: String) -> &mut Self {
+        self.label = label;
+        self
+    }
+
+    /// Sets the entries field.
+    ///
+    /// * `entries` - Optional array whose elements are validated as well
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_entries(
+        &mut self,
+        entries: Option<Vec<TestOptionalNestedEntriesType>>,
+    ) -> &mut Self {
+        self.entries = entries;
+        self
+    }
+
+    /// Gets a reference to the label field.
+    ///
+    /// # Returns
+    ///
+    /// Required label next to the optional array
+    pub fn get_label(&self) -> &String {
+        &self.label
+    }
+
+    /// Gets a reference to the entries field.
+    ///
+    /// # Returns
+    ///
+    /// Optional array whose elements are validated as well
+    pub fn get_entries(&self) -> Option<&Vec<TestOptionalNestedEntriesType>> {
+        self.entries.as_ref()
+    }
+
+    /// Sets the entries field and returns self for builder pattern.
+    ///
+    /// * `entries` - Optional array whose elements are validated as well
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_entries(mut self, entries: Vec<TestOptionalNestedEntriesType>) -> Self {
+        self.entries = Some(entries);
+        self
+    }
+
+    /// Clears the entries field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_entries(&mut self) -> &mut Self {
+        self.entries = None;
+        self
+    }
+
+    /// Checks whether the entries field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_entries_set(&self) -> bool {
+        self.entries.is_some()
+    }
+
+    /// Returns the number of elements in the entries list.
+    ///
+    /// # Returns
+    ///
+    /// The list length, or 0 if the list is not set.
+    pub fn entries_len(&self) -> usize {
+        self.entries.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Checks whether the entries list is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the list has no elements or is not set.
+    pub fn entries_is_empty(&self) -> bool {
+        self.entries.as_ref().is_none_or(Vec::is_empty)
+    }
+
+    /// Appends an element to the entries list.
+    ///
+    /// * `item` - The element to append
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn push_entries(&mut self, item: TestOptionalNestedEntriesType) -> &mut Self {
+        self.entries.get_or_insert_with(Vec::new).push(item);
+        self
+    }
+}
+
+impl TryFrom<serde_json::Value> for TestOptionalNested {
+    type Error = FromValueError;
+
+    /// Deserializes the message from a JSON value and validates it.
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let message: Self = serde_json::from_value(value).map_err(FromValueError::Json)?;
+        message.validate().map_err(FromValueError::Validation)?;
+        Ok(message)
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// TestPermissiveVendorDataType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestPermissiveVendorDataType {
+    #[validate(length(max = 255))]
+    pub vendor_id: String,
+}
+
+impl TestPermissiveVendorDataType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `vendor_id` - The vendor_id field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(vendor_id: String) -> Self {
+        Self { vendor_id }
+    }
+
+    /// Sets the vendor_id field.
+    ///
+    /// * `vendor_id` - The vendor_id field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_vendor_id(&mut self, vendor_id: String) -> &mut Self {
+        self.vendor_id = vendor_id;
+        self
+    }
+
+    /// Gets a reference to the vendor_id field.
+    ///
+    /// # Returns
+    ///
+    /// The vendor_id field
+    pub fn get_vendor_id(&self) -> &String {
+        &self.vendor_id
+    }
+}
+
+/// TestPermissive message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestPermissive {
+    /// Identifier of the tag
+    #[validate(length(max = 20))]
+    pub id_tag: String,
+
+    /// Extensible vendor object
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub vendor_data: Option<TestPermissiveVendorDataType>,
+}
+
+impl TestPermissive {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `id_tag` - Identifier of the tag
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(id_tag: String) -> Self {
+        Self {
+            id_tag,
+            vendor_data: None,
+        }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestPermissive` action name.
+    pub fn action(&self) -> &'static str {
+        "TestPermissive"
+    }
+
+    /// Sets the id_tag field.
+    ///
+    /// * `id_tag` - Identifier of the tag
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_id_tag(&mut self, id_tag: String) -> &mut Self {
+        self.id_tag = id_tag;
+        self
+    }
+
+    /// Sets the vendor_data field.
+    ///
+    /// * `vendor_data` - Extensible vendor object
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_vendor_data(
+        &mut self,
+        vendor_data: Option<TestPermissiveVendorDataType>,
+    ) -> &mut Self {
+        self.vendor_data = vendor_data;
+        self
+    }
+
+    /// Gets a reference to the id_tag field.
+    ///
+    /// # Returns
+    ///
+    /// Identifier of the tag
+    pub fn get_id_tag(&self) -> &String {
+        &self.id_tag
+    }
+
+    /// Gets a reference to the vendor_data field.
+    ///
+    /// # Returns
+    ///
+    /// Extensible vendor object
+    pub fn get_vendor_data(&self) -> Option<&TestPermissiveVendorDataType> {
+        self.vendor_data.as_ref()
+    }
+
+    /// Sets the vendor_data field and returns self for builder pattern.
+    ///
+    /// * `vendor_data` - Extensible vendor object
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_vendor_data(mut self, vendor_data: TestPermissiveVendorDataType) -> Self {
+        self.vendor_data = Some(vendor_data);
+        self
+    }
+
+    /// Clears the vendor_data field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_vendor_data(&mut self) -> &mut Self {
+        self.vendor_data = None;
+        self
+    }
+
+    /// Checks whether the vendor_data field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_vendor_data_set(&self) -> bool {
+        self.vendor_data.is_some()
+    }
+}
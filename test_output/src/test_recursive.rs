@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// TestRecursive message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestRecursive {
+    /// Node name
+    #[validate(length(max = 10))]
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub parent: Option<Box<TestRecursive>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub children: Option<Vec<TestRecursive>>,
+}
+
+impl TestRecursive {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `name` - Node name
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            parent: None,
+            children: None,
+        }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestRecursive` action name.
+    pub fn action(&self) -> &'static str {
+        "TestRecursive"
+    }
+
+    /// Sets the name field.
+    ///
+    /// * `name` - Node name
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_name(&mut self, name: String) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the parent field.
+    ///
+    /// * `parent` - The parent field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_parent(&mut self, parent: Option<Box<TestRecursive>>) -> &mut Self {
+        self.parent = parent;
+        self
+    }
+
+    /// Sets the children field.
+    ///
+    /// * `children` - The children field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_children(&mut self, children: Option<Vec<TestRecursive>>) -> &mut Self {
+        self.children = children;
+        self
+    }
+
+    /// Gets a reference to the name field.
+    ///
+    /// # Returns
+    ///
+    /// Node name
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Gets a reference to the parent field.
+    ///
+    /// # Returns
+    ///
+    /// The parent field
+    pub fn get_parent(&self) -> Option<&Box<TestRecursive>> {
+        self.parent.as_ref()
+    }
+
+    /// Gets a reference to the children field.
+    ///
+    /// # Returns
+    ///
+    /// The children field
+    pub fn get_children(&self) -> Option<&Vec<TestRecursive>> {
+        self.children.as_ref()
+    }
+
+    /// Sets the parent field and returns self for builder pattern.
+    ///
+    /// * `parent` - The parent field
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_parent(mut self, parent: Box<TestRecursive>) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Sets the children field and returns self for builder pattern.
+    ///
+    /// * `children` - The children field
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_children(mut self, children: Vec<TestRecursive>) -> Self {
+        self.children = Some(children);
+        self
+    }
+
+    /// Clears the parent field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_parent(&mut self) -> &mut Self {
+        self.parent = None;
+        self
+    }
+
+    /// Checks whether the parent field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_parent_set(&self) -> bool {
+        self.parent.is_some()
+    }
+
+    /// Clears the children field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_children(&mut self) -> &mut Self {
+        self.children = None;
+        self
+    }
+
+    /// Checks whether the children field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_children_set(&self) -> bool {
+        self.children.is_some()
+    }
+
+    /// Returns the number of elements in the children list.
+    ///
+    /// # Returns
+    ///
+    /// The list length, or 0 if the list is not set.
+    pub fn children_len(&self) -> usize {
+        self.children.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Checks whether the children list is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the list has no elements or is not set.
+    pub fn children_is_empty(&self) -> bool {
+        self.children.as_ref().is_none_or(Vec::is_empty)
+    }
+
+    /// Appends an element to the children list.
+    ///
+    /// * `item` - The element to append
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn push_children(&mut self, item: TestRecursive) -> &mut Self {
+        self.children.get_or_insert_with(Vec::new).push(item);
+        self
+    }
+}
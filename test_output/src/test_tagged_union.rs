@@ -0,0 +1,135 @@
+use crate::v2_1::datatypes::{EndedEventType, StartedEventType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Alternative shapes of `TestTaggedUnionEventUnionType`, tried in order.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "eventType")]
+pub enum TestTaggedUnionEventUnionType {
+    #[serde(rename = "Started")]
+    StartedEvent(
+        #[serde(
+            deserialize_with = "test_tagged_union_event_union_type_started_event",
+            serialize_with = "test_tagged_union_event_union_type_without_tag"
+        )]
+        StartedEventType,
+    ),
+    #[serde(rename = "Ended")]
+    EndedEvent(
+        #[serde(
+            deserialize_with = "test_tagged_union_event_union_type_ended_event",
+            serialize_with = "test_tagged_union_event_union_type_without_tag"
+        )]
+        EndedEventType,
+    ),
+}
+
+/// Deserializes a `TestTaggedUnionEventUnionType` branch, restoring the `eventType` tag consumed by serde.
+fn test_tagged_union_event_union_type_with_tag<'de, D, T>(
+    deserializer: D,
+    value: &str,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let mut fields = serde_json::Map::deserialize(deserializer)?;
+    fields.insert("eventType".to_string(), value.into());
+    serde_json::from_value(fields.into()).map_err(serde::de::Error::custom)
+}
+
+/// Deserializes the `Started` branch of `TestTaggedUnionEventUnionType`.
+fn test_tagged_union_event_union_type_started_event<'de, D>(
+    deserializer: D,
+) -> Result<StartedEventType, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    test_tagged_union_event_union_type_with_tag(deserializer, "Started")
+}
+
+/// Deserializes the `Ended` branch of `TestTaggedUnionEventUnionType`.
+fn test_tagged_union_event_union_type_ended_event<'de, D>(
+    deserializer: D,
+) -> Result<EndedEventType, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    test_tagged_union_event_union_type_with_tag(deserializer, "Ended")
+}
+
+/// Serializes a `TestTaggedUnionEventUnionType` branch without its own `eventType` field, which serde writes as the tag.
+fn test_tagged_union_event_union_type_without_tag<T, S>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: serde::Serializer,
+{
+    let mut value = serde_json::to_value(value).map_err(serde::ser::Error::custom)?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("eventType");
+    }
+    value.serialize(serializer)
+}
+
+impl Validate for TestTaggedUnionEventUnionType {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            Self::StartedEvent(value) => value.validate(),
+            Self::EndedEvent(value) => value.validate(),
+        }
+    }
+}
+
+/// TestTaggedUnion message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestTaggedUnion {
+    #[validate(nested)]
+    pub event: TestTaggedUnionEventUnionType,
+}
+
+impl TestTaggedUnion {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `event` - The event field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(event: TestTaggedUnionEventUnionType) -> Self {
+        Self { event }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestTaggedUnion` action name.
+    pub fn action(&self) -> &'static str {
+        "TestTaggedUnion"
+    }
+
+    /// Sets the event field.
+    ///
+    /// * `event` - The event field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_event(&mut self, event: TestTaggedUnionEventUnionType) -> &mut Self {
+        self.event = event;
+        self
+    }
+
+    /// Gets a reference to the event field.
+    ///
+    /// # Returns
+    ///
+    /// The event field
+    pub fn get_event(&self) -> &TestTaggedUnionEventUnionType {
+        &self.event
+    }
+}
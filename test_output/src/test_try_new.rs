@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// TestTryNew message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestTryNew {
+    /// Identifier of the tag
+    #[validate(length(max = 20))]
+    pub id_tag: String,
+
+    /// Connector number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connector_id: Option<u32>,
+}
+
+impl TestTryNew {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `id_tag` - Identifier of the tag
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(id_tag: String) -> Self {
+        Self {
+            id_tag,
+            connector_id: None,
+        }
+    }
+
+    /// Creates a new instance of the struct and validates it.
+    ///
+    /// * `id_tag` - Identifier of the tag
+    ///
+    /// # Returns
+    ///
+    /// The new instance, or the validation errors of its required fields.
+    pub fn try_new(id_tag: String) -> Result<Self, validator::ValidationErrors> {
+        let message = Self::new(id_tag);
+        message.validate()?;
+        Ok(message)
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestTryNew` action name.
+    pub fn action(&self) -> &'static str {
+        "TestTryNew"
+    }
+
+    /// Sets the id_tag field.
+    ///
+    /// * `id_tag` - Identifier of the tag
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_id_tag(&mut self, id_tag: String) -> &mut Self {
+        self.id_tag = id_tag;
+        self
+    }
+
+    /// Sets the connector_id field.
+    ///
+    /// * `connector_id` - Connector number
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_connector_id(&mut self, connector_id: Option<u32>) -> &mut Self {
+        self.connector_id = connector_id;
+        self
+    }
+
+    /// Gets a reference to the id_tag field.
+    ///
+    /// # Returns
+    ///
+    /// Identifier of the tag
+    pub fn get_id_tag(&self) -> &String {
+        &self.id_tag
+    }
+
+    /// Gets the value of the connector_id field.
+    ///
+    /// # Returns
+    ///
+    /// Connector number
+    pub fn get_connector_id(&self) -> Option<u32> {
+        self.connector_id
+    }
+
+    /// Sets the connector_id field and returns self for builder pattern.
+    ///
+    /// * `connector_id` - Connector number
+    ///
+    /// # Returns
+    ///
+    /// Self with the field set.
+    pub fn with_connector_id(mut self, connector_id: u32) -> Self {
+        self.connector_id = Some(connector_id);
+        self
+    }
+
+    /// Clears the connector_id field.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn unset_connector_id(&mut self) -> &mut Self {
+        self.connector_id = None;
+        self
+    }
+
+    /// Checks whether the connector_id field is set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field has a value.
+    pub fn is_connector_id_set(&self) -> bool {
+        self.connector_id.is_some()
+    }
+}
@@ -0,0 +1,78 @@
+use crate::v2_1::datatypes::IdTokenType;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Alternative shapes of `TestUnionTokenUnionType`, tried in order.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TestUnionTokenUnionType {
+    IdToken(IdTokenType),
+    String(String),
+}
+
+impl Validate for TestUnionTokenUnionType {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            Self::IdToken(value) => value.validate(),
+            Self::String(value) => {
+                if value.chars().count() > 36 {
+                    let mut errors = validator::ValidationErrors::new();
+                    errors.add("String", validator::ValidationError::new("length"));
+                    return Err(errors);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// TestUnion message structure.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TestUnion {
+    #[validate(nested)]
+    pub token: TestUnionTokenUnionType,
+}
+
+impl TestUnion {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `token` - The token field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(token: TestUnionTokenUnionType) -> Self {
+        Self { token }
+    }
+
+    /// Returns the OCPP action name of this message.
+    ///
+    /// # Returns
+    ///
+    /// The `TestUnion` action name.
+    pub fn action(&self) -> &'static str {
+        "TestUnion"
+    }
+
+    /// Sets the token field.
+    ///
+    /// * `token` - The token field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_token(&mut self, token: TestUnionTokenUnionType) -> &mut Self {
+        self.token = token;
+        self
+    }
+
+    /// Gets a reference to the token field.
+    ///
+    /// # Returns
+    ///
+    /// The token field
+    pub fn get_token(&self) -> &TestUnionTokenUnionType {
+        &self.token
+    }
+}
@@ -0,0 +1,309 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Result of an authorization
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum AuthorizationStatusEnumType {
+    #[serde(rename = "Accepted")]
+    Accepted,
+    #[serde(rename = "Blocked")]
+    Blocked,
+    #[serde(rename = "Invalid")]
+    Invalid,
+}
+
+impl AuthorizationStatusEnumType {
+    /// Returns the wire value of the variant, as serialized by serde.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Blocked => "Blocked",
+            Self::Invalid => "Invalid",
+        }
+    }
+}
+
+impl std::str::FromStr for AuthorizationStatusEnumType {
+    type Err = serde::de::value::Error;
+
+    /// Parses a wire value, rejecting strings that are not a variant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Accepted" => Ok(Self::Accepted),
+            "Blocked" => Ok(Self::Blocked),
+            "Invalid" => Ok(Self::Invalid),
+            other => Err(serde::de::Error::unknown_variant(
+                other,
+                &["Accepted", "Blocked", "Invalid"],
+            )),
+        }
+    }
+}
+
+/// IdTokenType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct IdTokenType {
+    #[validate(length(max = 36))]
+    pub id_token: String,
+
+    #[serde(rename = "type")]
+    #[validate(length(max = 20))]
+    pub type_: String,
+}
+
+impl IdTokenType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `id_token` - The id_token field
+    /// * `type_` - The type_ field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(id_token: String, type_: String) -> Self {
+        Self { id_token, type_ }
+    }
+
+    /// Sets the id_token field.
+    ///
+    /// * `id_token` - The id_token field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_id_token(&mut self, id_token: String) -> &mut Self {
+        self.id_token = id_token;
+        self
+    }
+
+    /// Sets the type_ field.
+    ///
+    /// * `type_` - The type_ field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_type_(&mut self, type_: String) -> &mut Self {
+        self.type_ = type_;
+        self
+    }
+
+    /// Gets a reference to the id_token field.
+    ///
+    /// # Returns
+    ///
+    /// The id_token field
+    pub fn get_id_token(&self) -> &String {
+        &self.id_token
+    }
+
+    /// Gets a reference to the type_ field.
+    ///
+    /// # Returns
+    ///
+    /// The type_ field
+    pub fn get_type_(&self) -> &String {
+        &self.type_
+    }
+}
+
+/// IdTokenInfoType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct IdTokenInfoType {
+    pub status: AuthorizationStatusEnumType,
+}
+
+impl IdTokenInfoType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `status` - The status field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(status: AuthorizationStatusEnumType) -> Self {
+        Self { status }
+    }
+
+    /// Sets the status field.
+    ///
+    /// * `status` - The status field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_status(&mut self, status: AuthorizationStatusEnumType) -> &mut Self {
+        self.status = status;
+        self
+    }
+
+    /// Gets a reference to the status field.
+    ///
+    /// # Returns
+    ///
+    /// The status field
+    pub fn get_status(&self) -> &AuthorizationStatusEnumType {
+        &self.status
+    }
+}
+
+/// Checks that `event_type` of `StartedEventType` equals its schema constant "Started".
+fn validate_started_event_type_event_type_const(
+    value: &str,
+) -> Result<(), validator::ValidationError> {
+    if value != "Started" {
+        return Err(validator::ValidationError::new("const"));
+    }
+    Ok(())
+}
+
+/// StartedEventType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct StartedEventType {
+    #[validate(length(max = 512))]
+    #[validate(custom(function = "validate_started_event_type_event_type_const"))]
+    pub event_type: String,
+
+    pub meter_start: u32,
+}
+
+impl StartedEventType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `meter_start` - The meter_start field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(meter_start: u32) -> Self {
+        Self {
+            event_type: "Started".to_string(),
+            meter_start,
+        }
+    }
+
+    /// Sets the event_type field.
+    ///
+    /// * `event_type` - The event_type field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_event_type(&mut self, event_type: String) -> &mut Self {
+        self.event_type = event_type;
+        self
+    }
+
+    /// Sets the meter_start field.
+    ///
+    /// * `meter_start` - The meter_start field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_meter_start(&mut self, meter_start: u32) -> &mut Self {
+        self.meter_start = meter_start;
+        self
+    }
+
+    /// Gets a reference to the event_type field.
+    ///
+    /// # Returns
+    ///
+    /// The event_type field
+    pub fn get_event_type(&self) -> &String {
+        &self.event_type
+    }
+
+    /// Gets the value of the meter_start field.
+    ///
+    /// # Returns
+    ///
+    /// The meter_start field
+    pub fn get_meter_start(&self) -> u32 {
+        self.meter_start
+    }
+}
+
+/// Checks that `event_type` of `EndedEventType` equals its schema constant "Ended".
+fn validate_ended_event_type_event_type_const(
+    value: &str,
+) -> Result<(), validator::ValidationError> {
+    if value != "Ended" {
+        return Err(validator::ValidationError::new("const"));
+    }
+    Ok(())
+}
+
+/// EndedEventType data type.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct EndedEventType {
+    #[validate(length(max = 512))]
+    #[validate(custom(function = "validate_ended_event_type_event_type_const"))]
+    pub event_type: String,
+
+    #[validate(length(max = 20))]
+    pub reason: String,
+}
+
+impl EndedEventType {
+    /// Creates a new instance of the struct.
+    ///
+    /// * `reason` - The reason field
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the struct with required fields set and optional fields as None.
+    pub fn new(reason: String) -> Self {
+        Self {
+            event_type: "Ended".to_string(),
+            reason,
+        }
+    }
+
+    /// Sets the event_type field.
+    ///
+    /// * `event_type` - The event_type field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_event_type(&mut self, event_type: String) -> &mut Self {
+        self.event_type = event_type;
+        self
+    }
+
+    /// Sets the reason field.
+    ///
+    /// * `reason` - The reason field
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to self for method chaining.
+    pub fn set_reason(&mut self, reason: String) -> &mut Self {
+        self.reason = reason;
+        self
+    }
+
+    /// Gets a reference to the event_type field.
+    ///
+    /// # Returns
+    ///
+    /// The event_type field
+    pub fn get_event_type(&self) -> &String {
+        &self.event_type
+    }
+
+    /// Gets a reference to the reason field.
+    ///
+    /// # Returns
+    ///
+    /// The reason field
+    pub fn get_reason(&self) -> &String {
+        &self.reason
+    }
+}
@@ -0,0 +1,2 @@
+//! Shared datatypes imported by the generated messages, generated from `test_schemas/TestDatatypes.json`.
+pub mod datatypes;